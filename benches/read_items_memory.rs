@@ -0,0 +1,136 @@
+//! Peak memory of reading items, the default path against `read_items_direct`.
+//!
+//! Run with `cargo bench --bench read_items_memory`. A local server answers every call with the
+//! same 10 items with large content, and a counting allocator records the most memory that was in
+//! use during each read. The server writes a body that was built up front and allocates next to
+//! nothing, so the peaks are the client's.
+use serde_json::json;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use yupdates::api::ReadOptions;
+use yupdates::clients::AsyncYupdatesClient;
+
+const FEED_ID: &str = "02fb24a4478462a4491067224b66d9a8b2338ddca2737";
+const CONTENT_BYTES: usize = 200_000;
+const RUNS: usize = 5;
+
+struct CountingAllocator;
+
+static IN_USE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let in_use = IN_USE.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(in_use, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        IN_USE.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// The most memory in use while `read` ran, above what was in use before it
+async fn peak_bytes<F, Fut>(read: F) -> usize
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = usize>,
+{
+    let mut peaks = Vec::new();
+    for _ in 0..RUNS {
+        let before = IN_USE.load(Ordering::SeqCst);
+        PEAK.store(before, Ordering::SeqCst);
+        let items = read().await;
+        assert_eq!(items, 10);
+        peaks.push(PEAK.load(Ordering::SeqCst) - before);
+    }
+    peaks.sort_unstable();
+    peaks[RUNS / 2]
+}
+
+// Answers each connection with `body`, whatever the request was
+fn serve(listener: TcpListener, body: Arc<Vec<u8>>) {
+    let head = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\
+         connection: close\r\n\r\n",
+        body.len()
+    );
+    for stream in listener.incoming() {
+        let mut stream = stream.unwrap();
+        // The request has no body, so it ends with the first empty line
+        let mut request = [0u8; 4096];
+        let mut len = 0;
+        while !request[..len].windows(4).any(|w| w == b"\r\n\r\n") {
+            len += stream.read(&mut request[len..]).unwrap();
+        }
+        stream.write_all(head.as_bytes()).unwrap();
+        stream.write_all(&body).unwrap();
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let feed_items: Vec<_> = (1..=10u64)
+        .rev()
+        .map(|n| {
+            json!({
+                "feed_id": FEED_ID,
+                "item_id": format!("item{}", n),
+                "input_id": format!("input{}", n),
+                "title": format!("title-{}", n),
+                "content": "<p>lorem ipsum</p>".repeat(CONTENT_BYTES / 18),
+                "canonical_url": format!("https://www.example.com/{}", n),
+                "item_time": format!("{:0>13}.00000", 1661564013000 + n),
+                "item_time_ms": 1661564013000 + n,
+                "deleted": false,
+                "associated_files": null
+            })
+        })
+        .collect();
+    let body = serde_json::to_vec(&json!({"code": 200, "feed_items": feed_items})).unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/", listener.local_addr().unwrap());
+    let body = Arc::new(body);
+    thread::spawn(move || serve(listener, body));
+
+    let yup = AsyncYupdatesClient {
+        base_url,
+        http_client: Default::default(),
+        token: "mock-token".to_string(),
+        extensions: Default::default(),
+    };
+    let options = ReadOptions {
+        max_items: 10,
+        include_item_content: true,
+        ..Default::default()
+    };
+
+    let default_peak = peak_bytes(|| async {
+        let items = yup.read_items_with_options(FEED_ID, &options).await;
+        items.unwrap().len()
+    })
+    .await;
+    let direct_peak = peak_bytes(|| async {
+        let items = yup.read_items_direct(FEED_ID, &options).await;
+        items.unwrap().len()
+    })
+    .await;
+
+    let body_bytes = 10 * CONTENT_BYTES;
+    println!("response body:      about {} KiB", body_bytes / 1024);
+    println!("read_items peak:    {} KiB", default_peak / 1024);
+    println!("read_items_direct:  {} KiB", direct_peak / 1024);
+    assert!(direct_peak < default_peak);
+}
@@ -0,0 +1,49 @@
+//! Construction time of a [SharedSyncClient] handle against a [SyncYupdatesClient] with its own
+//! runtime.
+//!
+//! Run with `cargo bench --bench shared_client`. No calls are made, only the clients are created
+//! and dropped, so the times are the runtime and HTTP client setup.
+//!
+//! [SharedSyncClient]: yupdates::clients::sync::SharedSyncClient
+//! [SyncYupdatesClient]: yupdates::clients::sync::SyncYupdatesClient
+use std::time::Instant;
+use tokio::runtime::Runtime;
+use yupdates::clients::sync::{shared_client_with_args, shutdown_shared, SyncYupdatesClient};
+use yupdates::clients::AsyncYupdatesClient;
+
+const BASE_URL: &str = "http://localhost/";
+const TOKEN: &str = "mock-token";
+const ITERATIONS: u32 = 50;
+
+fn main() {
+    // The first call pays for the shared runtime and HTTP client
+    let start = Instant::now();
+    drop(shared_client_with_args(BASE_URL.to_string(), TOKEN.to_string()).unwrap());
+    let first_shared = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let client = SyncYupdatesClient {
+            client: AsyncYupdatesClient {
+                base_url: BASE_URL.to_string(),
+                http_client: Default::default(),
+                token: TOKEN.to_string(),
+                extensions: Default::default(),
+            },
+            rt: Runtime::new().unwrap(),
+        };
+        drop(client);
+    }
+    let per_instance = start.elapsed() / ITERATIONS;
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        drop(shared_client_with_args(BASE_URL.to_string(), TOKEN.to_string()).unwrap());
+    }
+    let shared = start.elapsed() / ITERATIONS;
+    shutdown_shared();
+
+    println!("first shared handle:   {:?}", first_shared);
+    println!("per-instance client:   {:?} each", per_instance);
+    println!("shared handle:         {:?} each", shared);
+}
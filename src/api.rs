@@ -8,30 +8,342 @@
 //! Calling the stateless functions in this module (for example, `read_items`) will instantiate an
 //! HTTP client each time. That is convenient for one-off usages, but the client wrappers give you
 //! a convenient way to only do that work once.
-use crate::errors::{api_error, Error, Kind, Result};
-use crate::models::{FeedItem, InputItem};
-use crate::{api_token, env_or_default_url, normalize_item_time, X_AUTH_TOKEN_HEADER};
+use crate::compression::encode_body;
+use crate::errors::{
+    api_error, api_error_with_headers, msg_from_api_error_data, ApiErrorData, BulkError, Error,
+    Kind, Result,
+};
+#[cfg(feature = "csv")]
+use crate::items::{read_csv, CsvMapping};
+use crate::limits::{field_size_problems, size_problems};
+use crate::models::{
+    AssociatedFile, FeedId, FeedInfo, FeedItem, InputItem, ItemId, ItemPatch, FEED_ITEM_FIELDS,
+    FEED_ITEM_REQUIRED_FIELDS,
+};
+use crate::ndjson::{read_input_items, write_ndjson_line};
+use crate::rate_limit::acquire;
+use crate::{
+    api_token, env_or_default_url, is_http_url, normalize_item_time, validated_feed_id,
+    IDEMPOTENCY_KEY_HEADER, X_AUTH_TOKEN_HEADER,
+};
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use serde_json::from_str as json_from_str;
-use std::time::Duration;
-use tokio::time::sleep;
+use serde_json::{
+    from_reader as json_from_reader, from_slice as json_from_slice, from_value as json_from_value,
+    Map, Value,
+};
+use std::borrow::Cow;
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::io::{BufRead, Write};
+#[cfg(feature = "csv")]
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Instant};
+use tokio_util::sync::CancellationToken;
 
 pub trait YupdatesV0 {
+    /// Read a single item from a feed, with its content populated.
+    ///
+    /// Returns a [Kind::ItemNotFound] error if the API reports that there is no such item.
+    fn get_item<F, I>(&self, feed_id: F, item_id: I) -> Result<FeedItem>
+    where
+        F: AsRef<str>,
+        I: AsRef<str>;
+
+    /// Delete an item from a feed (using the feed-specific API token of that feed). The item is
+    /// then read back with `deleted` set, see [ReadOptions::include_deleted].
+    ///
+    /// Returns a [Kind::ItemNotFound] error if there is no such item, and a [Kind::ItemDeleted]
+    /// error if it was already deleted. A token that may not delete from the feed, like a
+    /// read-only token, gets a [Kind::Forbidden] error that says so, like
+    /// [YupdatesV0::delete_feed] does.
+    fn delete_item<F, I>(&self, feed_id: F, item_id: I) -> Result<DeleteItemResponse>
+    where
+        F: AsRef<str>,
+        I: AsRef<str>;
+
+    /// Change an existing item (using the feed-specific API token of its feed), for example to
+    /// fix a typo without posting a new item. Returns the item as it is after the change.
+    ///
+    /// Only the fields that are set in the [ItemPatch] are changed. To replace the whole item,
+    /// pass `&ItemPatch::from(&input_item)`. The patch is checked like new items are (see
+    /// [validate_items]) before it is sent, and an empty patch is rejected. The errors are the
+    /// same as for [YupdatesV0::delete_item].
+    fn update_item<F, I>(&self, feed_id: F, item_id: I, patch: &ItemPatch) -> Result<FeedItem>
+    where
+        F: AsRef<str>,
+        I: AsRef<str>;
+
     /// Add items to a feed (using a feed-specific API token)
     ///
     /// You can send up to 10 at a time. See [YupdatesV0::new_items_all] for chunked example.
     /// Sending zero items is legal (you might want to verify the token is authorized for this
     /// call, or you might want to get the matching `feed_id` returned without adding an item).
+    /// The items are checked before anything is sent, see [validate_associated_files] and
+    /// [crate::limits].
     fn new_items(&self, items: &[InputItem]) -> Result<NewInputItemsResponse>;
 
+    /// Like [YupdatesV0::new_items], but more than [MAX_ITEMS_PER_CALL] items is not an error:
+    /// the first [MAX_ITEMS_PER_CALL] are sent and the rest are dropped, with a warning when the
+    /// `tracing` feature is enabled. This is for best-effort producers, like telemetry, that
+    /// would rather lose items than fail. To send every item, see [YupdatesV0::new_items_all].
+    fn new_items_lenient(&self, items: &[InputItem]) -> Result<LenientNewItemsResponse>;
+
+    /// The ID of the feed that this client's feed-specific API token adds items to.
+    ///
+    /// This performs a POST to the new items API with an empty item list, which adds nothing and
+    /// returns the matching `feed_id`. It fails if the token is not a feed-specific token.
+    fn feed_id(&self) -> Result<String>;
+
+    /// Check the items with [validate_items] and confirm the token can add items, without
+    /// publishing anything. The token is checked with a call that adds zero items, which the API
+    /// allows. Any number of items can be checked.
+    ///
+    /// Returns the feed ID that the items would be added to
+    fn new_items_dry_run(&self, items: &[InputItem]) -> Result<String>;
+
+    /// Like [YupdatesV0::new_items], but without checking the associated files (see
+    /// [validate_associated_files]) or the sizes (see [crate::limits]) first. Use this if you
+    /// intentionally send unusual file lengths or types, or want the server to judge the sizes.
+    /// To send more than 10 items this way, see [chunk_items].
+    fn new_items_unchecked(&self, items: &[InputItem]) -> Result<NewInputItemsResponse>;
+
+    /// Like [YupdatesV0::new_items], but sends `key` in the [crate::IDEMPOTENCY_KEY_HEADER]
+    /// header so that the server can tell a retry from a new call. If a call fails without a
+    /// response, like on a timeout, call this again with the same items and key: the items are
+    /// added once, whether or not the first call landed.
+    ///
+    /// Derive the key from the batch, so that a retry sends the same key even from another
+    /// process. For example, a SHA-256 hex digest of the serialized items. The key must be 1 to
+    /// [MAX_IDEMPOTENCY_KEY_LEN] printable ASCII characters.
+    fn new_items_idempotent(&self, items: &[InputItem], key: &str)
+        -> Result<NewInputItemsResponse>;
+
     /// Add an arbitrary number of items to a feed (using a feed-specific API token)
     ///
     /// This sends all of the input items in batches, up to 10 at a time. It pauses for N ms
     /// between each call (to preemptively avoid throttling). Must be 5 or more ms.
     ///
+    /// A batch that fails with an error that [Error::is_retryable], like throttling (HTTP 429)
+    /// or the service being unavailable (HTTP 503), is retried with backoff, see [RetryPolicy].
+    /// If a batch fails, the error is a [Kind::BatchFailed] that says which batch failed and how
+    /// many items were already sent.
+    /// Give the items an [InputItem::input_id] to make calling this again after a failure safe:
+    /// the items that were already added are not added twice.
+    ///
     /// Returns feed ID
     fn new_items_all(&self, items: &[InputItem], sleep_ms: u64) -> Result<String>;
 
+    /// Like [YupdatesV0::new_items_all], with the pause between calls as a [Duration]. It must be
+    /// at least [MIN_PACING].
+    fn new_items_all_with_pacing(&self, items: &[InputItem], pacing: Duration) -> Result<String>;
+
+    /// Like [YupdatesV0::new_items_all], with the batch sizes and the pause between calls set by
+    /// `options`. With [BatchOptions::max_bytes_per_batch], batches of large items stay under a
+    /// request size limit, see [chunk_items_with_options]. Everything is checked before the
+    /// first call.
+    fn new_items_all_with_options(
+        &self,
+        items: &[InputItem],
+        options: &BatchOptions,
+    ) -> Result<String>;
+
+    /// Like [YupdatesV0::new_items_all], with your own [RetryPolicy] for throttled batches.
+    fn new_items_all_with_retry(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+        retry: &RetryPolicy,
+    ) -> Result<String>;
+
+    /// Like [YupdatesV0::new_items_all], but returns the response for each batch, in order.
+    ///
+    /// Every batch must report the same feed ID. If one does not, that points at a token or
+    /// configuration problem mid-run and this returns a [Kind::IllegalResult] error. Sending
+    /// zero items returns no responses.
+    fn new_items_all_detailed(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+    ) -> Result<Vec<NewInputItemsResponse>>;
+
+    /// Like [YupdatesV0::new_items_all], but also returns what happened to each item, collected
+    /// from every batch, with [ItemResult::index] being the index into `items`. Use this to report
+    /// which source records need attention.
+    ///
+    /// The results are `None` if any batch did not report them. Errors are the same as for
+    /// [YupdatesV0::new_items_all].
+    fn new_items_all_with_results(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+    ) -> Result<(String, Option<Vec<ItemResult>>)>;
+
+    /// Add the input items read from `reader` as NDJSON, one item per line (see
+    /// [crate::ndjson::read_input_items]). Items are read as they are sent, up to 10 at a time,
+    /// so a large export is never held in memory. The pause between calls and the retries are
+    /// like [YupdatesV0::new_items_all].
+    ///
+    /// A line that is not a valid item stops the call. The error is a [Kind::BatchFailed] that
+    /// wraps the [Kind::Deserialization] error with the line number, and says how many items were
+    /// already sent. A batch that fails its checks or is rejected stops the call the same way.
+    /// Give the items input IDs to make calling this again after fixing the input safe.
+    fn new_items_from_ndjson<R>(&self, reader: R, sleep_ms: u64) -> Result<BatchSummary>
+    where
+        R: BufRead;
+
+    /// Like [YupdatesV0::new_items_all], but calls `progress` once at the start and again after
+    /// each batch is accepted. See [BatchProgress].
+    ///
+    /// Errors are the same as for [YupdatesV0::new_items_all].
+    fn new_items_all_with_progress<F>(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+        progress: F,
+    ) -> Result<String>
+    where
+        F: FnMut(BatchProgress);
+
+    /// Like [YupdatesV0::new_items_all], but stops when `token` is cancelled, for example from a
+    /// Ctrl-C handler.
+    ///
+    /// The token is checked before each batch is sent. Once it is cancelled, no more batches are
+    /// sent and this returns a [Kind::Cancelled] error with the number of items that were sent
+    /// and their feed. A batch that is already in flight is not interrupted: it completes (with
+    /// retries, if it is throttled) and is included in that number.
+    ///
+    /// The token can be cancelled from any thread, so a synchronous program can cancel a clone of
+    /// it from its Ctrl-C handler (for example one set with the `ctrlc` crate's `set_handler`)
+    /// while this call blocks.
+    fn new_items_all_cancellable(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+        token: CancellationToken,
+    ) -> Result<String>;
+
+    /// Like [YupdatesV0::new_items_all], but can pick up where an earlier call stopped. Pass
+    /// `BulkProgress::default()` to start from the first item.
+    ///
+    /// If a batch fails, the error is a [BulkError] with the [BulkProgress] so far. Call this
+    /// again with the same items and that progress to send only the items that were not sent
+    /// yet. The progress can be serialized, so it can be saved and the call resumed from another
+    /// process. If the progress has a feed ID, the resumed batches must be added to the same
+    /// feed, or the call stops with a [Kind::IllegalResult] error.
+    ///
+    /// Returns feed ID
+    fn new_items_all_resume(
+        &self,
+        items: &[InputItem],
+        progress: &BulkProgress,
+        sleep_ms: u64,
+    ) -> std::result::Result<String, BulkError>;
+
+    /// Like [YupdatesV0::new_items_all], but with up to `concurrency` batches in flight at once.
+    ///
+    /// Batches are started at least `pacing` apart (which must be at least [MIN_PACING]), and
+    /// `concurrency` must be 1 to [MAX_NEW_ITEMS_CONCURRENCY]. Items within a batch keep their
+    /// order, but batches can be accepted in any order, so the order of items across batches is
+    /// no longer guaranteed. Use [YupdatesV0::new_items_all] when the order matters. Throttled
+    /// batches are not retried: the first failed batch fails the call, and batches that were
+    /// still in flight may or may not have been added.
+    fn new_items_all_concurrent(
+        &self,
+        items: &[InputItem],
+        concurrency: usize,
+        pacing: Duration,
+    ) -> Result<BatchSummary>;
+
+    /// Like [YupdatesV0::new_items_all], but first drops any item whose `canonical_url` was
+    /// already used by an earlier item in `items` (the first occurrence is kept, see
+    /// [dedup_items]). This only looks at the items in this call, not at what the feed holds.
+    ///
+    /// Returns the feed ID and the number of items that were skipped
+    fn new_items_all_dedup(&self, items: &[InputItem], sleep_ms: u64) -> Result<(String, usize)>;
+
+    /// Add the items that are not in the feed yet, matching on `canonical_url`.
+    ///
+    /// The latest `lookback` items of `feed_id` are read (deleted items do not count), and an
+    /// input item whose URL is among them is skipped, or with [UpsertMode::Update], updated if
+    /// its title or content changed (see [YupdatesV0::update_item]). An item that repeats the URL
+    /// of an earlier input item is skipped as well. The rest, including items without a URL, are
+    /// added like [YupdatesV0::new_items_all] would. Before anything is added, this checks that
+    /// the token adds items to `feed_id`, and fails with a [Kind::IllegalResult] error if not.
+    /// Older items are not seen, so pick a `lookback` that covers how far back repeats can be.
+    fn upsert_items<S>(
+        &self,
+        feed_id: S,
+        items: &[InputItem],
+        mode: UpsertMode,
+        lookback: usize,
+    ) -> Result<UpsertSummary>
+    where
+        S: AsRef<str>;
+
+    /// Add the items that are not in the token's feed yet, so that running an import again after
+    /// a partial failure does not add what made it the first time.
+    ///
+    /// The feed is found with a dry run (see [YupdatesV0::new_items_dry_run]), and its latest
+    /// `lookback` items are read (deleted items do not count). An input item whose key (see
+    /// [DedupKey]) is among them, or repeats the key of an earlier input item, is skipped. The
+    /// rest, including items without a URL, are added like [YupdatesV0::new_items_all] would.
+    /// Titles and URLs are compared exactly. Older items are not seen, so pick a `lookback` that
+    /// covers the earlier run.
+    ///
+    /// This is the [UpsertMode::Skip] of [YupdatesV0::upsert_items] for the token's own feed, with
+    /// a choice of key. Unlike [YupdatesV0::new_items_all_dedup], it looks at the feed.
+    fn new_items_all_skip_existing(
+        &self,
+        items: &[InputItem],
+        lookback: usize,
+        key: DedupKey,
+    ) -> Result<DedupSummary>;
+
+    /// Create a feed (using a general API token, not a feed-specific one), for example to
+    /// provision a feed per customer.
+    ///
+    /// The name must not be blank and is at most [MAX_FEED_NAME_LEN] characters, and the
+    /// description at most [MAX_FEED_DESCRIPTION_LEN]. These are checked before anything is sent.
+    /// Returns the new feed ID, and the new feed's feed-specific token if the API issued one.
+    fn new_feed(&self, options: &NewFeedOptions) -> Result<NewFeedResponse>;
+
+    /// Delete a feed and all of its items (using a general API token). This cannot be undone,
+    /// which is why the call needs [DeleteConfirmation::IUnderstandThisIsPermanent].
+    ///
+    /// Returns a [Kind::FeedNotFound] error if there is no such feed, and a [Kind::Forbidden]
+    /// error if the token may not delete it. To see what would be deleted without deleting
+    /// anything, see [YupdatesV0::delete_feed_dry_run].
+    fn delete_feed<S>(
+        &self,
+        feed_id: S,
+        confirmation: DeleteConfirmation,
+    ) -> Result<DeleteFeedResponse>
+    where
+        S: AsRef<str>;
+
+    /// The feed that [YupdatesV0::delete_feed] would delete, with its item count if the API
+    /// reports one, without deleting anything. The feed is looked up with
+    /// [YupdatesV0::list_feeds], so only GET requests are sent.
+    ///
+    /// Returns a [Kind::FeedNotFound] error if the token's account has no such feed. Whether the
+    /// token may delete the feed is only known when it does.
+    fn delete_feed_dry_run<S>(&self, feed_id: S) -> Result<FeedInfo>
+    where
+        S: AsRef<str>;
+
+    /// List the feeds in the account that the token belongs to.
+    ///
+    /// The API returns the feeds a page at a time, and this follows the pages until the last
+    /// one, so the result has every feed. Fields that the API does not return are empty or `None`
+    /// in each [FeedInfo].
+    fn list_feeds(&self) -> Result<Vec<FeedInfo>>;
+
     /// Tests configuration and authentication. If this is Ok, the call worked and your API token
     /// configuration is valid. There may be permissions errors for other operations, but it was
     /// a working credential for some operations.
@@ -41,6 +353,9 @@ pub trait YupdatesV0 {
     /// If you need error logging, use [YupdatesV0::ping] instead.
     fn ping_bool(&self) -> bool;
 
+    /// Like [YupdatesV0::ping], but also returns the wall-clock time the request took.
+    fn ping_timed(&self) -> Result<(PingResponse, Duration)>;
+
     /// Read items from a feed. Gets up to ten most recent items. The content is not returned (but
     /// that is an option, see [YupdatesV0::read_items_with_options]).
     fn read_items<S>(&self, feed_id: S) -> Result<Vec<FeedItem>>
@@ -48,6 +363,9 @@ pub trait YupdatesV0 {
         S: AsRef<str>;
 
     /// Read items from a feed, with options. See [ReadOptions].
+    ///
+    /// This returns just the items. For the whole response, including the server-reported
+    /// `code`, see [YupdatesV0::read_items_response].
     fn read_items_with_options<S>(
         &self,
         feed_id: S,
@@ -55,201 +373,3782 @@ pub trait YupdatesV0 {
     ) -> Result<Vec<FeedItem>>
     where
         S: AsRef<str>;
+
+    /// Read the ten most recent items from a feed, including their content. This is shorthand
+    /// for [YupdatesV0::read_items_with_options] with `include_item_content` set.
+    fn read_items_with_content<S>(&self, feed_id: S) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>;
+
+    /// Read the `max_items` most recent items from a feed, including their content.
+    ///
+    /// The API returns at most 10 items with content per call, so `max_items` must be 1 to 10.
+    /// Larger values are rejected rather than split into several calls: to read further back,
+    /// page with `item_time_before` (see [ReadOptions]).
+    fn read_items_with_content_n<S>(&self, feed_id: S, max_items: usize) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>;
+
+    /// Read items from many feeds at once, with up to `concurrency` requests in flight.
+    ///
+    /// The results are in the same order as `feed_ids`, and each feed has its own result so one
+    /// failing feed does not fail the others. `concurrency` must be at least 1.
+    fn read_items_many<S>(
+        &self,
+        feed_ids: &[S],
+        options: &ReadOptions,
+        concurrency: usize,
+    ) -> Result<Vec<(String, Result<Vec<FeedItem>>)>>
+    where
+        S: AsRef<str>;
+
+    /// Read items from a feed, with options, and return the whole response. This gives you the
+    /// server-reported code and any other top-level fields (see [ReadFeedItemsResponse::extra]).
+    /// [YupdatesV0::ping] and [YupdatesV0::new_items] return their whole responses already.
+    ///
+    /// Only an HTTP 200 is a success. Any other status, including another 2XX like a 206, is an
+    /// error that carries the status (see [Kind::HttpCode] and [Kind::DetailedHttpCode]).
+    fn read_items_response<S>(
+        &self,
+        feed_id: S,
+        options: &ReadOptions,
+    ) -> Result<ReadFeedItemsResponse>
+    where
+        S: AsRef<str>;
+
+    /// Read items from a feed into your own type instead of [FeedItem].
+    ///
+    /// Each item is deserialized directly into `T`, so `T` can model a subset of the fields. If
+    /// an item cannot be deserialized, the error says which index in the response failed.
+    fn read_items_as<T, S>(&self, feed_id: S, options: &ReadOptions) -> Result<Vec<T>>
+    where
+        T: DeserializeOwned,
+        S: AsRef<str>;
+
+    /// Read items from a feed and return the whole response body as untyped JSON.
+    ///
+    /// This makes fields available that the SDK does not model yet. The options are validated
+    /// and sent like [YupdatesV0::read_items_with_options], but the items are returned exactly
+    /// as the API sent them (`order` is not applied).
+    fn read_items_raw<S>(&self, feed_id: S, options: &ReadOptions) -> Result<Value>
+    where
+        S: AsRef<str>;
+
+    /// Like [YupdatesV0::read_items_with_options], but with less memory at its peak: the response
+    /// body is parsed as it streams in, straight into [FeedItem]s, and is never held whole. The
+    /// default path reads the body into a `String` first and parses each item into a JSON value
+    /// on the way, which lets an error name the index of the item that failed and show the body.
+    /// Here, a deserialization error only has the line and column.
+    ///
+    /// This is worth it for large reads, like 10 items with their content. It does not use the
+    /// read cache (see [crate::clients::AsyncYupdatesClient::with_read_cache]).
+    fn read_items_direct<S>(&self, feed_id: S, options: &ReadOptions) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>;
+
+    /// Read items from a feed unless they are unchanged since the response that had `etag`.
+    ///
+    /// Pass the ETag from a previous [ReadOutcome::Modified], or `None` for the first read. If
+    /// the API answers "304 Not Modified", this returns [ReadOutcome::NotModified] instead of an
+    /// error. To have the ETags tracked for you, see [crate::clients::CachedReader].
+    ///
+    /// An ETag is only valid for the request that returned it: the same feed and the same
+    /// options, including the cursor (`item_time_after` or `item_time_before`) and `max_items`.
+    /// Sending it with other options can get a 304 for a page that was never read, so keep one
+    /// ETag per query, as [crate::clients::CachedReader] does.
+    fn read_items_conditional<S>(
+        &self,
+        feed_id: S,
+        options: &ReadOptions,
+        etag: Option<&str>,
+    ) -> Result<ReadOutcome>
+    where
+        S: AsRef<str>;
+
+    /// Page back through a feed and return the items that match the predicate.
+    ///
+    /// Each page has `max_items` items, and paging starts at `item_time_before` (or the latest
+    /// item) and moves back in time. It stops after `stop_after` matches, at the start of the
+    /// feed, or after [MAX_FILTERED_PAGES] pages, whichever comes first. The result says how many
+    /// items were scanned so you can judge the cost. `item_time_after` is not supported.
+    fn read_items_filtered<S, F>(
+        &self,
+        feed_id: S,
+        options: &ReadOptions,
+        predicate: F,
+        stop_after: Option<usize>,
+    ) -> Result<FilteredItems>
+    where
+        S: AsRef<str>,
+        F: Fn(&FeedItem) -> bool;
+
+    /// Read every item with an item time between `after` and `before`.
+    ///
+    /// The API accepts only one of `item_time_after` and `item_time_before` per request, so this
+    /// pages back from `before` with `max_items` items per page until it reaches `after`, the
+    /// start of the feed, or the limit in `range`. Both bounds are exclusive unless `range` asks
+    /// for an inclusive `after`, and `after` must be earlier than `before`. The item time fields
+    /// in `options` must not be set. The items are returned newest first unless `options` asks
+    /// for [Order::Oldest].
+    fn read_items_in_range<S, T>(
+        &self,
+        feed_id: S,
+        after: T,
+        before: T,
+        options: &ReadOptions,
+        range: &RangeOptions,
+    ) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>,
+        T: AsRef<str>;
+
+    /// Read the items in a feed that match a text query. The API does the matching.
+    ///
+    /// The query is sent as the `q` parameter along with the options, which are validated like
+    /// [YupdatesV0::read_items_with_options], so `max_items` and the item time bounds work the
+    /// same way. An empty (or all whitespace) query is rejected with [Kind::IllegalParameter].
+    fn search_items<S>(
+        &self,
+        feed_id: S,
+        query: &str,
+        options: &ReadOptions,
+    ) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>;
+
+    /// Count the items in a feed, not counting deleted items.
+    ///
+    /// The API has no count endpoint, so this pages back through the feed, 50 item IDs and times
+    /// per call, and stops after [MAX_COUNTED_ITEMS] items (including deleted ones). For a longer
+    /// feed the count is a lower bound, so treat it as approximate.
+    fn count_items<S>(&self, feed_id: S) -> Result<u64>
+    where
+        S: AsRef<str>;
+
+    /// Write every item in a feed, with content, to `writer` as NDJSON (see [crate::ndjson]).
+    ///
+    /// The feed is read page by page from the newest item back to the oldest, and each page is
+    /// written and flushed before the next is read, so a large feed is never held in memory.
+    /// Stops after `limit` items if given. Returns the number of items written.
+    fn dump_feed_ndjson<S, W>(&self, feed_id: S, writer: W, limit: Option<usize>) -> Result<usize>
+    where
+        S: AsRef<str>,
+        W: Write;
+
+    /// Poll the latest items in a feed until one matches the predicate, and return it.
+    ///
+    /// This is useful to wait until an item you published is visible. The interval is raised to
+    /// [MIN_WAIT_POLL_INTERVAL] if it is shorter. If nothing matches before the timeout, this
+    /// returns a [Kind::Timeout] error that says how many polls were made.
+    fn wait_for_item<S, F>(
+        &self,
+        feed_id: S,
+        predicate: F,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<FeedItem>
+    where
+        S: AsRef<str>,
+        F: Fn(&FeedItem) -> bool;
+}
+
+/// The calls of the `csv` feature, which the sync clients implement like [YupdatesV0]. They are
+/// a trait of their own so that enabling the feature does not add required methods to
+/// [YupdatesV0].
+#[cfg(feature = "csv")]
+pub trait YupdatesV0Csv {
+    /// Add the input items in the CSV file at `path` with [YupdatesV0::new_items_all]. The file
+    /// is read with [crate::items::read_csv] first, so no item is sent if any row has a problem.
+    ///
+    /// Returns feed ID
+    fn new_items_from_csv<P>(&self, path: P, mapping: CsvMapping, sleep_ms: u64) -> Result<String>
+    where
+        P: AsRef<Path>;
 }
 
 // ─────────────────────────────────────────────────────────────────────────────────────────────────
-// ping(): GET $base_url/ping/
+// get_item(): GET $base_url/feeds/$feed_id/items/$item_id/
 // ─────────────────────────────────────────────────────────────────────────────────────────────────
 
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
-pub struct PingResponse {
+pub struct ReadFeedItemResponse {
     pub code: u16,
-    pub message: String,
+    pub feed_item: FeedItem,
 }
 
-/// See [YupdatesV0::ping]
-pub async fn ping() -> Result<PingResponse> {
+/// See [YupdatesV0::get_item]
+pub async fn get_item<F, I>(feed_id: F, item_id: I) -> Result<FeedItem>
+where
+    F: AsRef<str>,
+    I: AsRef<str>,
+{
     let base_url = env_or_default_url()?;
     let token = api_token()?;
     let http_client = reqwest::Client::new();
-    ping_with_args(&http_client, base_url, token).await
+    get_item_with_args(
+        feed_id.as_ref(),
+        item_id.as_ref(),
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
 }
 
-/// See [YupdatesV0::ping_bool]
-pub async fn ping_bool() -> bool {
-    ping().await.is_ok()
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(feed_id = feed_id.as_ref(), item_id = item_id.as_ref())
+    )
+)]
+pub async fn get_item_with_args<F, I, S>(
+    feed_id: F,
+    item_id: I,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<FeedItem>
+where
+    F: AsRef<str>,
+    I: AsRef<str>,
+    S: AsRef<str>,
+{
+    let feed_id_str = validated_feed_id(feed_id.as_ref())?;
+    let item_id_str = validated_item_id(item_id.as_ref())?;
+
+    let url = format!(
+        "{}feeds/{}/items/{}/",
+        base_url.as_ref(),
+        feed_id_str,
+        item_id_str
+    );
+    let (code, text, headers) = api_get(http_client, &url, token.as_ref()).await?;
+    let response: ReadFeedItemResponse = match code {
+        200 => json_from_body(&text)?,
+        404 => {
+            return Err(Error {
+                kind: Kind::ItemNotFound(item_id_str.to_string()),
+            });
+        }
+        // Including other 2XX/3XX in this category for now, they are unexpected
+        _ => return Err(api_error_with_headers(code, &headers, &text)),
+    };
+
+    Ok(response.feed_item)
 }
 
-pub async fn ping_with_args<S>(
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// delete_item(): DELETE $base_url/feeds/$feed_id/items/$item_id/
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct DeleteItemResponse {
+    pub code: u16,
+    pub message: String,
+}
+
+/// See [YupdatesV0::delete_item]
+pub async fn delete_item<F, I>(feed_id: F, item_id: I) -> Result<DeleteItemResponse>
+where
+    F: AsRef<str>,
+    I: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    delete_item_with_args(
+        feed_id.as_ref(),
+        item_id.as_ref(),
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(feed_id = feed_id.as_ref(), item_id = item_id.as_ref())
+    )
+)]
+pub async fn delete_item_with_args<F, I, S>(
+    feed_id: F,
+    item_id: I,
     http_client: &reqwest::Client,
     base_url: S,
     token: S,
-) -> Result<PingResponse>
+) -> Result<DeleteItemResponse>
 where
+    F: AsRef<str>,
+    I: AsRef<str>,
     S: AsRef<str>,
 {
-    let full_url = format!("{}ping/", base_url.as_ref());
-    let (code, text) = api_get(http_client, &full_url, token.as_ref()).await?;
-    if code == 200 {
-        Ok(json_from_str(&text)?)
-    } else {
+    let feed_id_str = validated_feed_id(feed_id.as_ref())?;
+    let item_id_str = validated_item_id(item_id.as_ref())?;
+
+    let url = format!(
+        "{}feeds/{}/items/{}/",
+        base_url.as_ref(),
+        feed_id_str,
+        item_id_str
+    );
+    let (code, text, headers) = api_delete(http_client, &url, token.as_ref()).await?;
+    match code {
+        200 => json_from_body(&text),
+        _ => Err(item_change_error(
+            code,
+            &headers,
+            &text,
+            "deleting",
+            item_id_str,
+        )),
+    }
+}
+
+// The errors of a call that changes an item, `action` is "deleting" or "updating"
+fn item_change_error(
+    code: u16,
+    headers: &HeaderMap,
+    text: &str,
+    action: &str,
+    item_id: &str,
+) -> Error {
+    match code {
+        401 | 403 => {
+            let msg = error_message(text);
+            let detail = if msg.is_empty() {
+                msg
+            } else {
+                format!(" ({})", msg)
+            };
+            let msg = format!(
+                "{} an item needs the feed-specific API token of its feed{}",
+                action, detail
+            );
+            let kind = if code == 403 {
+                Kind::Forbidden(msg)
+            } else {
+                Kind::DetailedHttpCode(code, msg)
+            };
+            Error { kind }
+        }
+        404 => Error {
+            kind: Kind::ItemNotFound(item_id.to_string()),
+        },
+        410 => Error {
+            kind: Kind::ItemDeleted(item_id.to_string()),
+        },
         // Including other 2XX/3XX in this category for now, they are unexpected
-        Err(api_error(code, &text))
+        _ => api_error_with_headers(code, headers, text),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// update_item(): PATCH $base_url/feeds/$feed_id/items/$item_id/
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// See [YupdatesV0::update_item]
+pub async fn update_item<F, I>(feed_id: F, item_id: I, patch: &ItemPatch) -> Result<FeedItem>
+where
+    F: AsRef<str>,
+    I: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    update_item_with_args(
+        feed_id.as_ref(),
+        item_id.as_ref(),
+        patch,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(feed_id = feed_id.as_ref(), item_id = item_id.as_ref())
+    )
+)]
+pub async fn update_item_with_args<F, I, S>(
+    feed_id: F,
+    item_id: I,
+    patch: &ItemPatch,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<FeedItem>
+where
+    F: AsRef<str>,
+    I: AsRef<str>,
+    S: AsRef<str>,
+{
+    let feed_id_str = validated_feed_id(feed_id.as_ref())?;
+    let item_id_str = validated_item_id(item_id.as_ref())?;
+    let patch = normalized_patch(patch)?;
+
+    let url = format!(
+        "{}feeds/{}/items/{}/",
+        base_url.as_ref(),
+        feed_id_str,
+        item_id_str
+    );
+    let (code, text, headers) = api_patch(http_client, &url, token.as_ref(), &patch).await?;
+    match code {
+        200 => {
+            let response: ReadFeedItemResponse = json_from_body(&text)?;
+            Ok(response.feed_item)
+        }
+        _ => Err(item_change_error(
+            code,
+            &headers,
+            &text,
+            "updating",
+            item_id_str,
+        )),
+    }
+}
+
+// Checks the fields that are set like validate_items does, and normalizes the item time
+fn normalized_patch(patch: &ItemPatch) -> Result<ItemPatch> {
+    if patch.is_empty() {
+        return Err(Error {
+            kind: Kind::IllegalParameter("the patch must change at least one field".to_string()),
+        });
+    }
+    let mut problems = Vec::new();
+    if patch.title.as_ref().is_some_and(|t| t.trim().is_empty()) {
+        problems.push("the patch: `title` must not be blank".to_string());
+    }
+    if let Some(url) = &patch.canonical_url {
+        if !url.is_empty() && !is_http_url(url) {
+            problems.push(format!(
+                "the patch: `canonical_url` must be an absolute http(s) URL ('{}')",
+                url
+            ));
+        }
+    }
+    field_size_problems(
+        "the patch",
+        patch.title.as_deref(),
+        patch.content.as_deref(),
+        patch.associated_files.as_deref(),
+        &mut problems,
+    );
+    if let Some(files) = &patch.associated_files {
+        file_problems(
+            "the patch",
+            files,
+            &FileValidation::default(),
+            &mut problems,
+        );
+    }
+    let mut normalized = patch.clone();
+    if let Some(item_time) = &patch.item_time {
+        match normalize_item_time(item_time) {
+            Ok(item_time) => normalized.item_time = Some(item_time),
+            Err(_) => problems.push(format!(
+                "the patch: `item_time` is not a valid item time ('{}')",
+                item_time
+            )),
+        }
+    }
+    problems_to_result(problems)?;
+    Ok(normalized)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// new_feed(): POST $base_url/feeds/
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// The longest feed name that [YupdatesV0::new_feed] accepts, in characters
+pub const MAX_FEED_NAME_LEN: usize = 100;
+
+/// The longest feed description that [YupdatesV0::new_feed] accepts, in characters
+pub const MAX_FEED_DESCRIPTION_LEN: usize = 1000;
+
+/// The feed to create with [YupdatesV0::new_feed]
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NewFeedOptions {
+    /// The feed's name (its title), at most [MAX_FEED_NAME_LEN] characters
+    pub name: String,
+    /// Left out of the request when `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// The whole new feed response, including the server-reported `code`
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct NewFeedResponse {
+    pub code: u16,
+    pub feed_id: String,
+    /// The new feed's feed-specific API token, to add items to it with, if the API issued one.
+    /// This is a secret, so it is left out of the `Debug` output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feed_token: Option<String>,
+    #[serde(default)]
+    pub message: String,
+}
+
+impl NewFeedResponse {
+    /// The `feed_id` as a validated [FeedId]
+    pub fn typed_feed_id(&self) -> Result<FeedId> {
+        FeedId::new(&self.feed_id)
+    }
+}
+
+impl std::fmt::Debug for NewFeedResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NewFeedResponse")
+            .field("code", &self.code)
+            .field("feed_id", &self.feed_id)
+            .field(
+                "feed_token",
+                &self.feed_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("message", &self.message)
+            .finish()
+    }
+}
+
+/// See [YupdatesV0::new_feed]
+pub async fn new_feed(options: &NewFeedOptions) -> Result<NewFeedResponse> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_feed_with_args(options, &http_client, base_url, token).await
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub async fn new_feed_with_args<S>(
+    options: &NewFeedOptions,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<NewFeedResponse>
+where
+    S: AsRef<str>,
+{
+    validate_new_feed(options)?;
+    let url = format!("{}feeds/", base_url.as_ref());
+    let (code, text, headers) = api_post(http_client, &url, token.as_ref(), None, options).await?;
+    match code {
+        // The API may answer 201 Created, this is the one call where that is expected
+        200 | 201 => {
+            let response: NewFeedResponse = json_from_body(&text)?;
+            validated_feed_id(&response.feed_id).map_err(|e| Error {
+                kind: Kind::IllegalResult(format!("the new feed has an illegal feed ID: {}", e)),
+            })?;
+            Ok(response)
+        }
+        _ => Err(api_error_with_headers(code, &headers, &text)),
+    }
+}
+
+fn validate_new_feed(options: &NewFeedOptions) -> Result<()> {
+    let mut problems = Vec::new();
+    let name_len = options.name.chars().count();
+    if options.name.trim().is_empty() {
+        problems.push("`name` must not be blank".to_string());
+    } else if name_len > MAX_FEED_NAME_LEN {
+        problems.push(format!(
+            "`name` is {} characters, more than the limit of {}",
+            name_len, MAX_FEED_NAME_LEN
+        ));
+    }
+    let description_len = options
+        .description
+        .as_ref()
+        .map_or(0, |d| d.chars().count());
+    if description_len > MAX_FEED_DESCRIPTION_LEN {
+        problems.push(format!(
+            "`description` is {} characters, more than the limit of {}",
+            description_len, MAX_FEED_DESCRIPTION_LEN
+        ));
+    }
+    problems_to_result(problems)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// delete_feed(): DELETE $base_url/feeds/$feed_id/
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// The acknowledgement that [YupdatesV0::delete_feed] needs, so that it is never called casually
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DeleteConfirmation {
+    IUnderstandThisIsPermanent,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct DeleteFeedResponse {
+    pub code: u16,
+    pub message: String,
+}
+
+/// See [YupdatesV0::delete_feed]
+pub async fn delete_feed<S>(
+    feed_id: S,
+    confirmation: DeleteConfirmation,
+) -> Result<DeleteFeedResponse>
+where
+    S: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    delete_feed_with_args(
+        feed_id.as_ref(),
+        confirmation,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn delete_feed_with_args<S>(
+    feed_id: S,
+    confirmation: DeleteConfirmation,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<DeleteFeedResponse>
+where
+    S: AsRef<str>,
+{
+    let DeleteConfirmation::IUnderstandThisIsPermanent = confirmation;
+    send_delete_feed(
+        feed_id.as_ref(),
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+    )
+    .await
+}
+
+/// See [YupdatesV0::delete_feed_dry_run]
+pub async fn delete_feed_dry_run<S>(feed_id: S) -> Result<FeedInfo>
+where
+    S: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    delete_feed_dry_run_with_args(feed_id.as_ref(), &http_client, &base_url, &token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn delete_feed_dry_run_with_args<S>(
+    feed_id: S,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<FeedInfo>
+where
+    S: AsRef<str>,
+{
+    let feed_id_str = validated_feed_id(feed_id.as_ref())?;
+    list_feeds_with_args(http_client, base_url, token)
+        .await?
+        .into_iter()
+        .find(|feed| feed.feed_id == feed_id_str)
+        .ok_or_else(|| Error {
+            kind: Kind::FeedNotFound(feed_id_str.to_string()),
+        })
+}
+
+async fn send_delete_feed(
+    feed_id: &str,
+    http_client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<DeleteFeedResponse> {
+    let feed_id_str = validated_feed_id(feed_id)?;
+    let url = format!("{}feeds/{}/", base_url, feed_id_str);
+    let (code, text, headers) = api_delete(http_client, &url, token).await?;
+    match code {
+        200 => json_from_body(&text),
+        403 => Err(Error {
+            kind: Kind::Forbidden(error_message(&text)),
+        }),
+        404 => Err(Error {
+            kind: Kind::FeedNotFound(feed_id_str.to_string()),
+        }),
+        // Including other 2XX/3XX in this category for now, they are unexpected
+        _ => Err(api_error_with_headers(code, &headers, &text)),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// list_feeds(): paged GET $base_url/feeds/
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// One page of the feeds in the account, see [YupdatesV0::list_feeds]
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct ListFeedsResponse {
+    pub code: u16,
+    pub feeds: Vec<FeedInfo>,
+    /// Send this as `cursor` to get the next page. `None` on the last page.
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// See [YupdatesV0::list_feeds]
+pub async fn list_feeds() -> Result<Vec<FeedInfo>> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    list_feeds_with_args(&http_client, base_url, token).await
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub async fn list_feeds_with_args<S>(
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<Vec<FeedInfo>>
+where
+    S: AsRef<str>,
+{
+    let url = format!("{}feeds/", base_url.as_ref());
+    let mut feeds = Vec::new();
+    let mut cursors = HashSet::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let query: Vec<(&str, &str)> = cursor.iter().map(|c| ("cursor", c.as_str())).collect();
+        let (code, text, headers) =
+            api_get_with_query(http_client, &url, &query, token.as_ref(), None).await?;
+        if code != 200 {
+            // Including other 2XX/3XX in this category for now, they are unexpected
+            return Err(api_error_with_headers(code, &headers, &text));
+        }
+        let page: ListFeedsResponse = json_from_body(&text)?;
+        feeds.extend(page.feeds);
+        match page.next_cursor {
+            None => break,
+            // A cursor that comes back again would page forever
+            Some(next) if !cursors.insert(next.clone()) => {
+                return Err(Error {
+                    kind: Kind::IllegalResult(format!(
+                        "the feed list repeated the cursor '{}'",
+                        next
+                    )),
+                });
+            }
+            Some(next) => cursor = Some(next),
+        }
+    }
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        feeds = feeds.len(),
+        pages = cursors.len() + 1,
+        "listed feeds"
+    );
+    Ok(feeds)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// ping(): GET $base_url/ping/
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// The whole ping response, including the server-reported `code`
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct PingResponse {
+    pub code: u16,
+    pub message: String,
+}
+
+/// See [YupdatesV0::ping]
+pub async fn ping() -> Result<PingResponse> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    ping_with_args(&http_client, base_url, token).await
+}
+
+/// See [YupdatesV0::ping_bool]
+pub async fn ping_bool() -> bool {
+    ping().await.is_ok()
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub async fn ping_with_args<S>(
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<PingResponse>
+where
+    S: AsRef<str>,
+{
+    let (response, _) = ping_timed_with_args(http_client, base_url, token).await?;
+    Ok(response)
+}
+
+/// See [YupdatesV0::ping_timed]
+pub async fn ping_timed() -> Result<(PingResponse, Duration)> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    ping_timed_with_args(&http_client, base_url, token).await
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub async fn ping_timed_with_args<S>(
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<(PingResponse, Duration)>
+where
+    S: AsRef<str>,
+{
+    let full_url = format!("{}ping/", base_url.as_ref());
+    let start = Instant::now();
+    let (code, text, headers) = api_get(http_client, &full_url, token.as_ref()).await?;
+    let elapsed = start.elapsed();
+    if code == 200 {
+        Ok((json_from_body(&text)?, elapsed))
+    } else {
+        // Including other 2XX/3XX in this category for now, they are unexpected
+        Err(api_error_with_headers(code, &headers, &text))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// new_items(): POST $base_url/items/
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// The whole new items response, including the server-reported `code`
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct NewInputItemsResponse {
+    pub code: u16,
+    pub feed_id: String,
+    pub message: String,
+    /// What happened to each item, when the API reports it. Older responses do not have this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_results: Option<Vec<ItemResult>>,
+}
+
+/// What happened to one item of a new items call
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct ItemResult {
+    /// The index of the item in the call. In [YupdatesV0::new_items_all_with_results] it is the
+    /// index into all of the items.
+    pub index: usize,
+    pub status: ItemStatus,
+    /// The ID the item was given, if it was added
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<String>,
+    /// The input ID that was sent, or that the API generated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_id: Option<String>,
+    /// Why the item was not added
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// See [ItemResult]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemStatus {
+    /// The item was added
+    Accepted,
+    /// The item has the input ID of an item that is already in the feed, it was not added again
+    Duplicate,
+    /// The item was not added, see [ItemResult::message]
+    Rejected,
+    /// A status this version of the library does not know
+    #[serde(other)]
+    Other,
+}
+
+impl NewInputItemsResponse {
+    /// The `feed_id` as a validated [FeedId]
+    pub fn typed_feed_id(&self) -> Result<FeedId> {
+        FeedId::new(&self.feed_id)
+    }
+}
+
+/// The most input items that one call to [YupdatesV0::new_items] accepts
+pub const MAX_ITEMS_PER_CALL: usize = 10;
+
+/// Split input items into batches that [YupdatesV0::new_items] accepts, in order. This is the
+/// batching that [YupdatesV0::new_items_all] uses, for when you want to drive the calls yourself.
+pub fn chunk_items(items: &[InputItem]) -> impl Iterator<Item = &[InputItem]> {
+    items.chunks(MAX_ITEMS_PER_CALL)
+}
+
+/// How [YupdatesV0::new_items_all_with_options] splits items into batches and paces the calls.
+/// The default is the batching of [YupdatesV0::new_items_all]: [MAX_ITEMS_PER_CALL] items per
+/// batch whatever their size, [MIN_PACING] apart.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BatchOptions {
+    /// The most items in one batch, 1 to [MAX_ITEMS_PER_CALL]
+    pub max_items_per_batch: usize,
+    /// If set, a batch is closed early when the next item would make its request body longer
+    /// than this many bytes (of JSON, before any compression)
+    pub max_bytes_per_batch: Option<usize>,
+    /// The pause between calls, at least [MIN_PACING]
+    pub sleep: Duration,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        BatchOptions {
+            max_items_per_batch: MAX_ITEMS_PER_CALL,
+            max_bytes_per_batch: None,
+            sleep: MIN_PACING,
+        }
+    }
+}
+
+/// Split input items into batches like [chunk_items] does, in order, but with the batch sizes of
+/// `options`. With a byte budget, each item's serialized size is measured, and a batch is closed
+/// before the item that would make its request body too long, so batches can hold fewer items
+/// than `max_items_per_batch`.
+///
+/// A `max_items_per_batch` that is 0 or more than [MAX_ITEMS_PER_CALL], and an item whose
+/// request body alone would be over the budget, are [Kind::IllegalParameter] errors.
+pub fn chunk_items_with_options<'a>(
+    items: &'a [InputItem],
+    options: &BatchOptions,
+) -> Result<Vec<&'a [InputItem]>> {
+    let max_items = options.max_items_per_batch;
+    if max_items == 0 || max_items > MAX_ITEMS_PER_CALL {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "`max_items_per_batch` must be 1 to {}, received {}",
+                MAX_ITEMS_PER_CALL, max_items
+            )),
+        });
+    }
+    let Some(max_bytes) = options.max_bytes_per_batch else {
+        return Ok(items.chunks(max_items).collect());
+    };
+    let empty_body = serde_json::to_vec(&NewItemsBody { items: &[] })?.len();
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut body_len = empty_body;
+    for (index, item) in items.iter().enumerate() {
+        let item_len = serde_json::to_vec(item)?.len();
+        if empty_body + item_len > max_bytes {
+            return Err(Error {
+                kind: Kind::IllegalParameter(format!(
+                    "item {} alone makes a request body of {} bytes, more than \
+                     `max_bytes_per_batch` ({})",
+                    index,
+                    empty_body + item_len,
+                    max_bytes
+                )),
+            });
+        }
+        // Items after the first in a batch are preceded by a comma
+        let count = index - start;
+        let added = item_len + usize::from(count > 0);
+        if count == max_items || body_len + added > max_bytes {
+            batches.push(&items[start..index]);
+            start = index;
+            body_len = empty_body + item_len;
+        } else {
+            body_len += added;
+        }
+    }
+    if start < items.len() {
+        batches.push(&items[start..]);
+    }
+    Ok(batches)
+}
+
+/// Drop items that share a `canonical_url` with an earlier item, keeping the first occurrence and
+/// the order. Items without a canonical URL are always kept. Returns the remaining items and the
+/// number that were dropped.
+pub fn dedup_items(items: &[InputItem]) -> (Vec<InputItem>, usize) {
+    let mut seen = HashSet::new();
+    let kept: Vec<InputItem> = items
+        .iter()
+        .filter(|item| item.canonical_url.is_empty() || seen.insert(item.canonical_url.as_str()))
+        .cloned()
+        .collect();
+    let skipped = items.len() - kept.len();
+    (kept, skipped)
+}
+
+/// The largest associated file `length` that is accepted, 1 TiB. Anything larger is almost
+/// certainly a mistake.
+pub const MAX_ASSOCIATED_FILE_LENGTH: u64 = 1 << 40;
+
+/// Check the associated files of input items before they are sent: each `url` must be an
+/// absolute http(s) URL, each `length` must be 1 to [MAX_ASSOCIATED_FILE_LENGTH], and each
+/// `type_str` must look like a `type/subtype` MIME type.
+///
+/// [YupdatesV0::new_items] and [YupdatesV0::new_items_all] make this check, the error is a
+/// [Kind::IllegalParameter] that names the item index, file index, and file URL of every
+/// problem. To skip it, see [YupdatesV0::new_items_unchecked]. To also accept a `length` of 0,
+/// see [validate_associated_files_with].
+pub fn validate_associated_files(items: &[InputItem]) -> Result<()> {
+    validate_associated_files_with(items, &FileValidation::default())
+}
+
+/// How [validate_associated_files_with] checks associated files
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct FileValidation {
+    /// Accept a `length` of 0, which feeds use when the size is not known. Podcast apps can
+    /// show such files with a broken size or progress bar, so this is off by default.
+    pub allow_unknown_length: bool,
+}
+
+/// Like [validate_associated_files], with the given [FileValidation]
+pub fn validate_associated_files_with(
+    items: &[InputItem],
+    validation: &FileValidation,
+) -> Result<()> {
+    let mut problems = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        item_file_problems(index, item, validation, &mut problems);
+    }
+    problems_to_result(problems)
+}
+
+/// Check that the associated files of input items can be downloaded, with a HEAD request for
+/// each, for stronger checks than [validate_associated_files]. A file fails if the request fails,
+/// if the status is not a success (redirects are followed), or if the response has a
+/// `Content-Length` that differs from the file's nonzero `length`.
+///
+/// The error is a [Kind::IllegalParameter] that names the item index, file index, and file URL
+/// of every problem. The requests are made one at a time, and no API token is sent.
+pub async fn verify_files(items: &[InputItem]) -> Result<()> {
+    verify_files_with_args(items, &reqwest::Client::new()).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn verify_files_with_args(
+    items: &[InputItem],
+    http_client: &reqwest::Client,
+) -> Result<()> {
+    let mut problems = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        for (file_index, file) in item.associated_files.iter().flatten().enumerate() {
+            let prefix = format!("item {}, file {} ({})", index, file_index, file.url);
+            let res = match http_client.head(&file.url).send().await {
+                Ok(res) => res,
+                Err(e) => {
+                    problems.push(format!("{}: the HEAD request failed: {}", prefix, e));
+                    continue;
+                }
+            };
+            if !res.status().is_success() {
+                problems.push(format!(
+                    "{}: HEAD returned HTTP {}",
+                    prefix,
+                    res.status().as_u16()
+                ));
+                continue;
+            }
+            // HEAD responses have no body, so the header is read rather than the body's length
+            let content_length = res
+                .headers()
+                .get(reqwest::header::CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u64>().ok());
+            if let Some(content_length) = content_length {
+                if file.length != 0 && content_length != file.length {
+                    problems.push(format!(
+                        "{}: `length` is {}, but the server reports {} bytes",
+                        prefix, file.length, content_length
+                    ));
+                }
+            }
+        }
+    }
+    problems_to_result(problems)
+}
+
+/// The longest [InputItem::input_id] that is accepted
+pub const MAX_INPUT_ID_LEN: usize = 64;
+
+/// Check input items without sending them, for example before a large import.
+///
+/// Each item needs a non-blank `title`, a `canonical_url` that is empty or an absolute http(s)
+/// URL, sizes within the [crate::limits], and a legal `input_id` and an `item_time` that
+/// [crate::normalize_item_time] accepts if it has them, and its associated files must pass
+/// [validate_associated_files]. The error is a [Kind::IllegalParameter] that lists every
+/// problem, with the item index and field. See also [YupdatesV0::new_items_dry_run].
+pub fn validate_items(items: &[InputItem]) -> Result<()> {
+    let mut problems = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        if item.title.trim().is_empty() {
+            problems.push(format!("item {}: `title` must not be blank", index));
+        }
+        if !item.canonical_url.is_empty() && !is_http_url(&item.canonical_url) {
+            problems.push(format!(
+                "item {}: `canonical_url` must be an absolute http(s) URL ('{}')",
+                index, item.canonical_url
+            ));
+        }
+        size_problems(index, item, &mut problems);
+        if let Some(problem) = input_id_problem(index, item) {
+            problems.push(problem);
+        }
+        if let Some(Err(problem)) = item
+            .item_time
+            .as_deref()
+            .map(|item_time| normalized_item_time(index, item_time))
+        {
+            problems.push(problem);
+        }
+        item_file_problems(index, item, &FileValidation::default(), &mut problems);
+    }
+    problems_to_result(problems)
+}
+
+// The checks that the calls adding items make, see YupdatesV0::new_items_unchecked
+pub(crate) fn check_before_send(items: &[InputItem]) -> Result<()> {
+    let mut problems = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        size_problems(index, item, &mut problems);
+        item_file_problems(index, item, &FileValidation::default(), &mut problems);
+    }
+    problems_to_result(problems)
+}
+
+fn item_file_problems(
+    index: usize,
+    item: &InputItem,
+    validation: &FileValidation,
+    problems: &mut Vec<String>,
+) {
+    let files = item.associated_files.as_deref().unwrap_or_default();
+    file_problems(&format!("item {}", index), files, validation, problems);
+}
+
+// The problems with associated files, each starting with `subject` and the file
+fn file_problems(
+    subject: &str,
+    files: &[AssociatedFile],
+    validation: &FileValidation,
+    problems: &mut Vec<String>,
+) {
+    for (file_index, file) in files.iter().enumerate() {
+        let prefix = format!("{}, file {} ({})", subject, file_index, file.url);
+        if !is_http_url(&file.url) {
+            problems.push(format!("{}: `url` must be an absolute http(s) URL", prefix));
+        }
+        if file.length == 0 && !validation.allow_unknown_length {
+            problems.push(format!(
+                "{}: `length` is 0, set the size in bytes or allow unknown lengths (see \
+                 FileValidation)",
+                prefix
+            ));
+        } else if file.length > MAX_ASSOCIATED_FILE_LENGTH {
+            problems.push(format!(
+                "{}: `length` must be 1 to {}, received {}",
+                prefix, MAX_ASSOCIATED_FILE_LENGTH, file.length
+            ));
+        }
+        if !is_mime_type(&file.type_str) {
+            problems.push(format!(
+                "{}: `type` must be a MIME type like \"audio/mpeg\" ('{}')",
+                prefix, file.type_str
+            ));
+        }
+    }
+}
+
+pub(crate) fn problems_to_result(problems: Vec<String>) -> Result<()> {
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(Error {
+        kind: Kind::IllegalParameter(problems.join("; ")),
+    })
+}
+
+// `type/subtype` with RFC 6838 name characters, optionally followed by parameters
+fn is_mime_type(mime: &str) -> bool {
+    let essence = mime.split(';').next().unwrap_or_default().trim();
+    let legal_char = |c: char| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c);
+    match essence.split_once('/') {
+        Some((type_, subtype)) => {
+            !type_.is_empty()
+                && !subtype.is_empty()
+                && type_.chars().all(legal_char)
+                && subtype.chars().all(legal_char)
+        }
+        None => false,
+    }
+}
+
+/// See [YupdatesV0::new_items]
+pub async fn new_items(items: &[InputItem]) -> Result<NewInputItemsResponse> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_with_args(items, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_with_args<S>(
+    items: &[InputItem],
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<NewInputItemsResponse>
+where
+    S: AsRef<str>,
+{
+    check_before_send(items)?;
+    new_items_unchecked_with_args(items, http_client, base_url, token).await
+}
+
+/// The result of [YupdatesV0::new_items_lenient]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct LenientNewItemsResponse {
+    pub response: NewInputItemsResponse,
+    /// How many items were sent
+    pub sent: usize,
+    /// How many items were dropped, 0 unless there were more than [MAX_ITEMS_PER_CALL]
+    pub dropped: usize,
+}
+
+/// See [YupdatesV0::new_items_lenient]
+pub async fn new_items_lenient(items: &[InputItem]) -> Result<LenientNewItemsResponse> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_lenient_with_args(items, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_lenient_with_args<S>(
+    items: &[InputItem],
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<LenientNewItemsResponse>
+where
+    S: AsRef<str>,
+{
+    let sent = items.len().min(MAX_ITEMS_PER_CALL);
+    let dropped = items.len() - sent;
+    #[cfg(feature = "tracing")]
+    if dropped > 0 {
+        tracing::warn!(
+            sent = sent,
+            dropped = dropped,
+            "too many items for one call, the rest were dropped"
+        );
+    }
+    let response = new_items_with_args(&items[..sent], http_client, base_url, token).await?;
+    Ok(LenientNewItemsResponse {
+        response,
+        sent,
+        dropped,
+    })
+}
+
+/// See [YupdatesV0::feed_id]
+pub async fn feed_id() -> Result<String> {
+    Ok(new_items(&[]).await?.feed_id)
+}
+
+/// See [YupdatesV0::new_items_dry_run]
+pub async fn new_items_dry_run(items: &[InputItem]) -> Result<String> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_dry_run_with_args(items, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_dry_run_with_args<S>(
+    items: &[InputItem],
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    validate_items(items)?;
+    let response = new_items_unchecked_with_args(&[], http_client, base_url, token).await?;
+    Ok(response.feed_id)
+}
+
+/// See [YupdatesV0::new_items_unchecked]
+pub async fn new_items_unchecked(items: &[InputItem]) -> Result<NewInputItemsResponse> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_unchecked_with_args(items, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_unchecked_with_args<S>(
+    items: &[InputItem],
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<NewInputItemsResponse>
+where
+    S: AsRef<str>,
+{
+    if items.len() > MAX_ITEMS_PER_CALL {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "too many items ({}). See chunking example (new_items_all) to send {} at a time.",
+                items.len(),
+                MAX_ITEMS_PER_CALL
+            )),
+        });
+    }
+    post_items(items, None, http_client, base_url.as_ref(), token.as_ref()).await
+}
+
+/// The longest idempotency key that [YupdatesV0::new_items_idempotent] accepts
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// See [YupdatesV0::new_items_idempotent]
+pub async fn new_items_idempotent(items: &[InputItem], key: &str) -> Result<NewInputItemsResponse> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_idempotent_with_args(items, key, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_idempotent_with_args<S>(
+    items: &[InputItem],
+    key: &str,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<NewInputItemsResponse>
+where
+    S: AsRef<str>,
+{
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "the idempotency key must be 1 to {} characters ('{}')",
+                MAX_IDEMPOTENCY_KEY_LEN, key
+            )),
+        });
+    }
+    if !key.chars().all(|c| c.is_ascii_graphic()) {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "the idempotency key must be printable ASCII without spaces ('{}')",
+                key
+            )),
+        });
+    }
+    if items.len() > MAX_ITEMS_PER_CALL {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "too many items ({}). See chunking example (new_items_all) to send {} at a time.",
+                items.len(),
+                MAX_ITEMS_PER_CALL
+            )),
+        });
+    }
+    check_before_send(items)?;
+    post_items(
+        items,
+        Some(key),
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+    )
+    .await
+}
+
+// One call to the new items API
+async fn post_items(
+    items: &[InputItem],
+    idempotency_key: Option<&str>,
+    http_client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<NewInputItemsResponse> {
+    let items = normalized_items(items)?;
+    let data = NewItemsBody { items: &items };
+    let full_url = format!("{}items/", base_url);
+    let (code, text, headers) =
+        api_post(http_client, &full_url, token, idempotency_key, &data).await?;
+    if code == 200 {
+        json_from_body(&text)
+    } else {
+        // Including other 2XX/3XX in this category for now, they are unexpected
+        Err(api_error_with_headers(code, &headers, &text))
+    }
+}
+
+// The items with any item times normalized, see InputItem::item_time. Input IDs are checked
+// here too, so that no call sends an illegal one. The items are only copied if an item time
+// changes, since they can be large.
+fn normalized_items(items: &[InputItem]) -> Result<Cow<'_, [InputItem]>> {
+    let mut normalized = Cow::Borrowed(items);
+    for (index, item) in items.iter().enumerate() {
+        if let Some(problem) = input_id_problem(index, item) {
+            return Err(Error {
+                kind: Kind::IllegalParameter(problem),
+            });
+        }
+        if let Some(item_time) = &item.item_time {
+            let normalized_time =
+                normalized_item_time(index, item_time).map_err(|problem| Error {
+                    kind: Kind::IllegalParameter(problem),
+                })?;
+            if normalized_time != *item_time {
+                normalized.to_mut()[index].item_time = Some(normalized_time);
+            }
+        }
+    }
+    Ok(normalized)
+}
+
+fn input_id_problem(index: usize, item: &InputItem) -> Option<String> {
+    let input_id = item.input_id.as_deref()?;
+    let legal = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.');
+    if input_id.is_empty() || input_id.len() > MAX_INPUT_ID_LEN {
+        Some(format!(
+            "item {}: `input_id` must be 1 to {} characters ('{}')",
+            index, MAX_INPUT_ID_LEN, input_id
+        ))
+    } else if !input_id.chars().all(legal) {
+        Some(format!(
+            "item {}: `input_id` may only contain ASCII letters, digits, '-', '_', and '.' ('{}')",
+            index, input_id
+        ))
+    } else {
+        None
+    }
+}
+
+// The problem is worded like the others that validate_items lists
+fn normalized_item_time(index: usize, item_time: &str) -> std::result::Result<String, String> {
+    normalize_item_time(item_time).map_err(|e| {
+        let reason = match e.kind {
+            Kind::IllegalParameter(reason) | Kind::Deserialization(reason) => reason,
+            kind => format!("{:?}", kind),
+        };
+        format!(
+            "item {}: `item_time` is not a valid item time ('{}'): {}",
+            index, item_time, reason
+        )
+    })
+}
+
+/// See [YupdatesV0::new_items_all]
+pub async fn new_items_all(items: &[InputItem], sleep_ms: u64) -> Result<String> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_all_with_args(items, sleep_ms, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_all_with_args<S>(
+    items: &[InputItem],
+    sleep_ms: u64,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    let pacing = Duration::from_millis(sleep_ms);
+    new_items_all_with_pacing_with_args(items, pacing, http_client, base_url, token).await
+}
+
+/// The shortest pause between calls when sending items in batches
+pub const MIN_PACING: Duration = Duration::from_millis(5);
+
+/// See [YupdatesV0::new_items_all_with_pacing]
+pub async fn new_items_all_with_pacing(items: &[InputItem], pacing: Duration) -> Result<String> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_all_with_pacing_with_args(items, pacing, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_all_with_pacing_with_args<S>(
+    items: &[InputItem],
+    pacing: Duration,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    let responses = send_batches(
+        items,
+        BatchRun {
+            pacing,
+            retry: &RetryPolicy::default(),
+            cancel: None,
+            batching: &BatchOptions::default(),
+        },
+        |_| {},
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+    )
+    .await?;
+    first_feed_id(responses)
+}
+
+/// See [YupdatesV0::new_items_all_with_options]
+pub async fn new_items_all_with_options(
+    items: &[InputItem],
+    options: &BatchOptions,
+) -> Result<String> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_all_with_options_with_args(items, options, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_all_with_options_with_args<S>(
+    items: &[InputItem],
+    options: &BatchOptions,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    let responses = send_batches(
+        items,
+        BatchRun {
+            pacing: options.sleep,
+            retry: &RetryPolicy::default(),
+            cancel: None,
+            batching: options,
+        },
+        |_| {},
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+    )
+    .await?;
+    first_feed_id(responses)
+}
+
+/// How [YupdatesV0::new_items_all] retries a batch that failed with an error that
+/// [Error::is_retryable]: a connection problem, a timeout, throttling (HTTP 429), or, if
+/// `retry_unavailable` is set, a server error (HTTP 500, 502, 503, or 504).
+///
+/// The wait before retry N is `initial_backoff * 2^(N-1)`, but never more than `max_backoff`. If
+/// the server sent a `Retry-After` (see [Error::retry_after]), the wait is that instead, and if
+/// that is more than `max_backoff` the batch fails with the [Kind::RateLimited] error rather
+/// than being retried early. Other errors are not retried.
+///
+/// Every attempt of a batch sends the same key in the [crate::IDEMPOTENCY_KEY_HEADER] header,
+/// so that a retry after a timeout or a server error does not add items that the first attempt
+/// already added.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct RetryPolicy {
+    /// The most times a batch is sent, including the first. 1 turns retrying off. Default is 5.
+    pub max_attempts: u32,
+    /// Default is 500 ms.
+    pub initial_backoff: Duration,
+    /// Default is 30 seconds.
+    pub max_backoff: Duration,
+    /// Default is true.
+    pub retry_unavailable: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            retry_unavailable: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn should_retry(&self, e: &Error) -> bool {
+        e.is_retryable() && (self.retry_unavailable || !e.is_server_error())
+    }
+
+    // The wait before the given retry, 1 for the first. None if the server asked for a longer
+    // wait than `max_backoff`.
+    fn backoff(&self, retry: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        match retry_after {
+            Some(wait) => Some(wait).filter(|wait| *wait <= self.max_backoff),
+            None => Some(
+                self.initial_backoff
+                    .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+                    .min(self.max_backoff),
+            ),
+        }
+    }
+}
+
+/// See [YupdatesV0::new_items_all_with_retry]
+pub async fn new_items_all_with_retry(
+    items: &[InputItem],
+    sleep_ms: u64,
+    retry: &RetryPolicy,
+) -> Result<String> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_all_with_retry_with_args(items, sleep_ms, retry, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_all_with_retry_with_args<S>(
+    items: &[InputItem],
+    sleep_ms: u64,
+    retry: &RetryPolicy,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    let responses = send_batches(
+        items,
+        BatchRun {
+            pacing: Duration::from_millis(sleep_ms),
+            retry,
+            cancel: None,
+            batching: &BatchOptions::default(),
+        },
+        |_| {},
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+    )
+    .await?;
+    first_feed_id(responses)
+}
+
+/// See [YupdatesV0::new_items_all_detailed]
+pub async fn new_items_all_detailed(
+    items: &[InputItem],
+    sleep_ms: u64,
+) -> Result<Vec<NewInputItemsResponse>> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_all_detailed_with_args(items, sleep_ms, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_all_detailed_with_args<S>(
+    items: &[InputItem],
+    sleep_ms: u64,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<Vec<NewInputItemsResponse>>
+where
+    S: AsRef<str>,
+{
+    send_batches(
+        items,
+        BatchRun {
+            pacing: Duration::from_millis(sleep_ms),
+            retry: &RetryPolicy::default(),
+            cancel: None,
+            batching: &BatchOptions::default(),
+        },
+        |_| {},
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+    )
+    .await
+}
+
+/// See [YupdatesV0::new_items_all_with_results]
+pub async fn new_items_all_with_results(
+    items: &[InputItem],
+    sleep_ms: u64,
+) -> Result<(String, Option<Vec<ItemResult>>)> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_all_with_results_with_args(items, sleep_ms, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_all_with_results_with_args<S>(
+    items: &[InputItem],
+    sleep_ms: u64,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<(String, Option<Vec<ItemResult>>)>
+where
+    S: AsRef<str>,
+{
+    let responses =
+        new_items_all_detailed_with_args(items, sleep_ms, http_client, base_url, token).await?;
+    // Every batch but the last is full, see chunk_items
+    let mut item_results = Some(Vec::new());
+    for (batch, response) in responses.iter().enumerate() {
+        match (&mut item_results, &response.item_results) {
+            (Some(all), Some(results)) => all.extend(results.iter().cloned().map(|mut result| {
+                result.index += batch * MAX_ITEMS_PER_CALL;
+                result
+            })),
+            _ => item_results = None,
+        }
+    }
+    Ok((first_feed_id(responses)?, item_results))
+}
+
+/// See [YupdatesV0::new_items_from_ndjson]
+pub async fn new_items_from_ndjson<R>(reader: R, sleep_ms: u64) -> Result<BatchSummary>
+where
+    R: BufRead,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_from_ndjson_with_args(reader, sleep_ms, &http_client, base_url, token).await
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub async fn new_items_from_ndjson_with_args<R, S>(
+    reader: R,
+    sleep_ms: u64,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<BatchSummary>
+where
+    R: BufRead,
+    S: AsRef<str>,
+{
+    let pacing = Duration::from_millis(sleep_ms);
+    check_pacing(pacing)?;
+    let retry = RetryPolicy::default();
+    let (base_url, token) = (base_url.as_ref(), token.as_ref());
+
+    let mut items = read_input_items(reader);
+    let mut sent = BulkProgress::default();
+    loop {
+        let batch = items
+            .by_ref()
+            .take(MAX_ITEMS_PER_CALL)
+            .collect::<Result<Vec<InputItem>>>()
+            .map_err(|e| Error {
+                kind: Kind::BatchFailed(sent.batches_sent, sent.next_index, Box::new(e)),
+            })?;
+        if batch.is_empty() {
+            break;
+        }
+        let run = BatchRun {
+            pacing,
+            retry: &retry,
+            cancel: None,
+            batching: &BatchOptions::default(),
+        };
+        send_batches_from(&batch, run, |_| {}, &mut sent, http_client, base_url, token)
+            .await
+            // A batch that fails its checks is counted like one that is rejected
+            .map_err(|e| match e.kind {
+                Kind::BatchFailed(..) | Kind::IllegalResult(_) => e,
+                _ => Error {
+                    kind: Kind::BatchFailed(sent.batches_sent, sent.next_index, Box::new(e)),
+                },
+            })?;
+    }
+    match sent.feed_id {
+        None => Err(Error {
+            kind: Kind::IllegalParameter("the NDJSON has no items".to_string()),
+        }),
+        Some(feed_id) => Ok(BatchSummary {
+            feed_id,
+            batches_sent: sent.batches_sent,
+            items_sent: sent.next_index,
+        }),
+    }
+}
+
+/// See [YupdatesV0Csv::new_items_from_csv]
+#[cfg(feature = "csv")]
+pub async fn new_items_from_csv<P>(path: P, mapping: CsvMapping, sleep_ms: u64) -> Result<String>
+where
+    P: AsRef<Path>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_from_csv_with_args(path, mapping, sleep_ms, &http_client, base_url, token).await
+}
+
+#[cfg(feature = "csv")]
+pub async fn new_items_from_csv_with_args<P, S>(
+    path: P,
+    mapping: CsvMapping,
+    sleep_ms: u64,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<String>
+where
+    P: AsRef<Path>,
+    S: AsRef<str>,
+{
+    let items = read_csv(std::fs::File::open(path)?, mapping)?;
+    new_items_all_with_args(&items, sleep_ms, http_client, base_url, token).await
+}
+
+/// Where [YupdatesV0::new_items_all_with_progress] is up to
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BatchProgress {
+    /// How many items have been accepted so far.
+    pub items_sent: usize,
+    /// How many items are being sent in total.
+    pub total_items: usize,
+    /// The 1-based index of the batch that was just accepted, or 0 at the start.
+    pub batch_index: usize,
+    /// How many batches the items are sent in.
+    pub total_batches: usize,
+    /// The code the API returned for the last batch, or `None` at the start.
+    pub last_code: Option<u16>,
+}
+
+/// The most batches that [YupdatesV0::new_items_all_concurrent] sends at once
+pub const MAX_NEW_ITEMS_CONCURRENCY: usize = 4;
+
+/// The result of [YupdatesV0::new_items_all_concurrent]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BatchSummary {
+    pub feed_id: String,
+    /// How many batches were accepted.
+    pub batches_sent: usize,
+    /// How many items were accepted.
+    pub items_sent: usize,
+}
+
+/// See [YupdatesV0::new_items_all_with_progress]
+pub async fn new_items_all_with_progress<F>(
+    items: &[InputItem],
+    sleep_ms: u64,
+    progress: F,
+) -> Result<String>
+where
+    F: FnMut(BatchProgress),
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_all_with_progress_with_args(items, sleep_ms, progress, &http_client, base_url, token)
+        .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_all_with_progress_with_args<S, F>(
+    items: &[InputItem],
+    sleep_ms: u64,
+    progress: F,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<String>
+where
+    S: AsRef<str>,
+    F: FnMut(BatchProgress),
+{
+    let responses = send_batches(
+        items,
+        BatchRun {
+            pacing: Duration::from_millis(sleep_ms),
+            retry: &RetryPolicy::default(),
+            cancel: None,
+            batching: &BatchOptions::default(),
+        },
+        progress,
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+    )
+    .await?;
+    first_feed_id(responses)
+}
+
+/// See [YupdatesV0::new_items_all_cancellable]
+pub async fn new_items_all_cancellable(
+    items: &[InputItem],
+    sleep_ms: u64,
+    cancel: CancellationToken,
+) -> Result<String> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_all_cancellable_with_args(items, sleep_ms, cancel, &http_client, base_url, token)
+        .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_all_cancellable_with_args<S>(
+    items: &[InputItem],
+    sleep_ms: u64,
+    cancel: CancellationToken,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    let responses = send_batches(
+        items,
+        BatchRun {
+            pacing: Duration::from_millis(sleep_ms),
+            retry: &RetryPolicy::default(),
+            cancel: Some(&cancel),
+            batching: &BatchOptions::default(),
+        },
+        |_| {},
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+    )
+    .await?;
+    first_feed_id(responses)
+}
+
+/// How far [YupdatesV0::new_items_all_resume] got, to resume it from
+#[derive(PartialEq, Eq, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BulkProgress {
+    /// The index of the first item that was not sent, all of the items before it were accepted.
+    pub next_index: usize,
+    /// The feed the accepted batches were added to, `None` if no batch was accepted yet.
+    pub feed_id: Option<String>,
+    /// How many batches were accepted.
+    pub batches_sent: usize,
+}
+
+/// See [YupdatesV0::new_items_all_resume]
+pub async fn new_items_all_resume(
+    items: &[InputItem],
+    progress: &BulkProgress,
+    sleep_ms: u64,
+) -> std::result::Result<String, BulkError> {
+    let bulk_error = |error| BulkError {
+        progress: progress.clone(),
+        error,
+    };
+    let base_url = env_or_default_url().map_err(bulk_error)?;
+    let token = api_token().map_err(bulk_error)?;
+    let http_client = reqwest::Client::new();
+    new_items_all_resume_with_args(items, progress, sleep_ms, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(items = items.len(), next_index = progress.next_index)
+    )
+)]
+pub async fn new_items_all_resume_with_args<S>(
+    items: &[InputItem],
+    progress: &BulkProgress,
+    sleep_ms: u64,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> std::result::Result<String, BulkError>
+where
+    S: AsRef<str>,
+{
+    let mut progress = progress.clone();
+    let (base_url, token) = (base_url.as_ref(), token.as_ref());
+
+    let remaining = match items.get(progress.next_index..) {
+        Some(remaining) => remaining,
+        None => {
+            let error = Error {
+                kind: Kind::IllegalParameter(format!(
+                    "the progress resumes at item {}, but there are only {} items",
+                    progress.next_index,
+                    items.len()
+                )),
+            };
+            return Err(BulkError { progress, error });
+        }
+    };
+    let run = BatchRun {
+        pacing: Duration::from_millis(sleep_ms),
+        retry: &RetryPolicy::default(),
+        cancel: None,
+        batching: &BatchOptions::default(),
+    };
+    let sent = send_batches_from(
+        remaining,
+        run,
+        |_| {},
+        &mut progress,
+        http_client,
+        base_url,
+        token,
+    )
+    .await;
+    let error = match (sent, progress.feed_id.clone()) {
+        (Ok(_), Some(feed_id)) => return Ok(feed_id),
+        (Ok(_), None) => Error {
+            kind: Kind::IllegalParameter("there are no items to send".to_string()),
+        },
+        // The progress already says which batch failed, see From<BulkError> for Error
+        (
+            Err(Error {
+                kind: Kind::BatchFailed(_, _, cause),
+            }),
+            _,
+        ) => *cause,
+        (Err(error), _) => error,
+    };
+    Err(BulkError { progress, error })
+}
+
+// How send_batches paces, retries, and stops
+struct BatchRun<'a> {
+    pacing: Duration,
+    retry: &'a RetryPolicy,
+    cancel: Option<&'a CancellationToken>,
+    // Only the batch sizes are used, the pause is `pacing`
+    batching: &'a BatchOptions,
+}
+
+// Sends the items in batches, retrying throttled batches, reporting progress, stopping if it is
+// cancelled, and wrapping a failed batch in `Kind::BatchFailed`. Returns the response for each
+// batch, which all have the same feed ID.
+async fn send_batches<F>(
+    items: &[InputItem],
+    run: BatchRun<'_>,
+    progress: F,
+    http_client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<Vec<NewInputItemsResponse>>
+where
+    F: FnMut(BatchProgress),
+{
+    let mut sent = BulkProgress::default();
+    send_batches_from(
+        items,
+        run,
+        progress,
+        &mut sent,
+        http_client,
+        base_url,
+        token,
+    )
+    .await
+}
+
+// Like send_batches, for items that follow what `sent` says was already sent: the batches must go
+// to the same feed, the failed batch is counted from there, and the first batch waits `pacing`
+// like any other after the first. `sent` is updated as each batch is accepted. The progress that
+// is reported only counts these items.
+async fn send_batches_from<F>(
+    items: &[InputItem],
+    run: BatchRun<'_>,
+    mut progress: F,
+    sent: &mut BulkProgress,
+    http_client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<Vec<NewInputItemsResponse>>
+where
+    F: FnMut(BatchProgress),
+{
+    let BatchRun {
+        pacing,
+        retry,
+        cancel,
+        batching,
+    } = run;
+    check_pacing(pacing)?;
+    // Checked up front so that the index is into all of the items and nothing is half sent
+    check_before_send(items)?;
+    let items: &[InputItem] = &normalized_items(items)?;
+    let batches = chunk_items_with_options(items, batching)?;
+
+    let mut current = BatchProgress {
+        items_sent: 0,
+        total_items: items.len(),
+        batch_index: 0,
+        total_batches: batches.len(),
+        last_code: None,
+    };
+    progress(current.clone());
+
+    let mut responses: Vec<NewInputItemsResponse> = Vec::new();
+    for chunk in batches {
+        if sent.batches_sent > 0 {
+            sleep(pacing).await;
+        }
+        if cancel.is_some_and(CancellationToken::is_cancelled) {
+            return Err(Error {
+                kind: Kind::Cancelled {
+                    items_sent: sent.next_index,
+                    feed_id: sent.feed_id.clone(),
+                },
+            });
+        }
+        let response = send_batch_with_retry(chunk, retry, http_client, base_url, token)
+            .await
+            .map_err(|e| Error {
+                kind: Kind::BatchFailed(sent.batches_sent, sent.next_index, Box::new(e)),
+            })?;
+        check_same_feed(sent, &response.feed_id)?;
+        sent.next_index += chunk.len();
+        sent.batches_sent += 1;
+        current.items_sent += chunk.len();
+        current.batch_index += 1;
+        current.last_code = Some(response.code);
+        progress(current.clone());
+        responses.push(response);
+    }
+    Ok(responses)
+}
+
+// Records the feed of the first accepted batch, and fails if a later batch went to another feed
+fn check_same_feed(sent: &mut BulkProgress, feed_id: &str) -> Result<()> {
+    match &sent.feed_id {
+        None => sent.feed_id = Some(feed_id.to_string()),
+        Some(first) if first != feed_id => {
+            return Err(Error {
+                kind: Kind::IllegalResult(format!(
+                    "batch {} was added to feed {}, but earlier batches were added to feed {}",
+                    sent.batches_sent + 1,
+                    feed_id,
+                    first
+                )),
+            });
+        }
+        Some(_) => {}
+    }
+    Ok(())
+}
+
+fn check_pacing(pacing: Duration) -> Result<()> {
+    if pacing < MIN_PACING {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "the pause between calls ({:?}) must be {:?} or more",
+                pacing, MIN_PACING
+            )),
+        });
+    }
+    Ok(())
+}
+
+async fn send_batch_with_retry(
+    chunk: &[InputItem],
+    retry: &RetryPolicy,
+    http_client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<NewInputItemsResponse> {
+    let key = batch_idempotency_key(chunk);
+    let mut attempt = 1;
+    loop {
+        let e = match post_items(chunk, Some(&key), http_client, base_url, token).await {
+            Err(e) if attempt < retry.max_attempts && retry.should_retry(&e) => e,
+            result => return result,
+        };
+        let wait = match retry.backoff(attempt, e.retry_after()) {
+            Some(wait) => wait,
+            // Retrying sooner than the server asked would only be throttled again
+            None => return Err(e),
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(attempt, wait_ms = wait.as_millis() as u64, "retrying batch");
+        sleep(wait).await;
+        attempt += 1;
+    }
+}
+
+// The idempotency key of one batch: every attempt of the batch sends it, and no other call does.
+// The items are hashed with a random seed, so that sending the same items again later is a new
+// call.
+fn batch_idempotency_key(chunk: &[InputItem]) -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    chunk.len().hash(&mut hasher);
+    for item in chunk {
+        (&item.title, &item.canonical_url, &item.content).hash(&mut hasher);
+    }
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    format!("yupdates-batch-{:x}-{:016x}", nanos, hasher.finish())
+}
+
+fn first_feed_id(responses: Vec<NewInputItemsResponse>) -> Result<String> {
+    match responses.into_iter().next() {
+        None => Err(Error {
+            kind: Kind::IllegalResult("new items API success(es) without a feed ID".to_string()),
+        }),
+        Some(response) => Ok(response.feed_id),
+    }
+}
+
+/// See [YupdatesV0::new_items_all_dedup]
+pub async fn new_items_all_dedup(items: &[InputItem], sleep_ms: u64) -> Result<(String, usize)> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_all_dedup_with_args(items, sleep_ms, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()))
+)]
+pub async fn new_items_all_dedup_with_args<S>(
+    items: &[InputItem],
+    sleep_ms: u64,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<(String, usize)>
+where
+    S: AsRef<str>,
+{
+    let (items, skipped) = dedup_items(items);
+    let feed_id = new_items_all_with_args(&items, sleep_ms, http_client, base_url, token).await?;
+    Ok((feed_id, skipped))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// upsert_items(): paged GET $base_url/feeds/$feed_id, then POST $base_url/items/
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// What [YupdatesV0::upsert_items] does with an item whose URL is already in the feed
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum UpsertMode {
+    /// Leave the item in the feed as it is
+    #[default]
+    Skip,
+    /// Change the title and content of the item in the feed if they differ
+    Update,
+}
+
+/// The result of [YupdatesV0::upsert_items]
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct UpsertSummary {
+    /// How many items were added
+    pub inserted: usize,
+    /// How many items were already in the feed, or repeated an earlier item, and left as they are
+    pub skipped: usize,
+    /// How many items in the feed were updated
+    pub updated: usize,
+}
+
+/// See [YupdatesV0::upsert_items]
+pub async fn upsert_items<S>(
+    feed_id: S,
+    items: &[InputItem],
+    mode: UpsertMode,
+    lookback: usize,
+) -> Result<UpsertSummary>
+where
+    S: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    upsert_items_with_args(
+        feed_id.as_ref(),
+        items,
+        mode,
+        lookback,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(feed_id = feed_id.as_ref(), items = items.len())
+    )
+)]
+pub async fn upsert_items_with_args<S>(
+    feed_id: S,
+    items: &[InputItem],
+    mode: UpsertMode,
+    lookback: usize,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<UpsertSummary>
+where
+    S: AsRef<str>,
+{
+    let feed_id = validated_feed_id(feed_id.as_ref())?;
+    let (base_url, token) = (base_url.as_ref(), token.as_ref());
+    check_before_send(items)?;
+
+    let mut summary = UpsertSummary::default();
+    let (to_insert, to_update) = match mode {
+        UpsertMode::Skip => {
+            let key = DedupKey::CanonicalUrl;
+            let existing =
+                existing_keys(feed_id, lookback, key, http_client, base_url, token).await?;
+            let (to_insert, skipped_keys) = skip_existing(items, key, existing);
+            summary.skipped = skipped_keys.len();
+            (to_insert, Vec::new())
+        }
+        UpsertMode::Update => {
+            let (to_insert, to_update, skipped) =
+                items_to_update(feed_id, items, lookback, http_client, base_url, token).await?;
+            summary.skipped = skipped;
+            (to_insert, to_update)
+        }
+    };
+
+    if !to_insert.is_empty() {
+        let token_feed_id =
+            new_items_dry_run_with_args(&to_insert, http_client, base_url, token).await?;
+        if token_feed_id != feed_id {
+            return Err(Error {
+                kind: Kind::IllegalResult(format!(
+                    "the token adds items to feed {}, not to feed {}",
+                    token_feed_id, feed_id
+                )),
+            });
+        }
+    }
+    for (item_id, item) in to_update {
+        let patch = ItemPatch {
+            title: Some(item.title.clone()),
+            content: Some(item.content.clone()),
+            ..Default::default()
+        };
+        update_item_with_args(
+            feed_id,
+            item_id.as_str(),
+            &patch,
+            http_client,
+            base_url,
+            token,
+        )
+        .await?;
+        summary.updated += 1;
+    }
+    if !to_insert.is_empty() {
+        new_items_all_with_pacing_with_args(&to_insert, MIN_PACING, http_client, base_url, token)
+            .await?;
+        summary.inserted = to_insert.len();
+    }
+    Ok(summary)
+}
+
+// Sorts the items for [UpsertMode::Update]: the items whose URL is not in the feed are added, and
+// an item in the feed whose title or content differs is updated by item ID. Returns these and how
+// many items were skipped because they are unchanged or repeat the URL of an earlier input item.
+async fn items_to_update<'a>(
+    feed_id: &str,
+    items: &'a [InputItem],
+    lookback: usize,
+    http_client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<(Vec<InputItem>, Vec<(ItemId, &'a InputItem)>, usize)> {
+    // The newest item with each URL, and a hash of its title and content
+    let mut existing: HashMap<String, (ItemId, u64)> = HashMap::new();
+    if lookback > 0 {
+        // Content is needed to compare, and the API returns at most 10 items with content
+        let options = ReadOptions {
+            max_items: 10.min(lookback),
+            include_item_content: true,
+            fields: Some(
+                [
+                    "canonical_url",
+                    "content",
+                    "deleted",
+                    "item_id",
+                    "item_time",
+                    "title",
+                ]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            ),
+            ..Default::default()
+        };
+        let recent =
+            recent_items(feed_id, lookback, &options, http_client, base_url, token).await?;
+        for item in recent {
+            if item.canonical_url.is_empty() {
+                continue;
+            }
+            let hash = title_content_hash(&item.title, item.content.as_deref());
+            existing
+                .entry(item.canonical_url)
+                .or_insert((item.item_id, hash));
+        }
+    }
+
+    let mut to_insert = Vec::new();
+    let mut to_update = Vec::new();
+    let mut skipped = 0;
+    let mut urls = HashSet::new();
+    for item in items {
+        if item.canonical_url.is_empty() {
+            to_insert.push(item.clone());
+            continue;
+        }
+        if !urls.insert(item.canonical_url.as_str()) {
+            skipped += 1;
+            continue;
+        }
+        match existing.get(&item.canonical_url) {
+            None => to_insert.push(item.clone()),
+            Some((item_id, hash))
+                if *hash != title_content_hash(&item.title, Some(&item.content)) =>
+            {
+                to_update.push((item_id.clone(), item));
+            }
+            Some(_) => skipped += 1,
+        }
+    }
+    Ok((to_insert, to_update, skipped))
+}
+
+fn title_content_hash(title: &str, content: Option<&str>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (title, content.unwrap_or_default()).hash(&mut hasher);
+    hasher.finish()
+}
+
+// The items that are not deleted among the latest `lookback` items of the feed, newest first,
+// read in pages of `options.max_items`
+async fn recent_items(
+    feed_id: &str,
+    lookback: usize,
+    options: &ReadOptions,
+    http_client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<Vec<FeedItem>> {
+    let mut items = Vec::new();
+    let mut seen = 0;
+    page_back(
+        feed_id,
+        options,
+        lookback.div_ceil(options.max_items),
+        http_client,
+        base_url,
+        token,
+        |page| {
+            for item in page.into_iter().take(lookback - seen) {
+                seen += 1;
+                if !item.deleted {
+                    items.push(item);
+                }
+            }
+            Ok(seen < lookback)
+        },
+    )
+    .await?;
+    Ok(items)
+}
+
+// The keys of the items that are not deleted among the latest `lookback` items of the feed
+async fn existing_keys(
+    feed_id: &str,
+    lookback: usize,
+    key: DedupKey,
+    http_client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<HashSet<ItemKey>> {
+    let mut existing = HashSet::new();
+    if lookback > 0 {
+        let options = ReadOptions {
+            max_items: 50.min(lookback),
+            fields: Some(
+                ["canonical_url", "deleted", "item_id", "item_time", "title"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let recent =
+            recent_items(feed_id, lookback, &options, http_client, base_url, token).await?;
+        existing.extend(
+            recent
+                .iter()
+                .filter(|item| !item.canonical_url.is_empty())
+                .map(|item| key.key(&item.title, &item.canonical_url)),
+        );
+    }
+    Ok(existing)
+}
+
+// The skip mode of [YupdatesV0::upsert_items] and [YupdatesV0::new_items_all_skip_existing]:
+// returns the items to add, in order, and the keys of the items whose key is in `existing` or
+// repeats an earlier input item. Items without a URL are always added.
+fn skip_existing(
+    items: &[InputItem],
+    key: DedupKey,
+    mut existing: HashSet<ItemKey>,
+) -> (Vec<InputItem>, Vec<ItemKey>) {
+    let mut to_add = Vec::new();
+    let mut skipped_keys = Vec::new();
+    for item in items {
+        if item.canonical_url.is_empty() {
+            to_add.push(item.clone());
+            continue;
+        }
+        let item_key = key.key(&item.title, &item.canonical_url);
+        if existing.contains(&item_key) {
+            skipped_keys.push(item_key);
+        } else {
+            existing.insert(item_key);
+            to_add.push(item.clone());
+        }
+    }
+    (to_add, skipped_keys)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// new_items_all_skip_existing(): POST $base_url/items/ (dry run), paged GET
+// $base_url/feeds/$feed_id, then POST $base_url/items/
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// What makes an input item the same as an item in the feed, see
+/// [YupdatesV0::new_items_all_skip_existing]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum DedupKey {
+    /// The same `canonical_url`
+    #[default]
+    CanonicalUrl,
+    /// The same `title` and `canonical_url`, for feeds where one URL has many items
+    TitleAndUrl,
+}
+
+/// The key of an item, the fields that [DedupKey] compares. `title` is only set for
+/// [DedupKey::TitleAndUrl].
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct ItemKey {
+    pub title: Option<String>,
+    pub canonical_url: String,
+}
+
+impl DedupKey {
+    fn key(self, title: &str, canonical_url: &str) -> ItemKey {
+        ItemKey {
+            title: match self {
+                DedupKey::CanonicalUrl => None,
+                DedupKey::TitleAndUrl => Some(title.to_string()),
+            },
+            canonical_url: canonical_url.to_string(),
+        }
+    }
+}
+
+/// The result of [YupdatesV0::new_items_all_skip_existing]
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct DedupSummary {
+    /// The feed that the token adds items to
+    pub feed_id: String,
+    /// How many items were added
+    pub submitted: usize,
+    /// How many items were already in the feed, or repeated an earlier input item
+    pub skipped: usize,
+    /// The key of each skipped item, in input order
+    pub skipped_keys: Vec<ItemKey>,
+}
+
+/// See [YupdatesV0::new_items_all_skip_existing]
+pub async fn new_items_all_skip_existing(
+    items: &[InputItem],
+    lookback: usize,
+    key: DedupKey,
+) -> Result<DedupSummary> {
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    new_items_all_skip_existing_with_args(items, lookback, key, &http_client, base_url, token).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len(), lookback = lookback))
+)]
+pub async fn new_items_all_skip_existing_with_args<S>(
+    items: &[InputItem],
+    lookback: usize,
+    key: DedupKey,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<DedupSummary>
+where
+    S: AsRef<str>,
+{
+    let (base_url, token) = (base_url.as_ref(), token.as_ref());
+    check_before_send(items)?;
+    // An empty call only to learn which feed the token is for
+    let feed_id = new_items_unchecked_with_args(&[], http_client, base_url, token)
+        .await?
+        .feed_id;
+    let existing = existing_keys(&feed_id, lookback, key, http_client, base_url, token).await?;
+    let (to_submit, skipped_keys) = skip_existing(items, key, existing);
+    if !to_submit.is_empty() {
+        new_items_all_with_pacing_with_args(&to_submit, MIN_PACING, http_client, base_url, token)
+            .await?;
+    }
+    Ok(DedupSummary {
+        feed_id,
+        submitted: to_submit.len(),
+        skipped: skipped_keys.len(),
+        skipped_keys,
+    })
+}
+
+/// The body of a new items call. It borrows the items, they are only serialized.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize)]
+pub struct NewItemsBody<'a> {
+    pub(crate) items: &'a [InputItem],
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// read_items(): GET $base_url/feeds/$feed_id
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// Extra options for reading items.
+///
+/// If you don't supply `item_time_after` or `item_time_before`, the latest items are queried.
+/// You cannot supply `item_time_after` and `item_time_before` at the same time.
+///
+/// An item time is a unix epoch millisecond with an optional 5 digit suffix. In practice, you
+/// would only use the suffix form if you got that as the item time string from the service.
+/// Examples: 1234, 1661564013555, "1661564013555", "1661564013555.00003", "123456.789"
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ReadOptions {
+    /// The number of items to return, must be 1 <= N <= 50. Default is 10. May not be more than
+    /// 10 if `include_item_content` is true.
+    pub max_items: usize,
+
+    /// If true, populate each FeedItem with the full item content.
+    pub include_item_content: bool,
+
+    /// Only return items that come after this item time (non-inclusive).
+    pub item_time_after: Option<String>,
+
+    /// Only return items that come before this item time (non-inclusive).
+    pub item_time_before: Option<String>,
+
+    /// The order of the returned items. Default is [Order::Newest].
+    pub order: Order,
+
+    /// If true, also return items that were deleted (see [FeedItem::deleted]). Default is false,
+    /// in which case any deleted items in the response are also filtered out client-side.
+    pub include_deleted: bool,
+
+    /// Only return these [FeedItem] fields (see [crate::models::FEED_ITEM_FIELDS]), which saves
+    /// bandwidth when polling frequently. `item_id` and `item_time` are always included. The
+    /// default is `None`, which returns all fields.
+    pub fields: Option<Vec<String>>,
+
+    /// If true, skip the client's read cache and refresh it with the result. This only has an
+    /// effect with the `cache` feature (see [crate::cache::ReadCache]) and is never sent to the
+    /// API. Default is false.
+    pub cache_bypass: bool,
+}
+
+/// The order that items are returned in when reading from a feed.
+///
+/// The API returns the newest items first. Choosing [Order::Oldest] reverses the returned items
+/// client-side: it changes the order within the result, not which items are selected. To process
+/// a feed chronologically, combine it with `item_time_after` and move the bound forward to the
+/// last item time you received.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum Order {
+    #[default]
+    Newest,
+    Oldest,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self {
+            max_items: 10,
+            include_item_content: false,
+            item_time_after: None,
+            item_time_before: None,
+            order: Order::Newest,
+            include_deleted: false,
+            fields: None,
+            cache_bypass: false,
+        }
+    }
+}
+
+impl ReadOptions {
+    /// Validate the options and return the exact query string that a read would send.
+    ///
+    /// The parameters are always sorted by key, so the same options produce the same string
+    /// across calls and SDK versions. This is useful if you sign requests or key a cache on the
+    /// query string out-of-band.
+    pub fn canonical_query_string(&self) -> Result<String> {
+        let validated = validate_read_options(self)?;
+        serde_urlencoded::to_string(validated.query_params()).map_err(|e| Error {
+            kind: Kind::IllegalParameter(format!("could not encode query string: {}", e)),
+        })
+    }
+
+    // The single source of the read query parameters, sorted by key. Expects validated options.
+    fn query_params(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![
+            ("max_items", self.max_items.to_string()),
+            (
+                "include_item_content",
+                self.include_item_content.to_string(),
+            ),
+        ];
+        if let Some(item_time_after) = &self.item_time_after {
+            query.push(("item_time_after", item_time_after.clone()));
+        }
+        if let Some(item_time_before) = &self.item_time_before {
+            query.push(("item_time_before", item_time_before.clone()));
+        }
+        // Only sent when set, so the query string for existing option combinations is unchanged
+        if self.include_deleted {
+            query.push(("include_deleted", true.to_string()));
+        }
+        if let Some(fields) = &self.fields {
+            query.push(("fields", fields.join(",")));
+        }
+        // Stable sort: ties (there are none today) keep their insertion order
+        query.sort_by_key(|(key, _)| *key);
+        query
+    }
+}
+
+/// See [YupdatesV0::read_items]
+pub async fn read_items<S>(feed_id: S, read_options: Option<&ReadOptions>) -> Result<Vec<FeedItem>>
+where
+    S: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    read_items_with_args(
+        feed_id.as_ref(),
+        read_options,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn read_items_with_args<S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<Vec<FeedItem>>
+where
+    S: AsRef<str>,
+{
+    let response =
+        read_items_response_with_args(feed_id, read_options, http_client, base_url, token).await?;
+    Ok(response.feed_items)
+}
+
+/// See [YupdatesV0::read_items_response]
+pub async fn read_items_response<S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+) -> Result<ReadFeedItemsResponse>
+where
+    S: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    read_items_response_with_args(
+        feed_id.as_ref(),
+        read_options,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn read_items_response_with_args<S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<ReadFeedItemsResponse>
+where
+    S: AsRef<str>,
+{
+    read_items_response_as_with_args(feed_id, read_options, http_client, base_url, token).await
+}
+
+/// See [YupdatesV0::read_items_as]
+pub async fn read_items_as<T, S>(feed_id: S, read_options: Option<&ReadOptions>) -> Result<Vec<T>>
+where
+    T: DeserializeOwned,
+    S: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    read_items_as_with_args(
+        feed_id.as_ref(),
+        read_options,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn read_items_as_with_args<T, S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<Vec<T>>
+where
+    T: DeserializeOwned,
+    S: AsRef<str>,
+{
+    let response =
+        read_items_response_as_with_args(feed_id, read_options, http_client, base_url, token)
+            .await?;
+    Ok(response.feed_items)
+}
+
+/// The whole response with the items deserialized into your own type, see
+/// [YupdatesV0::read_items_as] and [YupdatesV0::read_items_response]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn read_items_response_as_with_args<T, S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<ReadFeedItemsResponse<T>>
+where
+    T: DeserializeOwned,
+    S: AsRef<str>,
+{
+    let text = read_items_text(feed_id, read_options, http_client, base_url, token).await?;
+    feed_items_response_from_text(&text, read_options)
+}
+
+/// See [YupdatesV0::read_items_conditional]
+pub async fn read_items_conditional<S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    etag: Option<&str>,
+) -> Result<ReadOutcome>
+where
+    S: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    read_items_conditional_with_args(
+        feed_id.as_ref(),
+        read_options,
+        etag,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn read_items_conditional_with_args<S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    etag: Option<&str>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<ReadOutcome>
+where
+    S: AsRef<str>,
+{
+    let (text, etag) = match read_items_text_conditional(
+        feed_id,
+        read_options,
+        etag,
+        None,
+        http_client,
+        base_url,
+        token,
+    )
+    .await?
+    {
+        None => return Ok(ReadOutcome::NotModified),
+        Some(modified) => modified,
+    };
+    let response: ReadFeedItemsResponse = feed_items_response_from_text(&text, read_options)?;
+    Ok(ReadOutcome::Modified {
+        items: response.feed_items,
+        etag,
+    })
+}
+
+// Parses a successful read body, dropping deleted items unless requested and applying the order
+fn feed_items_response_from_text<T>(
+    text: &str,
+    read_options: Option<&ReadOptions>,
+) -> Result<ReadFeedItemsResponse<T>>
+where
+    T: DeserializeOwned,
+{
+    let response: RawFeedItemsResponse = json_from_body(text)?;
+
+    let include_deleted = read_options.is_some_and(|o| o.include_deleted);
+    let mut feed_items = response
+        .feed_items
+        .into_iter()
+        // The index is the position in the response, counting the deleted items too
+        .enumerate()
+        .filter(|(_, value)| include_deleted || value.get("deleted") != Some(&Value::Bool(true)))
+        .map(|(idx, value)| {
+            json_from_value(value).map_err(|e| Error {
+                kind: Kind::Deserialization(format!("feed item at index {}: {}", idx, e)),
+            })
+        })
+        .collect::<Result<Vec<T>>>()?;
+
+    if read_options.map(|o| o.order) == Some(Order::Oldest) {
+        feed_items.reverse();
+    }
+    #[cfg(feature = "tracing")]
+    tracing::debug!(items = feed_items.len(), "read feed items");
+    Ok(ReadFeedItemsResponse {
+        code: response.code,
+        feed_items,
+        extra: response.extra,
+    })
+}
+
+/// See [YupdatesV0::read_items_raw]
+pub async fn read_items_raw<S>(feed_id: S, read_options: Option<&ReadOptions>) -> Result<Value>
+where
+    S: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    read_items_raw_with_args(
+        feed_id.as_ref(),
+        read_options,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn read_items_raw_with_args<S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<Value>
+where
+    S: AsRef<str>,
+{
+    let text = read_items_text(feed_id, read_options, http_client, base_url, token).await?;
+    json_from_body(&text)
+}
+
+/// See [YupdatesV0::read_items_direct]
+pub async fn read_items_direct<S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+) -> Result<Vec<FeedItem>>
+where
+    S: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    read_items_direct_with_args(
+        feed_id.as_ref(),
+        read_options,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn read_items_direct_with_args<S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<Vec<FeedItem>>
+where
+    S: AsRef<str>,
+{
+    let (url, query) = read_items_url_and_query(feed_id.as_ref(), read_options, base_url.as_ref())?;
+    let res = api_get_response_with_query(http_client, &url, &query, token.as_ref()).await?;
+    let code = res.status().as_u16();
+    if code != 200 {
+        let headers = res.headers().clone();
+        return Err(api_error_with_headers(code, &headers, &res.text().await?));
+    }
+    let response: ReadFeedItemsResponse = json_from_streamed_body(res).await?;
+
+    let mut feed_items = response.feed_items;
+    if !read_options.is_some_and(|o| o.include_deleted) {
+        feed_items.retain(|item| !item.deleted);
+    }
+    if read_options.map(|o| o.order) == Some(Order::Oldest) {
+        feed_items.reverse();
+    }
+    #[cfg(feature = "tracing")]
+    tracing::debug!(items = feed_items.len(), "read feed items");
+    Ok(feed_items)
+}
+
+// Validates the inputs, makes the call, and returns the body of a successful response
+async fn read_items_text<S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    match read_items_text_conditional(
+        feed_id,
+        read_options,
+        None,
+        None,
+        http_client,
+        base_url,
+        token,
+    )
+    .await?
+    {
+        Some((text, _)) => Ok(text),
+        // Not expected without If-None-Match
+        None => Err(api_error(304, "")),
+    }
+}
+
+// Like read_items_text, but sends If-None-Match when there is an ETag and `q` when there is a
+// search query. Returns None for a 304, otherwise the body and the ETag of the response, if any.
+async fn read_items_text_conditional<S>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    etag: Option<&str>,
+    search_query: Option<&str>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<Option<(String, Option<String>)>>
+where
+    S: AsRef<str>,
+{
+    let (url, mut query) =
+        read_items_url_and_query(feed_id.as_ref(), read_options, base_url.as_ref())?;
+    if let Some(search_query) = search_query {
+        query.push(("q", search_query.to_string()));
+        query.sort_by_key(|(key, _)| *key);
+    }
+    let (code, text, headers) =
+        api_get_with_query(http_client, &url, &query, token.as_ref(), etag).await?;
+    if code == 200 {
+        let response_etag = headers
+            .get(reqwest::header::ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+        Ok(Some((text, response_etag)))
+    } else if code == 304 && etag.is_some() {
+        Ok(None)
+    } else {
+        // Including other 2XX/3XX in this category for now, they are unexpected
+        Err(api_error_with_headers(code, &headers, &text))
+    }
+}
+
+// Validates the inputs of a read, and returns the URL and query parameters to call
+fn read_items_url_and_query(
+    feed_id: &str,
+    read_options: Option<&ReadOptions>,
+    base_url: &str,
+) -> Result<(String, Vec<(&'static str, String)>)> {
+    let feed_id_str = validated_feed_id(feed_id)?;
+
+    let validated = match read_options.as_ref() {
+        None => ReadOptions {
+            ..Default::default()
+        },
+        Some(given) => validate_read_options(given)?,
+    };
+    let url = format!("{}feeds/{}/", base_url, feed_id_str);
+    Ok((url, validated.query_params()))
+}
+
+/// The response from reading a feed. `T` is [FeedItem] unless you use [YupdatesV0::read_items_as].
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct ReadFeedItemsResponse<T = FeedItem> {
+    pub code: u16,
+    pub feed_items: Vec<T>,
+    /// Any other top-level fields in the response, so that they are not lost if the API adds
+    /// them before the SDK models them.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The result of [YupdatesV0::read_items_conditional]. This distinguishes "unchanged" from a
+/// feed that is empty, which is `Modified` with no items.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ReadOutcome {
+    /// The items, and the ETag to send next time if the API returned one.
+    Modified {
+        items: Vec<FeedItem>,
+        etag: Option<String>,
+    },
+    /// Nothing changed since the response with the given ETag.
+    NotModified,
+}
+
+// The items are deserialized one at a time so that errors can report which item failed
+#[derive(Deserialize)]
+struct RawFeedItemsResponse {
+    code: u16,
+    feed_items: Vec<Value>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// search_items(): GET $base_url/feeds/$feed_id?q=$query
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// See [YupdatesV0::search_items]
+pub async fn search_items<S>(
+    feed_id: S,
+    query: &str,
+    read_options: Option<&ReadOptions>,
+) -> Result<Vec<FeedItem>>
+where
+    S: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    search_items_with_args(
+        feed_id.as_ref(),
+        query,
+        read_options,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn search_items_with_args<S>(
+    feed_id: S,
+    query: &str,
+    read_options: Option<&ReadOptions>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<Vec<FeedItem>>
+where
+    S: AsRef<str>,
+{
+    if query.trim().is_empty() {
+        return Err(Error {
+            kind: Kind::IllegalParameter("the search query cannot be empty".to_string()),
+        });
+    }
+    let text = match read_items_text_conditional(
+        feed_id,
+        read_options,
+        None,
+        Some(query),
+        http_client,
+        base_url,
+        token,
+    )
+    .await?
+    {
+        Some((text, _)) => text,
+        // Not expected without If-None-Match
+        None => return Err(api_error(304, "")),
+    };
+    let response: ReadFeedItemsResponse = feed_items_response_from_text(&text, read_options)?;
+    Ok(response.feed_items)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// read_items_filtered(): paged GET $base_url/feeds/$feed_id
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// The most pages that [YupdatesV0::read_items_filtered] reads in one call
+pub const MAX_FILTERED_PAGES: usize = 100;
+
+/// The result of [YupdatesV0::read_items_filtered]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct FilteredItems {
+    /// The matching items, in the requested order.
+    pub items: Vec<FeedItem>,
+    /// How many items the API returned across all pages, matching or not.
+    pub scanned: usize,
+    /// How many pages were read.
+    pub pages: usize,
+    /// True if paging reached the start of the feed, false if it stopped early because of
+    /// `stop_after` or [MAX_FILTERED_PAGES].
+    pub exhausted: bool,
+}
+
+/// See [YupdatesV0::read_items_filtered]
+pub async fn read_items_filtered<S, F>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    predicate: F,
+    stop_after: Option<usize>,
+) -> Result<FilteredItems>
+where
+    S: AsRef<str>,
+    F: Fn(&FeedItem) -> bool,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    read_items_filtered_with_args(
+        feed_id.as_ref(),
+        read_options,
+        predicate,
+        stop_after,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn read_items_filtered_with_args<S, F>(
+    feed_id: S,
+    read_options: Option<&ReadOptions>,
+    predicate: F,
+    stop_after: Option<usize>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<FilteredItems>
+where
+    S: AsRef<str>,
+    F: Fn(&FeedItem) -> bool,
+{
+    let given = read_options.cloned().unwrap_or_default();
+    let mut result = FilteredItems {
+        items: Vec::new(),
+        scanned: 0,
+        pages: 0,
+        exhausted: false,
+    };
+    if stop_after == Some(0) {
+        return Ok(result);
+    }
+    let (pages, exhausted) = page_back(
+        feed_id.as_ref(),
+        &given,
+        MAX_FILTERED_PAGES,
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+        |page| {
+            result.scanned += page.len();
+            for item in page {
+                if (given.include_deleted || !item.deleted) && predicate(&item) {
+                    result.items.push(item);
+                    if stop_after == Some(result.items.len()) {
+                        return Ok(false);
+                    }
+                }
+            }
+            Ok(true)
+        },
+    )
+    .await?;
+    result.pages = pages;
+    result.exhausted = exhausted;
+
+    if given.order == Order::Oldest {
+        result.items.reverse();
+    }
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        items = result.items.len(),
+        scanned = result.scanned,
+        pages = result.pages,
+        "filtered feed items"
+    );
+    Ok(result)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// read_items_in_range(): paged GET $base_url/feeds/$feed_id
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// How [YupdatesV0::read_items_in_range] treats the bounds of its range. The default is an
+/// exclusive `after` and no limit.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct RangeOptions {
+    /// Include the items at exactly `after`, so the range is `[after, before)` and consecutive
+    /// ranges like "the items from last Tuesday" do not miss or repeat an item
+    pub inclusive_after: bool,
+    /// If set, stop after this many items. The limit keeps the newest items of the range.
+    pub limit: Option<usize>,
+}
+
+/// See [YupdatesV0::read_items_in_range]
+pub async fn read_items_in_range<S, T>(
+    feed_id: S,
+    after: T,
+    before: T,
+    read_options: Option<&ReadOptions>,
+    range_options: Option<&RangeOptions>,
+) -> Result<Vec<FeedItem>>
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    let base_url = env_or_default_url()?;
+    let token = api_token()?;
+    let http_client = reqwest::Client::new();
+    read_items_in_range_with_args(
+        feed_id.as_ref(),
+        after.as_ref(),
+        before.as_ref(),
+        read_options,
+        range_options,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn read_items_in_range_with_args<S, T>(
+    feed_id: S,
+    after: T,
+    before: T,
+    read_options: Option<&ReadOptions>,
+    range_options: Option<&RangeOptions>,
+    http_client: &reqwest::Client,
+    base_url: S,
+    token: S,
+) -> Result<Vec<FeedItem>>
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    let given = read_options.cloned().unwrap_or_default();
+    let range = range_options.cloned().unwrap_or_default();
+    let (after, before) = range_bounds(&given, after, before)?;
+    if range.limit == Some(0) {
+        return Ok(Vec::new());
+    }
+    let past_start = |item_time: &str| {
+        if range.inclusive_after {
+            item_time < after.as_str()
+        } else {
+            item_time <= after.as_str()
+        }
+    };
+    read_range(
+        feed_id.as_ref(),
+        &given,
+        past_start,
+        before,
+        range.limit.unwrap_or(usize::MAX),
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+    )
+    .await
+}
+
+// Normalizes the bounds of a range, which must be in order, and checks that the options do not
+// have item times of their own
+fn range_bounds<T: AsRef<str>>(
+    given: &ReadOptions,
+    after: T,
+    before: T,
+) -> Result<(String, String)> {
+    if given.item_time_after.is_some() || given.item_time_before.is_some() {
+        return Err(Error {
+            kind: Kind::IllegalParameter(
+                "the range is given by `after` and `before`, not by the item times in the options"
+                    .to_string(),
+            ),
+        });
+    }
+    let after = normalize_item_time(after)?;
+    let before = normalize_item_time(before)?;
+    if after >= before {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "`after` ({}) must be earlier than `before` ({})",
+                after, before
+            )),
+        });
+    }
+    Ok((after, before))
+}
+
+// Pages back from `before` (exclusive) and collects the items until one is `past_start` (given
+// its normalized item time) or there are `limit` of them
+#[allow(clippy::too_many_arguments)]
+async fn read_range<F>(
+    feed_id: &str,
+    given: &ReadOptions,
+    past_start: F,
+    before: String,
+    limit: usize,
+    http_client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<Vec<FeedItem>>
+where
+    F: Fn(&str) -> bool,
+{
+    let options = ReadOptions {
+        item_time_before: Some(before),
+        ..given.clone()
+    };
+    let mut items = Vec::new();
+    page_back(
+        feed_id,
+        &options,
+        usize::MAX,
+        http_client,
+        base_url,
+        token,
+        |page| {
+            for item in page {
+                if past_start(&normalize_item_time(&item.item_time)?) {
+                    return Ok(false);
+                }
+                if given.include_deleted || !item.deleted {
+                    items.push(item);
+                    if items.len() >= limit {
+                        return Ok(false);
+                    }
+                }
+            }
+            Ok(true)
+        },
+    )
+    .await?;
+
+    if given.order == Order::Oldest {
+        items.reverse();
     }
+    Ok(items)
 }
 
 // ─────────────────────────────────────────────────────────────────────────────────────────────────
-// new_items(): POST $base_url/items/
+// count_items(): paged GET $base_url/feeds/$feed_id
 // ─────────────────────────────────────────────────────────────────────────────────────────────────
 
-#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
-pub struct NewInputItemsResponse {
-    pub code: u16,
-    pub feed_id: String,
-    pub message: String,
-}
+/// The most items that [YupdatesV0::count_items] pages through
+pub const MAX_COUNTED_ITEMS: u64 = 5_000;
 
-/// See [YupdatesV0::new_items]
-pub async fn new_items(items: &[InputItem]) -> Result<NewInputItemsResponse> {
+// The largest page the API returns without content
+const COUNT_PAGE_SIZE: usize = 50;
+
+/// See [YupdatesV0::count_items]
+pub async fn count_items<S>(feed_id: S) -> Result<u64>
+where
+    S: AsRef<str>,
+{
     let base_url = env_or_default_url()?;
     let token = api_token()?;
     let http_client = reqwest::Client::new();
-    new_items_with_args(items, &http_client, base_url, token).await
+    count_items_with_args(feed_id.as_ref(), &http_client, &base_url, &token).await
 }
 
-pub async fn new_items_with_args<S>(
-    items: &[InputItem],
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn count_items_with_args<S>(
+    feed_id: S,
     http_client: &reqwest::Client,
     base_url: S,
     token: S,
-) -> Result<NewInputItemsResponse>
+) -> Result<u64>
 where
     S: AsRef<str>,
 {
-    if items.len() > 10 {
-        return Err(Error {
-            kind: Kind::IllegalParameter(format!(
-                "too many items ({}). See chunking example (new_items_all) to send 10 at a time.",
-                items.len()
-            )),
-        });
-    }
-    let data = NewItemsBody {
-        items: items.to_vec(),
+    let feed_id = validated_feed_id(feed_id.as_ref())?;
+    let options = ReadOptions {
+        max_items: COUNT_PAGE_SIZE,
+        fields: Some(vec![
+            "deleted".to_string(),
+            "item_id".to_string(),
+            "item_time".to_string(),
+        ]),
+        ..Default::default()
     };
-    let full_url = format!("{}items/", base_url.as_ref());
-    let (code, text) = api_post(http_client, &full_url, token.as_ref(), &data).await?;
-    if code == 200 {
-        Ok(json_from_str(&text)?)
-    } else {
-        // Including other 2XX/3XX in this category for now, they are unexpected
-        Err(api_error(code, &text))
-    }
+    let max_pages = (MAX_COUNTED_ITEMS as usize).div_ceil(COUNT_PAGE_SIZE);
+    let mut count = 0;
+    page_back(
+        feed_id,
+        &options,
+        max_pages,
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+        |page| {
+            count += page.iter().filter(|item| !item.deleted).count() as u64;
+            Ok(true)
+        },
+    )
+    .await?;
+    Ok(count)
 }
 
-/// See [YupdatesV0::new_items_all]
-pub async fn new_items_all(items: &[InputItem], sleep_ms: u64) -> Result<String> {
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// dump_feed_ndjson(): paged GET $base_url/feeds/$feed_id
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// See [YupdatesV0::dump_feed_ndjson]
+pub async fn dump_feed_ndjson<S, W>(feed_id: S, writer: W, limit: Option<usize>) -> Result<usize>
+where
+    S: AsRef<str>,
+    W: Write,
+{
     let base_url = env_or_default_url()?;
     let token = api_token()?;
     let http_client = reqwest::Client::new();
-    new_items_all_with_args(items, sleep_ms, &http_client, base_url, token).await
+    dump_feed_ndjson_with_args(
+        feed_id.as_ref(),
+        writer,
+        limit,
+        &http_client,
+        &base_url,
+        &token,
+    )
+    .await
 }
 
-pub async fn new_items_all_with_args<S>(
-    items: &[InputItem],
-    sleep_ms: u64,
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn dump_feed_ndjson_with_args<S, W>(
+    feed_id: S,
+    mut writer: W,
+    limit: Option<usize>,
     http_client: &reqwest::Client,
     base_url: S,
     token: S,
-) -> Result<String>
+) -> Result<usize>
 where
     S: AsRef<str>,
+    W: Write,
 {
-    if sleep_ms < 5 {
-        return Err(Error {
-            kind: Kind::IllegalParameter(format!("sleep_ms ({}) must be 5 or more", sleep_ms)),
-        });
-    }
-    let sleep_duration = Duration::from_millis(sleep_ms);
-
-    let base_url = base_url.as_ref();
-    let token = token.as_ref();
-
-    let mut feed_id = None;
-    let mut chunks = items.chunks(10).peekable();
-    while let Some(chunk) = chunks.next() {
-        let response = new_items_with_args(chunk, http_client, base_url, token).await?;
-        if feed_id.is_none() {
-            feed_id = Some(response.feed_id);
-        }
-        if chunks.peek().is_some() {
-            sleep(sleep_duration).await;
-        }
-    }
-
-    match feed_id {
-        None => Err(Error {
-            kind: Kind::IllegalResult("new items API success(es) without a feed ID".to_string()),
-        }),
-        Some(fid) => Ok(fid),
+    let mut written = 0;
+    if limit == Some(0) {
+        return Ok(written);
     }
-}
-
-#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
-pub struct NewItemsBody {
-    items: Vec<InputItem>,
+    // Content is limited to 10 items per call
+    let options = ReadOptions {
+        max_items: 10,
+        include_item_content: true,
+        ..Default::default()
+    };
+    page_back(
+        feed_id.as_ref(),
+        &options,
+        usize::MAX,
+        http_client,
+        base_url.as_ref(),
+        token.as_ref(),
+        |page| {
+            for item in page.iter().filter(|item| !item.deleted) {
+                write_ndjson_line(&mut writer, item)?;
+                written += 1;
+                if limit == Some(written) {
+                    break;
+                }
+            }
+            writer.flush()?;
+            Ok(limit != Some(written))
+        },
+    )
+    .await?;
+    #[cfg(feature = "tracing")]
+    tracing::debug!(items = written, "dumped feed items");
+    Ok(written)
 }
 
 // ─────────────────────────────────────────────────────────────────────────────────────────────────
-// read_items(): GET $base_url/feeds/$feed_id
+// wait_for_item(): repeated GET $base_url/feeds/$feed_id
 // ─────────────────────────────────────────────────────────────────────────────────────────────────
 
-/// Extra options for reading items.
-///
-/// If you don't supply `item_time_after` or `item_time_before`, the latest items are queried.
-/// You cannot supply `item_time_after` and `item_time_before` at the same time.
-///
-/// An item time is a unix epoch millisecond with an optional 5 digit suffix. In practice, you
-/// would only use the suffix form if you got that as the item time string from the service.
-/// Examples: 1234, 1661564013555, "1661564013555", "1661564013555.00003", "123456.789"
-#[derive(PartialEq, Eq, Clone, Debug)]
-pub struct ReadOptions {
-    /// The number of items to return, must be 1 <= N <= 50. Default is 10. May not be more than
-    /// 10 if `include_item_content` is true.
-    pub max_items: usize,
-
-    /// If true, populate each FeedItem with the full item content.
-    pub include_item_content: bool,
-
-    /// Only return items that come after this item time (non-inclusive).
-    pub item_time_after: Option<String>,
-
-    /// Only return items that come before this item time (non-inclusive).
-    pub item_time_before: Option<String>,
-}
-
-impl Default for ReadOptions {
-    fn default() -> Self {
-        Self {
-            max_items: 10,
-            include_item_content: false,
-            item_time_after: None,
-            item_time_before: None,
-        }
-    }
-}
+/// The shortest poll interval that [YupdatesV0::wait_for_item] uses
+pub const MIN_WAIT_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
-/// See [YupdatesV0::read_items]
-pub async fn read_items<S>(feed_id: S, read_options: Option<&ReadOptions>) -> Result<Vec<FeedItem>>
+/// See [YupdatesV0::wait_for_item]
+pub async fn wait_for_item<S, F>(
+    feed_id: S,
+    predicate: F,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> Result<FeedItem>
 where
     S: AsRef<str>,
+    F: Fn(&FeedItem) -> bool,
 {
     let base_url = env_or_default_url()?;
     let token = api_token()?;
     let http_client = reqwest::Client::new();
-    read_items_with_args(
+    wait_for_item_with_args(
         feed_id.as_ref(),
-        read_options,
+        predicate,
+        timeout,
+        poll_interval,
         &http_client,
         &base_url,
         &token,
@@ -257,122 +4156,468 @@ where
     .await
 }
 
-pub async fn read_items_with_args<S>(
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_id = feed_id.as_ref()))
+)]
+pub async fn wait_for_item_with_args<S, F>(
     feed_id: S,
-    read_options: Option<&ReadOptions>,
+    predicate: F,
+    timeout: Duration,
+    poll_interval: Duration,
     http_client: &reqwest::Client,
     base_url: S,
     token: S,
-) -> Result<Vec<FeedItem>>
+) -> Result<FeedItem>
 where
     S: AsRef<str>,
+    F: Fn(&FeedItem) -> bool,
 {
-    let feed_id_str = feed_id.as_ref().trim();
-    if feed_id_str.len() != 45 {
-        return Err(Error {
-            kind: Kind::IllegalParameter(format!(
-                "`feed_id` is expected to be 45 characters ('{}')",
-                feed_id.as_ref()
-            )),
-        });
-    }
-
-    let validated = match read_options.as_ref() {
-        None => ReadOptions {
-            ..Default::default()
-        },
-        Some(given) => validate_read_options(given)?,
-    };
+    let poll_interval = poll_interval.max(MIN_WAIT_POLL_INTERVAL);
+    let feed_id = feed_id.as_ref();
+    let base_url = base_url.as_ref();
+    let token = token.as_ref();
 
-    let mut query = vec![
-        ("max_items", validated.max_items.to_string()),
-        (
-            "include_item_content",
-            validated.include_item_content.to_string(),
-        ),
-    ];
-    if let Some(item_time_after) = validated.item_time_after {
-        query.push(("item_time_after", item_time_after));
-    }
-    if let Some(item_time_before) = validated.item_time_before {
-        query.push(("item_time_before", item_time_before));
+    let start = Instant::now();
+    let mut polls = 0;
+    loop {
+        polls += 1;
+        let items = read_items_with_args(feed_id, None, http_client, base_url, token).await?;
+        if let Some(item) = items.into_iter().find(|item| predicate(item)) {
+            return Ok(item);
+        }
+        if start.elapsed() + poll_interval > timeout {
+            return Err(Error {
+                kind: Kind::Timeout(format!(
+                    "no matching item after {} polls over {:?}",
+                    polls,
+                    start.elapsed()
+                )),
+            });
+        }
+        sleep(poll_interval).await;
     }
-
-    let url = format!("{}feeds/{}/", base_url.as_ref(), feed_id_str);
-    let (code, text) = api_get_with_query(http_client, &url, &query, token.as_ref()).await?;
-    let response: ReadFeedItemsResponse = if code == 200 {
-        json_from_str(&text)?
-    } else {
-        // Including other 2XX/3XX in this category for now, they are unexpected
-        return Err(api_error(code, &text));
-    };
-
-    Ok(response.feed_items)
-}
-
-#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
-pub struct ReadFeedItemsResponse {
-    pub code: u16,
-    pub feed_items: Vec<FeedItem>,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────────────────────────
 // IMPL
 // ─────────────────────────────────────────────────────────────────────────────────────────────────
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(method = "GET", endpoint = full_url, status = tracing::field::Empty)
+    )
+)]
 async fn api_get(
     http_client: &reqwest::Client,
     full_url: &str,
     token: &str,
-) -> Result<(u16, String)> {
-    let res = http_client
-        .get(full_url)
+) -> Result<(u16, String, HeaderMap)> {
+    acquire().await;
+    let res = send(http_client.get(full_url).header(X_AUTH_TOKEN_HEADER, token)).await?;
+    let code = res.status().as_u16();
+    #[cfg(feature = "tracing")]
+    trace_status(code);
+    let headers = res.headers().clone();
+    let text = res.text().await?;
+    Ok((code, text, headers))
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(method = "DELETE", endpoint = full_url, status = tracing::field::Empty)
+    )
+)]
+async fn api_delete(
+    http_client: &reqwest::Client,
+    full_url: &str,
+    token: &str,
+) -> Result<(u16, String, HeaderMap)> {
+    acquire().await;
+    let res = send(
+        http_client
+            .delete(full_url)
+            .header(X_AUTH_TOKEN_HEADER, token),
+    )
+    .await?;
+    let code = res.status().as_u16();
+    #[cfg(feature = "tracing")]
+    trace_status(code);
+    let headers = res.headers().clone();
+    let text = res.text().await?;
+    Ok((code, text, headers))
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(method = "PATCH", endpoint = full_url, status = tracing::field::Empty)
+    )
+)]
+async fn api_patch<T>(
+    http_client: &reqwest::Client,
+    full_url: &str,
+    token: &str,
+    data: &T,
+) -> Result<(u16, String, HeaderMap)>
+where
+    T: Serialize + ?Sized,
+{
+    acquire().await;
+    let request = http_client
+        .patch(full_url)
         .header(X_AUTH_TOKEN_HEADER, token)
-        .send()
-        .await?;
+        .json(data);
+    let res = send(request).await?;
     let code = res.status().as_u16();
+    #[cfg(feature = "tracing")]
+    trace_status(code);
+    let headers = res.headers().clone();
     let text = res.text().await?;
-    Ok((code, text))
+    Ok((code, text, headers))
 }
 
+// Sends If-None-Match if `if_none_match` is given
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(method = "GET", endpoint = url, status = tracing::field::Empty)
+    )
+)]
 async fn api_get_with_query<T>(
     http_client: &reqwest::Client,
     url: &str,
     query: &T,
     token: &str,
-) -> Result<(u16, String)>
+    if_none_match: Option<&str>,
+) -> Result<(u16, String, HeaderMap)>
 where
     T: Serialize + ?Sized,
 {
-    let res = http_client
+    acquire().await;
+    let mut req = http_client
         .get(url)
         .header(X_AUTH_TOKEN_HEADER, token)
-        .query(query)
-        .send()
-        .await?;
+        .query(query);
+    if let Some(etag) = if_none_match {
+        req = req.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    let res = send(req).await?;
     let code = res.status().as_u16();
+    #[cfg(feature = "tracing")]
+    trace_status(code);
+    let headers = res.headers().clone();
     let text = res.text().await?;
-    Ok((code, text))
+    Ok((code, text, headers))
+}
+
+// Like api_get_with_query, but returns the response before its body is read
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(method = "GET", endpoint = url, status = tracing::field::Empty)
+    )
+)]
+async fn api_get_response_with_query<T>(
+    http_client: &reqwest::Client,
+    url: &str,
+    query: &T,
+    token: &str,
+) -> Result<reqwest::Response>
+where
+    T: Serialize + ?Sized,
+{
+    acquire().await;
+    let request = http_client
+        .get(url)
+        .header(X_AUTH_TOKEN_HEADER, token)
+        .query(query);
+    let res = send(request).await?;
+    #[cfg(feature = "tracing")]
+    trace_status(res.status().as_u16());
+    Ok(res)
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(method = "POST", endpoint = full_url, status = tracing::field::Empty)
+    )
+)]
 async fn api_post<T>(
     http_client: &reqwest::Client,
     full_url: &str,
     token: &str,
+    idempotency_key: Option<&str>,
     data: &T,
-) -> Result<(u16, String)>
+) -> Result<(u16, String, HeaderMap)>
 where
     T: Serialize + ?Sized,
 {
-    let res = http_client
+    acquire().await;
+    let mut request = http_client
         .post(full_url)
-        .header(X_AUTH_TOKEN_HEADER, token)
-        .json(data)
-        .send()
-        .await?;
+        .header(X_AUTH_TOKEN_HEADER, token);
+    if let Some(key) = idempotency_key {
+        request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+    }
+    let (body, gzipped) = encode_body(serde_json::to_vec(data)?)?;
+    request = request.header(reqwest::header::CONTENT_TYPE, "application/json");
+    if gzipped {
+        request = request.header(reqwest::header::CONTENT_ENCODING, "gzip");
+    }
+    let res = send(request.body(body)).await?;
     let code = res.status().as_u16();
+    #[cfg(feature = "tracing")]
+    trace_status(code);
+    let headers = res.headers().clone();
     let text = res.text().await?;
-    Ok((code, text))
+    Ok((code, text, headers))
+}
+
+// Sends an API request, through the middleware of the running call if there is one
+async fn send(request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+    #[cfg(feature = "reqwest-middleware")]
+    return crate::middleware::send(request).await;
+    #[cfg(not(feature = "reqwest-middleware"))]
+    Ok(request.send().await?)
+}
+
+/// The most bytes of a response body that a [Kind::Deserialization] error includes
+pub const ERROR_BODY_LIMIT: usize = 2048;
+
+// Parses the body of a successful response. If that fails, the error includes the start of the
+// body, which shows schema drift at a glance. Error responses never get here (see api_error), so
+// the body of an authentication failure is not included.
+fn json_from_body<T>(text: &str) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    json_from_bytes(text.as_bytes())
+}
+
+// Like json_from_body, for a body that was not copied into a String. It is only decoded as text
+// for an error.
+fn json_from_bytes<T>(body: &[u8]) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    // A proxy or firewall can answer with a page of its own and a 200 status
+    let start = body
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(body.len());
+    if !body[start..].starts_with(b"{") && !body[start..].starts_with(b"[") {
+        let text = String::from_utf8_lossy(body);
+        let trimmed = text.trim_start();
+        let got = if trimmed.is_empty() {
+            "an empty body"
+        } else if trimmed.starts_with('<') {
+            "text/html"
+        } else {
+            "text"
+        };
+        let first_line = trimmed.lines().next().unwrap_or_default();
+        return Err(Error {
+            kind: Kind::Deserialization(format!(
+                "expected JSON but got {} (possible proxy interception); first line: {}",
+                got,
+                truncated(first_line)
+            )),
+        });
+    }
+    json_from_slice(body).map_err(|e| Error {
+        kind: Kind::Deserialization(format!(
+            "{}; response body: {}",
+            e,
+            truncated(&String::from_utf8_lossy(body))
+        )),
+    })
+}
+
+// How many received chunks of a streamed body can wait for the parser
+const STREAMED_CHUNKS: usize = 16;
+
+// Parses the body of a successful response on a blocking thread as its chunks arrive, so that the
+// whole body is never held at once. A body that does not start like JSON is read in full instead
+// and reported like json_from_body does. Other errors cannot include the body, it was not kept.
+async fn json_from_streamed_body<T>(mut res: reqwest::Response) -> Result<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let mut first = Vec::new();
+    while let Some(chunk) = res.chunk().await? {
+        first.extend_from_slice(&chunk);
+        if first.iter().any(|b| !b.is_ascii_whitespace()) {
+            break;
+        }
+    }
+    let start = first.iter().position(|b| !b.is_ascii_whitespace());
+    if !start.is_some_and(|start| first[start] == b'{' || first[start] == b'[') {
+        first.extend_from_slice(&res.bytes().await?);
+        return json_from_bytes(&first);
+    }
+
+    let (sender, receiver) = mpsc::channel(STREAMED_CHUNKS);
+    let parser = tokio::task::spawn_blocking(move || {
+        json_from_reader(ChunkReader {
+            receiver,
+            chunk: Bytes::new(),
+        })
+    });
+    let mut received = Ok(());
+    let mut chunk = Some(Bytes::from(first));
+    while let Some(next) = chunk {
+        // If the parser stopped early, it has the error
+        if sender.send(next).await.is_err() {
+            break;
+        }
+        chunk = match res.chunk().await {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                received = Err(e);
+                None
+            }
+        };
+    }
+    drop(sender);
+    let parsed = parser.await.map_err(|e| Error {
+        kind: Kind::IllegalResult(format!("the response parser did not finish: {}", e)),
+    })?;
+    received?;
+    parsed.map_err(|e| Error {
+        kind: Kind::Deserialization(format!("{}; the streamed response body was not kept", e)),
+    })
+}
+
+// Reads the chunks of a streamed body as they are sent, blocking while it waits for the next one
+struct ChunkReader {
+    receiver: mpsc::Receiver<Bytes>,
+    chunk: Bytes,
+}
+
+impl std::io::Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => self.chunk = chunk,
+                None => return Ok(0),
+            }
+        }
+        let len = buf.len().min(self.chunk.len());
+        buf[..len].copy_from_slice(&self.chunk.split_to(len));
+        Ok(len)
+    }
+}
+
+// The message of an error response: the API's, or the start of the body if it is not the API's
+// JSON
+fn error_message(text: &str) -> String {
+    match serde_json::from_str::<ApiErrorData>(text) {
+        Ok(data) => msg_from_api_error_data(&data),
+        Err(_) => truncated(text.trim()),
+    }
+}
+
+// At most ERROR_BODY_LIMIT bytes of the text, noting how much was left out
+fn truncated(text: &str) -> String {
+    let mut end = text.len().min(ERROR_BODY_LIMIT);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    if end < text.len() {
+        format!("{} ({} more bytes)", &text[..end], text.len() - end)
+    } else {
+        text.to_string()
+    }
+}
+
+// Reads pages of `max_items` items back in time from `item_time_before` (or the latest item) and
+// passes each one to `on_page` until it returns false or `max_pages` pages were read. Pages are
+// newest first and include deleted items, so that a short page reliably means the start of the
+// feed. Returns the number of pages read and whether the start of the feed was reached.
+async fn page_back<F>(
+    feed_id: &str,
+    options: &ReadOptions,
+    max_pages: usize,
+    http_client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+    mut on_page: F,
+) -> Result<(usize, bool)>
+where
+    F: FnMut(Vec<FeedItem>) -> Result<bool>,
+{
+    if options.item_time_after.is_some() {
+        return Err(Error {
+            kind: Kind::IllegalParameter(
+                "`item_time_after` is not supported when paging, paging goes back in time"
+                    .to_string(),
+            ),
+        });
+    }
+    let mut page_options = ReadOptions {
+        order: Order::Newest,
+        include_deleted: true,
+        ..options.clone()
+    };
+    let mut pages = 0;
+    while pages < max_pages {
+        let page = read_items_with_args(feed_id, Some(&page_options), http_client, base_url, token)
+            .await?;
+        pages += 1;
+        let full_page = page.len() >= page_options.max_items;
+        page_options.item_time_before = page.last().map(|item| item.item_time.clone());
+        if !on_page(page)? {
+            return Ok((pages, false));
+        }
+        if !full_page || page_options.item_time_before.is_none() {
+            return Ok((pages, true));
+        }
+    }
+    Ok((pages, false))
+}
+
+// Records the status on the current HTTP span. Only the method, URL and status are recorded,
+// the token is a header and is never part of a span.
+#[cfg(feature = "tracing")]
+fn trace_status(code: u16) {
+    tracing::Span::current().record("status", code);
+    // A 304 is only possible when it was asked for, see read_items_text_conditional
+    if code == 200 || code == 304 {
+        tracing::debug!(status = code, "API call succeeded");
+    } else {
+        tracing::warn!(status = code, "API call failed");
+    }
+}
+
+fn validated_item_id(item_id: &str) -> Result<&str> {
+    let item_id_str = item_id.trim();
+    let legal_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if item_id_str.is_empty() || !item_id_str.chars().all(legal_char) {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "`item_id` is expected to be non-empty and only letters, digits, '-' and '_' \
+                 ('{}')",
+                item_id
+            )),
+        });
+    }
+    Ok(item_id_str)
 }
 
 fn validate_read_options(given: &ReadOptions) -> Result<ReadOptions> {
@@ -408,10 +4653,38 @@ fn validate_read_options(given: &ReadOptions) -> Result<ReadOptions> {
         None => None,
         Some(it) => Some(normalize_item_time(it)?),
     };
+    let fields = match &given.fields {
+        None => None,
+        Some(requested) => {
+            let mut fields = FEED_ITEM_REQUIRED_FIELDS
+                .iter()
+                .map(|f| f.to_string())
+                .collect::<Vec<String>>();
+            for field in requested {
+                if !FEED_ITEM_FIELDS.contains(&field.as_str()) {
+                    return Err(Error {
+                        kind: Kind::IllegalParameter(format!(
+                            "unknown field in `fields`: '{}'",
+                            field
+                        )),
+                    });
+                }
+                fields.push(field.to_string());
+            }
+            // Sorted so the query string is canonical regardless of how they were given
+            fields.sort();
+            fields.dedup();
+            Some(fields)
+        }
+    };
     Ok(ReadOptions {
         max_items: given.max_items,
         include_item_content: given.include_item_content,
         item_time_after,
         item_time_before,
+        order: given.order,
+        include_deleted: given.include_deleted,
+        fields,
+        cache_bypass: given.cache_bypass,
     })
 }
@@ -0,0 +1,106 @@
+//! An in-process cache for reads, enabled with the `cache` feature
+//!
+//! Configure an [crate::clients::AsyncYupdatesClient] with a [ReadCache] (see
+//! [crate::clients::AsyncYupdatesClient::with_read_cache]) and identical reads within the TTL are
+//! answered without calling the API. This suits tools that render the same feed repeatedly. Set
+//! [crate::api::ReadOptions::cache_bypass] to force a refresh.
+use crate::api::ReadOptions;
+use crate::errors::Result;
+use crate::models::FeedItem;
+use crate::validated_feed_id;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// A TTL cache of read results, keyed by feed ID and the normalized [ReadOptions].
+///
+/// Entries expire `ttl` after they were stored. When the cache holds `max_entries` entries,
+/// expired entries are dropped first and then the oldest entry is evicted to make room.
+/// A cache can be shared between clients by wrapping it in an `Arc`.
+pub struct ReadCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<CacheKey, CacheEntry>>,
+}
+
+// The feed ID, the canonical query string, and the order (which is applied client-side)
+type CacheKey = (String, String, String);
+
+struct CacheEntry {
+    stored: Instant,
+    items: Vec<FeedItem>,
+}
+
+impl ReadCache {
+    pub fn new(ttl: Duration, max_entries: usize) -> ReadCache {
+        ReadCache {
+            ttl,
+            max_entries,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The number of entries, including any that have expired but were not evicted yet.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Drop every entry.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<Vec<FeedItem>> {
+        let mut entries = self.lock();
+        match entries.get(key) {
+            Some(entry) if entry.stored.elapsed() < self.ttl => Some(entry.items.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, key: CacheKey, items: Vec<FeedItem>) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.lock();
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.stored.elapsed() < self.ttl);
+            if entries.len() >= self.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.stored)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        let stored = Instant::now();
+        entries.insert(key, CacheEntry { stored, items });
+    }
+
+    // Validates like a read would, so that equivalent options share an entry
+    pub(crate) fn key(feed_id: &str, options: Option<&ReadOptions>) -> Result<CacheKey> {
+        let default_options = ReadOptions::default();
+        let options = options.unwrap_or(&default_options);
+        Ok((
+            validated_feed_id(feed_id)?.to_string(),
+            options.canonical_query_string()?,
+            format!("{:?}", options.order),
+        ))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<CacheKey, CacheEntry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
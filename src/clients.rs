@@ -2,6 +2,7 @@
 //!
 //! There is an `async` client and a synchronous version of it which hides the need for you to
 //! set up an async runtime. See the top-level documentation of this library for examples of each.
+//! If you create many short-lived synchronous clients, see [sync::shared_client].
 //!
 //! If you want control over the `base_url`, `token`, or `http_client`, you can instantiate the
 //! [AsyncYupdatesClient] and [sync::SyncYupdatesClient] structs directly.
@@ -9,14 +10,61 @@
 //! The HTTP client can be configured with many options, see the Reqwest library's documentation
 //! for [ClientBuilder](https://docs.rs/reqwest/latest/reqwest/struct.ClientBuilder.html), and be
 //! sure to adjust the documentation version to match the right version of this dependency (see
-//! this library's `Cargo.toml`).
+//! this library's `Cargo.toml`). [AsyncYupdatesClient::builder] passes the connection pool and
+//! keep-alive options through for you.
+#[cfg(feature = "csv")]
+use crate::api::new_items_from_csv_with_args;
 use crate::api::{
-    new_items_all_with_args, new_items_with_args, ping_with_args, read_items_with_args,
-    NewInputItemsResponse, PingResponse, ReadOptions,
+    check_before_send, chunk_items, count_items_with_args, delete_feed_dry_run_with_args,
+    delete_feed_with_args, delete_item_with_args, dump_feed_ndjson_with_args, get_item_with_args,
+    list_feeds_with_args, new_feed_with_args, new_items_all_cancellable_with_args,
+    new_items_all_dedup_with_args, new_items_all_detailed_with_args,
+    new_items_all_resume_with_args, new_items_all_skip_existing_with_args, new_items_all_with_args,
+    new_items_all_with_options_with_args, new_items_all_with_pacing_with_args,
+    new_items_all_with_progress_with_args, new_items_all_with_results_with_args,
+    new_items_all_with_retry_with_args, new_items_dry_run_with_args,
+    new_items_from_ndjson_with_args, new_items_idempotent_with_args, new_items_lenient_with_args,
+    new_items_unchecked_with_args, new_items_with_args, ping_timed_with_args, ping_with_args,
+    read_items_as_with_args, read_items_conditional_with_args, read_items_direct_with_args,
+    read_items_filtered_with_args, read_items_in_range_with_args, read_items_raw_with_args,
+    read_items_response_with_args, read_items_with_args, search_items_with_args,
+    update_item_with_args, upsert_items_with_args, wait_for_item_with_args, BatchOptions,
+    BatchProgress, BatchSummary, BulkProgress, DedupKey, DedupSummary, DeleteConfirmation,
+    DeleteFeedResponse, DeleteItemResponse, FilteredItems, ItemResult, LenientNewItemsResponse,
+    NewFeedOptions, NewFeedResponse, NewInputItemsResponse, Order, PingResponse, RangeOptions,
+    ReadFeedItemsResponse, ReadOptions, ReadOutcome, RetryPolicy, UpsertMode, UpsertSummary,
+    MAX_ITEMS_PER_CALL, MAX_NEW_ITEMS_CONCURRENCY, MIN_PACING,
 };
-use crate::errors::Result;
-use crate::models::{FeedItem, InputItem};
-use crate::{api_token, env_or_default_url};
+#[cfg(feature = "cache")]
+use crate::cache::ReadCache;
+use crate::compression::{compressed, RequestCompression};
+use crate::errors::{BulkError, Error, Kind, Result};
+#[cfg(feature = "csv")]
+use crate::items::CsvMapping;
+#[cfg(feature = "reqwest-middleware")]
+use crate::middleware::through;
+use crate::models::{FeedInfo, FeedItem, InputItem, ItemId, ItemPatch};
+use crate::rate_limit::{limited, RateLimiter};
+use crate::{
+    api_token, env_or_default_url, is_http_url, normalize_base_url, normalize_item_time,
+    validate_token,
+};
+use futures::{Stream, StreamExt};
+#[cfg(feature = "reqwest-middleware")]
+use reqwest_middleware::ClientWithMiddleware;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::io::{BufRead, Write};
+#[cfg(feature = "csv")]
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+use tokio_util::sync::CancellationToken;
 
 // ─────────────────────────────────────────────────────────────────────────────────────────────────
 // ASYNC CLIENT
@@ -31,6 +79,7 @@ pub fn new_async_client() -> Result<AsyncYupdatesClient> {
         base_url,
         http_client,
         token,
+        extensions: ClientExtensions::default(),
     })
 }
 
@@ -45,156 +94,2124 @@ pub fn new_async_client_with_http_client(
         base_url,
         http_client,
         token,
+        extensions: ClientExtensions::default(),
     })
 }
 
+/// Create an [AsyncYupdatesClient] instance using the default configuration sources that sends
+/// every API call through `middleware`, see [crate::middleware]
+#[cfg(feature = "reqwest-middleware")]
+pub fn new_async_client_with_middleware(
+    middleware: ClientWithMiddleware,
+) -> Result<AsyncYupdatesClient> {
+    Ok(new_async_client()?.with_middleware(middleware))
+}
+
+/// Builds an [AsyncYupdatesClient] whose HTTP client has the given connection pool and keep-alive
+/// options, see [AsyncYupdatesClient::builder].
+///
+/// The options are passed through to reqwest's
+/// [ClientBuilder](https://docs.rs/reqwest/latest/reqwest/struct.ClientBuilder.html), and the
+/// ones that are not set keep reqwest's defaults. For other options, build a `reqwest::Client`
+/// yourself and use [new_async_client_with_http_client].
+#[derive(Debug)]
+pub struct AsyncYupdatesClientBuilder {
+    http_client: reqwest::ClientBuilder,
+}
+
+impl AsyncYupdatesClientBuilder {
+    /// How long an idle connection is kept open for reuse. reqwest's default is 90 seconds.
+    pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.http_client = self.http_client.pool_idle_timeout(timeout);
+        self
+    }
+
+    /// The most idle connections kept open per host. reqwest's default is no limit. With 0, every
+    /// request opens a fresh connection.
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.http_client = self.http_client.pool_max_idle_per_host(max);
+        self
+    }
+
+    /// Set `SO_KEEPALIVE` on every socket, with this interval. reqwest's default is to not set it.
+    pub fn with_tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.http_client = self.http_client.tcp_keepalive(interval);
+        self
+    }
+
+    /// Whether to ask for gzip or brotli compressed responses, with an `Accept-Encoding` header,
+    /// and decompress them. Requires the `response-compression` feature.
+    ///
+    /// This is a bandwidth optimization for high-volume reads of items with their content: a
+    /// 100 item page of HTML articles shrinks to under a quarter of its size with gzip (333 KB to
+    /// 75 KB in the SDK's tests). The
+    /// feature turns compression on for every client by default (it is reqwest's default once
+    /// its `gzip` and `brotli` features are enabled), so this is mostly for turning it off.
+    #[cfg(feature = "response-compression")]
+    pub fn with_compression(mut self, enabled: bool) -> Self {
+        self.http_client = self.http_client.gzip(enabled).brotli(enabled);
+        self
+    }
+
+    /// Create the client, using the default configuration sources for the base URL and token
+    /// like [new_async_client] does
+    pub fn build(self) -> Result<AsyncYupdatesClient> {
+        new_async_client_with_http_client(self.http_client.build()?)
+    }
+
+    /// Create the client with the given base URL and token
+    pub fn build_with_args(self, base_url: String, token: String) -> Result<AsyncYupdatesClient> {
+        Ok(AsyncYupdatesClient {
+            base_url: normalize_base_url(base_url),
+            http_client: self.http_client.build()?,
+            token: validate_token(token)?,
+            extensions: ClientExtensions::default(),
+        })
+    }
+}
+
+// The base URL with a trailing slash, if it is an absolute http(s) URL
+fn checked_base_url<S: Into<String>>(base_url: S) -> Result<String> {
+    let base_url = normalize_base_url(base_url);
+    if !is_http_url(&base_url) {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "the base URL must be an absolute http(s) URL ('{}')",
+                base_url
+            )),
+        });
+    }
+    Ok(base_url)
+}
+
 /// Wraps everything needed to make async calls to the API
 ///
 /// Instantiate this struct directly if you want total control. See [new_async_client] impl for
 /// the default values.
+#[derive(Clone)]
 pub struct AsyncYupdatesClient {
     pub base_url: String,
     pub http_client: reqwest::Client,
     pub token: String,
+    /// The settings that are set with methods, see [ClientExtensions]. Use `Default::default()`
+    /// when creating the struct directly.
+    pub extensions: ClientExtensions,
+}
+
+/// The settings of an [AsyncYupdatesClient] that are set with methods: the rate limiter, the
+/// request compression, and the read cache of the `cache` feature and the middleware of the
+/// `reqwest-middleware` feature.
+///
+/// The fields are private, so that new settings, or enabling a feature anywhere in a build, do
+/// not break the code that creates an [AsyncYupdatesClient] with a struct literal. Set them with
+/// methods like [AsyncYupdatesClient::with_rate_limiter] and
+/// [AsyncYupdatesClient::with_read_cache].
+#[derive(Clone, Default)]
+pub struct ClientExtensions {
+    // Limits the rate of every request the client makes
+    rate_limiter: Option<Arc<RateLimiter>>,
+    // Gzips large request bodies
+    request_compression: Option<RequestCompression>,
+    // Sends every API call through this middleware client instead of `http_client`
+    #[cfg(feature = "reqwest-middleware")]
+    middleware: Option<ClientWithMiddleware>,
+    // Answers repeated reads without calling the API
+    #[cfg(feature = "cache")]
+    read_cache: Option<Arc<ReadCache>>,
+}
+
+impl ClientExtensions {
+    // The cached reads do not carry over to another base URL
+    fn for_other_base_url(&self) -> ClientExtensions {
+        ClientExtensions {
+            rate_limiter: self.rate_limiter.clone(),
+            request_compression: self.request_compression,
+            #[cfg(feature = "reqwest-middleware")]
+            middleware: self.middleware.clone(),
+            #[cfg(feature = "cache")]
+            read_cache: None,
+        }
+    }
 }
 
 // Rust does not support async traits, but here we "implement" `crate::api::YupdatesV0`
 impl AsyncYupdatesClient {
+    /// Start building a client with connection pool and keep-alive options, see
+    /// [AsyncYupdatesClientBuilder]
+    pub fn builder() -> AsyncYupdatesClientBuilder {
+        AsyncYupdatesClientBuilder {
+            http_client: reqwest::Client::builder(),
+        }
+    }
+
+    /// Use this cache for [AsyncYupdatesClient::read_items] and
+    /// [AsyncYupdatesClient::read_items_with_options]. Pass a clone of the same `Arc` to share
+    /// a cache between clients.
+    #[cfg(feature = "cache")]
+    pub fn with_read_cache(mut self, read_cache: Arc<ReadCache>) -> Self {
+        self.extensions.read_cache = Some(read_cache);
+        self
+    }
+
+    /// Limit every request this client makes to `requests_per_second`, see
+    /// [RateLimiter::per_second]. Clones of the client share the limit.
+    pub fn max_requests_per_second(self, requests_per_second: u32) -> Self {
+        self.with_rate_limiter(Arc::new(RateLimiter::per_second(requests_per_second)))
+    }
+
+    /// Use this limiter for every request this client makes. Pass a clone of the same `Arc` to
+    /// share a limit between clients, for example ones that use different tokens for one account.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.extensions.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Gzip the bodies of POST requests that are at least
+    /// [crate::compression::DEFAULT_MIN_COMPRESSED_BYTES] long,
+    /// or stop compressing them. See [crate::compression].
+    pub fn compress_requests(mut self, compress: bool) -> Self {
+        self.extensions.request_compression = compress.then(RequestCompression::default);
+        self
+    }
+
+    /// Send every API call through `middleware`, see [crate::middleware]
+    #[cfg(feature = "reqwest-middleware")]
+    pub fn with_middleware(mut self, middleware: ClientWithMiddleware) -> Self {
+        self.extensions.middleware = Some(middleware);
+        self
+    }
+
+    /// Gzip the bodies of POST requests as `compression` says, see [crate::compression]
+    pub fn with_request_compression(mut self, compression: RequestCompression) -> Self {
+        self.extensions.request_compression = Some(compression);
+        self
+    }
+
+    /// A copy of this client that calls the API at another URL, like a staging endpoint, with the
+    /// same token and HTTP client (and so the same connection pool). The URL gets a trailing slash
+    /// if it is missing, see [normalize_base_url], and must be an absolute http(s) URL.
+    ///
+    /// The copy has no read cache, since cached reads are not keyed by URL. It shares the rate
+    /// limiter, request compression, and middleware, if there are any.
+    pub fn at_base_url<S: Into<String>>(&self, base_url: S) -> Result<AsyncYupdatesClient> {
+        Ok(AsyncYupdatesClient {
+            base_url: checked_base_url(base_url)?,
+            http_client: self.http_client.clone(),
+            token: self.token.clone(),
+            extensions: self.extensions.for_other_base_url(),
+        })
+    }
+
+    /// See [crate::api::YupdatesV0::get_item]
+    pub async fn get_item<F, I>(&self, feed_id: F, item_id: I) -> Result<FeedItem>
+    where
+        F: AsRef<str>,
+        I: AsRef<str>,
+    {
+        self.scoped(get_item_with_args(
+            feed_id.as_ref(),
+            item_id.as_ref(),
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::delete_item]
+    pub async fn delete_item<F, I>(&self, feed_id: F, item_id: I) -> Result<DeleteItemResponse>
+    where
+        F: AsRef<str>,
+        I: AsRef<str>,
+    {
+        self.scoped(delete_item_with_args(
+            feed_id.as_ref(),
+            item_id.as_ref(),
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::update_item]
+    pub async fn update_item<F, I>(
+        &self,
+        feed_id: F,
+        item_id: I,
+        patch: &ItemPatch,
+    ) -> Result<FeedItem>
+    where
+        F: AsRef<str>,
+        I: AsRef<str>,
+    {
+        self.scoped(update_item_with_args(
+            feed_id.as_ref(),
+            item_id.as_ref(),
+            patch,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
     /// See [crate::api::YupdatesV0::new_items]
     pub async fn new_items(&self, items: &[InputItem]) -> Result<NewInputItemsResponse> {
-        new_items_with_args(items, &self.http_client, &self.base_url, &self.token).await
+        self.scoped(new_items_with_args(
+            items,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::new_items_lenient]
+    pub async fn new_items_lenient(&self, items: &[InputItem]) -> Result<LenientNewItemsResponse> {
+        self.scoped(new_items_lenient_with_args(
+            items,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::feed_id]
+    pub async fn feed_id(&self) -> Result<String> {
+        Ok(self.new_items(&[]).await?.feed_id)
+    }
+
+    /// See [crate::api::YupdatesV0::new_items_dry_run]
+    pub async fn new_items_dry_run(&self, items: &[InputItem]) -> Result<String> {
+        self.scoped(new_items_dry_run_with_args(
+            items,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::new_items_unchecked]
+    pub async fn new_items_unchecked(&self, items: &[InputItem]) -> Result<NewInputItemsResponse> {
+        self.scoped(new_items_unchecked_with_args(
+            items,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::new_items_idempotent]
+    pub async fn new_items_idempotent(
+        &self,
+        items: &[InputItem],
+        key: &str,
+    ) -> Result<NewInputItemsResponse> {
+        self.scoped(new_items_idempotent_with_args(
+            items,
+            key,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
     }
 
     /// See [crate::api::YupdatesV0::new_items_all]
     pub async fn new_items_all(&self, items: &[InputItem], sleep_ms: u64) -> Result<String> {
-        new_items_all_with_args(
+        self.scoped(new_items_all_with_args(
             items,
             sleep_ms,
             &self.http_client,
             &self.base_url,
             &self.token,
-        )
+        ))
         .await
     }
 
-    /// See [crate::api::YupdatesV0::ping]
-    pub async fn ping(&self) -> Result<PingResponse> {
-        ping_with_args(&self.http_client, &self.base_url, &self.token).await
+    /// See [crate::api::YupdatesV0::new_items_all_with_pacing]
+    pub async fn new_items_all_with_pacing(
+        &self,
+        items: &[InputItem],
+        pacing: Duration,
+    ) -> Result<String> {
+        self.scoped(new_items_all_with_pacing_with_args(
+            items,
+            pacing,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
     }
 
-    /// See [crate::api::YupdatesV0::ping_bool]
-    pub async fn ping_bool(&self) -> bool {
-        self.ping().await.is_ok()
+    /// See [crate::api::YupdatesV0::new_items_all_with_options]
+    pub async fn new_items_all_with_options(
+        &self,
+        items: &[InputItem],
+        options: &BatchOptions,
+    ) -> Result<String> {
+        self.scoped(new_items_all_with_options_with_args(
+            items,
+            options,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
     }
 
-    /// See [crate::api::YupdatesV0::read_items]
-    pub async fn read_items<S>(&self, feed_id: S) -> Result<Vec<FeedItem>>
+    /// See [crate::api::YupdatesV0::new_items_all_with_retry]
+    pub async fn new_items_all_with_retry(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+        retry: &RetryPolicy,
+    ) -> Result<String> {
+        self.scoped(new_items_all_with_retry_with_args(
+            items,
+            sleep_ms,
+            retry,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::new_items_all_with_results]
+    pub async fn new_items_all_with_results(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+    ) -> Result<(String, Option<Vec<ItemResult>>)> {
+        self.scoped(new_items_all_with_results_with_args(
+            items,
+            sleep_ms,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::new_items_from_ndjson]
+    pub async fn new_items_from_ndjson<R>(&self, reader: R, sleep_ms: u64) -> Result<BatchSummary>
     where
-        S: AsRef<str>,
+        R: BufRead,
     {
-        read_items_with_args(
-            feed_id.as_ref(),
-            None,
+        self.scoped(new_items_from_ndjson_with_args(
+            reader,
+            sleep_ms,
             &self.http_client,
             &self.base_url,
             &self.token,
-        )
+        ))
         .await
     }
 
-    /// See [crate::api::YupdatesV0::read_items_with_options]
-    pub async fn read_items_with_options<S>(
+    /// See [crate::api::YupdatesV0Csv::new_items_from_csv]
+    #[cfg(feature = "csv")]
+    pub async fn new_items_from_csv<P>(
         &self,
-        feed_id: S,
-        options: &ReadOptions,
-    ) -> Result<Vec<FeedItem>>
+        path: P,
+        mapping: CsvMapping,
+        sleep_ms: u64,
+    ) -> Result<String>
     where
-        S: AsRef<str>,
+        P: AsRef<Path>,
     {
-        read_items_with_args(
-            feed_id.as_ref(),
-            Some(options),
+        self.scoped(new_items_from_csv_with_args(
+            path,
+            mapping,
+            sleep_ms,
             &self.http_client,
             &self.base_url,
             &self.token,
-        )
+        ))
         .await
     }
-}
 
-// ─────────────────────────────────────────────────────────────────────────────────────────────────
-// SYNC CLIENT
-// ─────────────────────────────────────────────────────────────────────────────────────────────────
+    /// See [crate::api::YupdatesV0::new_items_all_detailed]
+    pub async fn new_items_all_detailed(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+    ) -> Result<Vec<NewInputItemsResponse>> {
+        self.scoped(new_items_all_detailed_with_args(
+            items,
+            sleep_ms,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
 
-// In the future, we would like this to be optional: #[cfg(feature = "sync_client")]
-/// Alternative client that sets up and hides a [tokio::runtime::Runtime](https://docs.rs/tokio/latest/tokio/runtime/index.html)
-pub mod sync {
-    use crate::api::{NewInputItemsResponse, PingResponse, ReadOptions, YupdatesV0};
-    use crate::clients::{new_async_client, AsyncYupdatesClient};
-    use crate::errors::{Error, Result};
-    use crate::models::{FeedItem, InputItem};
-    use crate::Kind;
-    use tokio::runtime::Runtime;
+    /// See [crate::api::YupdatesV0::new_items_all_with_progress]
+    pub async fn new_items_all_with_progress<F>(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+        progress: F,
+    ) -> Result<String>
+    where
+        F: FnMut(BatchProgress),
+    {
+        self.scoped(new_items_all_with_progress_with_args(
+            items,
+            sleep_ms,
+            progress,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
 
-    /// Wraps everything needed to make sync calls to the API, encapsulating a Tokio runtime.
-    ///
-    /// This allows you to make one-off CLIs more easily. You can list just `yupdates` as a
-    /// dependency and write code like `new_sync_client()?.ping()`.
-    pub struct SyncYupdatesClient {
-        pub client: AsyncYupdatesClient,
-        pub rt: Runtime,
+    /// See [crate::api::YupdatesV0::new_items_all_resume]
+    pub async fn new_items_all_resume(
+        &self,
+        items: &[InputItem],
+        progress: &BulkProgress,
+        sleep_ms: u64,
+    ) -> std::result::Result<String, BulkError> {
+        self.scoped(new_items_all_resume_with_args(
+            items,
+            progress,
+            sleep_ms,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
     }
 
-    /// Create a [SyncYupdatesClient] instance using the default configuration sources.
-    pub fn new_sync_client() -> Result<SyncYupdatesClient> {
-        let rt = match Runtime::new() {
-            Ok(rt) => rt,
-            Err(e) => {
-                return Err(Error {
-                    kind: Kind::Config(format!("Could not create Tokio runtime: {}", e)),
-                })
-            }
-        };
-        Ok(SyncYupdatesClient {
-            client: new_async_client()?,
-            rt,
-        })
+    /// See [crate::api::YupdatesV0::new_items_all_cancellable]
+    pub async fn new_items_all_cancellable(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+        token: CancellationToken,
+    ) -> Result<String> {
+        self.scoped(new_items_all_cancellable_with_args(
+            items,
+            sleep_ms,
+            token,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
     }
 
-    impl YupdatesV0 for SyncYupdatesClient {
-        fn new_items(&self, items: &[InputItem]) -> Result<NewInputItemsResponse> {
-            self.rt.block_on(self.client.new_items(items))
+    /// See [crate::api::YupdatesV0::new_items_all_concurrent]
+    pub async fn new_items_all_concurrent(
+        &self,
+        items: &[InputItem],
+        concurrency: usize,
+        pacing: Duration,
+    ) -> Result<BatchSummary> {
+        if !(1..=MAX_NEW_ITEMS_CONCURRENCY).contains(&concurrency) {
+            return Err(Error {
+                kind: Kind::IllegalParameter(format!(
+                    "`concurrency` must be 1 to {}, received {}",
+                    MAX_NEW_ITEMS_CONCURRENCY, concurrency
+                )),
+            });
         }
-
-        fn new_items_all(&self, items: &[InputItem], sleep_ms: u64) -> Result<String> {
-            self.rt.block_on(self.client.new_items_all(items, sleep_ms))
+        if pacing < MIN_PACING {
+            return Err(Error {
+                kind: Kind::IllegalParameter(format!(
+                    "`pacing` ({:?}) must be {:?} or more",
+                    pacing, MIN_PACING
+                )),
+            });
         }
+        // Checked up front so that the index is into all of the items and nothing is half sent
+        check_before_send(items)?;
+        // `buffer_unordered` only pulls the next batch when a slot is free, so the pause before
+        // each batch spaces out the starts rather than delaying batches that are already queued
+        let responses = futures::stream::iter(chunk_items(items).enumerate())
+            .then(|(index, chunk)| async move {
+                if index > 0 {
+                    sleep(pacing).await;
+                }
+                chunk
+            })
+            .map(|chunk| async move {
+                let response = self.new_items_unchecked(chunk).await?;
+                Ok::<_, Error>((response, chunk.len()))
+            })
+            .buffer_unordered(concurrency);
+        let mut responses = std::pin::pin!(responses);
 
-        fn ping(&self) -> Result<PingResponse> {
-            self.rt.block_on(self.client.ping())
+        let mut feed_id = None;
+        let mut batches_sent = 0;
+        let mut items_sent = 0;
+        while let Some(result) = responses.next().await {
+            let (response, count) = result?;
+            batches_sent += 1;
+            items_sent += count;
+            match &feed_id {
+                None => feed_id = Some(response.feed_id),
+                Some(first) if *first != response.feed_id => {
+                    return Err(Error {
+                        kind: Kind::IllegalResult(format!(
+                            "a batch was added to feed {}, but another was added to feed {}",
+                            response.feed_id, first
+                        )),
+                    });
+                }
+                Some(_) => {}
+            }
         }
-
-        fn ping_bool(&self) -> bool {
-            self.rt.block_on(self.client.ping_bool())
+        match feed_id {
+            None => Err(Error {
+                kind: Kind::IllegalResult(
+                    "new items API success(es) without a feed ID".to_string(),
+                ),
+            }),
+            Some(feed_id) => Ok(BatchSummary {
+                feed_id,
+                batches_sent,
+                items_sent,
+            }),
         }
+    }
 
-        fn read_items<S>(&self, feed_id: S) -> Result<Vec<FeedItem>>
-        where
-            S: AsRef<str>,
-        {
-            self.rt.block_on(self.client.read_items(feed_id))
-        }
+    /// See [crate::api::YupdatesV0::new_items_all_dedup]
+    pub async fn new_items_all_dedup(
+        &self,
+        items: &[InputItem],
+        sleep_ms: u64,
+    ) -> Result<(String, usize)> {
+        self.scoped(new_items_all_dedup_with_args(
+            items,
+            sleep_ms,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
 
-        fn read_items_with_options<S>(
-            &self,
-            feed_id: S,
-            options: &ReadOptions,
-        ) -> Result<Vec<FeedItem>>
-        where
-            S: AsRef<str>,
+    /// See [crate::api::YupdatesV0::upsert_items]
+    pub async fn upsert_items<S>(
+        &self,
+        feed_id: S,
+        items: &[InputItem],
+        mode: UpsertMode,
+        lookback: usize,
+    ) -> Result<UpsertSummary>
+    where
+        S: AsRef<str>,
+    {
+        self.scoped(upsert_items_with_args(
+            feed_id.as_ref(),
+            items,
+            mode,
+            lookback,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::new_items_all_skip_existing]
+    pub async fn new_items_all_skip_existing(
+        &self,
+        items: &[InputItem],
+        lookback: usize,
+        key: DedupKey,
+    ) -> Result<DedupSummary> {
+        self.scoped(new_items_all_skip_existing_with_args(
+            items,
+            lookback,
+            key,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::new_feed]
+    pub async fn new_feed(&self, options: &NewFeedOptions) -> Result<NewFeedResponse> {
+        self.scoped(new_feed_with_args(
+            options,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::delete_feed]
+    pub async fn delete_feed<S>(
+        &self,
+        feed_id: S,
+        confirmation: DeleteConfirmation,
+    ) -> Result<DeleteFeedResponse>
+    where
+        S: AsRef<str>,
+    {
+        self.scoped(delete_feed_with_args(
+            feed_id.as_ref(),
+            confirmation,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::delete_feed_dry_run]
+    pub async fn delete_feed_dry_run<S>(&self, feed_id: S) -> Result<FeedInfo>
+    where
+        S: AsRef<str>,
+    {
+        self.scoped(delete_feed_dry_run_with_args(
+            feed_id.as_ref(),
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::list_feeds]
+    pub async fn list_feeds(&self) -> Result<Vec<FeedInfo>> {
+        self.scoped(list_feeds_with_args(
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::ping]
+    pub async fn ping(&self) -> Result<PingResponse> {
+        self.scoped(ping_with_args(
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::ping_bool]
+    pub async fn ping_bool(&self) -> bool {
+        self.ping().await.is_ok()
+    }
+
+    /// See [crate::api::YupdatesV0::ping_timed]
+    pub async fn ping_timed(&self) -> Result<(PingResponse, Duration)> {
+        self.scoped(ping_timed_with_args(
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::read_items]
+    pub async fn read_items<S>(&self, feed_id: S) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>,
+    {
+        self.read_items_cached(feed_id.as_ref(), None).await
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_with_options]
+    pub async fn read_items_with_options<S>(
+        &self,
+        feed_id: S,
+        options: &ReadOptions,
+    ) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>,
+    {
+        self.read_items_cached(feed_id.as_ref(), Some(options))
+            .await
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_with_content]
+    pub async fn read_items_with_content<S>(&self, feed_id: S) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>,
+    {
+        self.read_items_with_content_n(feed_id, 10).await
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_with_content_n]
+    pub async fn read_items_with_content_n<S>(
+        &self,
+        feed_id: S,
+        max_items: usize,
+    ) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>,
+    {
+        let options = ReadOptions {
+            max_items,
+            include_item_content: true,
+            ..Default::default()
+        };
+        self.read_items_with_options(feed_id, &options).await
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_many]
+    pub async fn read_items_many<S>(
+        &self,
+        feed_ids: &[S],
+        options: &ReadOptions,
+        concurrency: usize,
+    ) -> Result<Vec<(String, Result<Vec<FeedItem>>)>>
+    where
+        S: AsRef<str>,
+    {
+        if concurrency < 1 {
+            return Err(Error {
+                kind: Kind::IllegalParameter("`concurrency` must be 1 or more".to_string()),
+            });
+        }
+        // `buffered` keeps the output in input order while running up to N reads at once
+        let results = futures::stream::iter(feed_ids)
+            .map(|feed_id| async move {
+                let feed_id = feed_id.as_ref().to_string();
+                let result = self.read_items_with_options(&feed_id, options).await;
+                (feed_id, result)
+            })
+            .buffered(concurrency)
+            .collect::<Vec<_>>()
+            .await;
+        Ok(results)
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_response]
+    pub async fn read_items_response<S>(
+        &self,
+        feed_id: S,
+        options: &ReadOptions,
+    ) -> Result<ReadFeedItemsResponse>
+    where
+        S: AsRef<str>,
+    {
+        self.scoped(read_items_response_with_args(
+            feed_id.as_ref(),
+            Some(options),
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_as]
+    pub async fn read_items_as<T, S>(&self, feed_id: S, options: &ReadOptions) -> Result<Vec<T>>
+    where
+        T: DeserializeOwned,
+        S: AsRef<str>,
+    {
+        self.scoped(read_items_as_with_args(
+            feed_id.as_ref(),
+            Some(options),
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_filtered]
+    pub async fn read_items_filtered<S, F>(
+        &self,
+        feed_id: S,
+        options: &ReadOptions,
+        predicate: F,
+        stop_after: Option<usize>,
+    ) -> Result<FilteredItems>
+    where
+        S: AsRef<str>,
+        F: Fn(&FeedItem) -> bool,
+    {
+        self.scoped(read_items_filtered_with_args(
+            feed_id.as_ref(),
+            Some(options),
+            predicate,
+            stop_after,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_in_range]
+    pub async fn read_items_in_range<S, T>(
+        &self,
+        feed_id: S,
+        after: T,
+        before: T,
+        options: &ReadOptions,
+        range: &RangeOptions,
+    ) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.scoped(read_items_in_range_with_args(
+            feed_id.as_ref(),
+            after.as_ref(),
+            before.as_ref(),
+            Some(options),
+            Some(range),
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::search_items]
+    pub async fn search_items<S>(
+        &self,
+        feed_id: S,
+        query: &str,
+        options: &ReadOptions,
+    ) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>,
+    {
+        self.scoped(search_items_with_args(
+            feed_id.as_ref(),
+            query,
+            Some(options),
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::count_items]
+    pub async fn count_items<S>(&self, feed_id: S) -> Result<u64>
+    where
+        S: AsRef<str>,
+    {
+        self.scoped(count_items_with_args(
+            feed_id.as_ref(),
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::dump_feed_ndjson]
+    pub async fn dump_feed_ndjson<S, W>(
+        &self,
+        feed_id: S,
+        writer: W,
+        limit: Option<usize>,
+    ) -> Result<usize>
+    where
+        S: AsRef<str>,
+        W: Write,
+    {
+        self.scoped(dump_feed_ndjson_with_args(
+            feed_id.as_ref(),
+            writer,
+            limit,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::wait_for_item]
+    pub async fn wait_for_item<S, F>(
+        &self,
+        feed_id: S,
+        predicate: F,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<FeedItem>
+    where
+        S: AsRef<str>,
+        F: Fn(&FeedItem) -> bool,
+    {
+        self.scoped(wait_for_item_with_args(
+            feed_id.as_ref(),
+            predicate,
+            timeout,
+            poll_interval,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// Follow a feed: a stream that yields new items as they appear, until it is dropped.
+    ///
+    /// Each tick reads the items after the newest item time seen so far and yields the ones
+    /// that have not been yielded yet, oldest first. If a page is full, the tick pages back to
+    /// the newest item time seen so far, so a burst of new items is not cut off. On the first
+    /// tick (unless `options` has an `item_time_after` to start from), it yields the latest
+    /// `max_items` items. The `order`, `item_time_after`, `item_time_before`, and `cache_bypass`
+    /// options are managed by the poller.
+    ///
+    /// Transient errors (network problems, throttling, server errors) are yielded and then
+    /// retried with exponential backoff, up to [MAX_POLL_BACKOFF]. Any other error is yielded
+    /// and ends the stream. Use [NewItemsPoller::cursor] to persist your position.
+    pub fn poll_new_items<S>(
+        &self,
+        feed_id: S,
+        interval: Duration,
+        options: &ReadOptions,
+    ) -> NewItemsPoller<'_>
+    where
+        S: AsRef<str>,
+    {
+        let cursor = Arc::new(Mutex::new(options.item_time_after.clone()));
+        let state = PollState {
+            client: self,
+            feed_id: feed_id.as_ref().to_string(),
+            options: options.clone(),
+            interval,
+            cursor: cursor.clone(),
+            seen_at_cursor: HashSet::new(),
+            pending: VecDeque::new(),
+            next_wait: None,
+            failures: 0,
+            done: false,
+        };
+        NewItemsPoller {
+            cursor,
+            inner: Box::pin(futures::stream::unfold(state, PollState::next_item)),
+        }
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_direct]
+    pub async fn read_items_direct<S>(
+        &self,
+        feed_id: S,
+        options: &ReadOptions,
+    ) -> Result<Vec<FeedItem>>
+    where
+        S: AsRef<str>,
+    {
+        self.scoped(read_items_direct_with_args(
+            feed_id.as_ref(),
+            Some(options),
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_raw]
+    pub async fn read_items_raw<S>(&self, feed_id: S, options: &ReadOptions) -> Result<Value>
+    where
+        S: AsRef<str>,
+    {
+        self.scoped(read_items_raw_with_args(
+            feed_id.as_ref(),
+            Some(options),
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    // Runs a call with the requests it makes limited by the rate limiter, with their bodies
+    // compressed, and sent through the middleware, if the client has those
+    async fn scoped<F: Future>(&self, call: F) -> F::Output {
+        let call = compressed(self.extensions.request_compression, call);
+        #[cfg(feature = "reqwest-middleware")]
+        let call = through(self.extensions.middleware.as_ref(), call);
+        limited(self.extensions.rate_limiter.as_ref(), call).await
+    }
+
+    // Consults the read cache, if there is one, before calling the API
+    async fn read_items_cached(
+        &self,
+        feed_id: &str,
+        options: Option<&ReadOptions>,
+    ) -> Result<Vec<FeedItem>> {
+        #[cfg(feature = "cache")]
+        if let Some(read_cache) = &self.extensions.read_cache {
+            let key = ReadCache::key(feed_id, options)?;
+            if !options.is_some_and(|o| o.cache_bypass) {
+                if let Some(items) = read_cache.get(&key) {
+                    return Ok(items);
+                }
+            }
+            let items = self
+                .scoped(read_items_with_args(
+                    feed_id,
+                    options,
+                    &self.http_client,
+                    &self.base_url,
+                    &self.token,
+                ))
+                .await?;
+            read_cache.insert(key, items.clone());
+            return Ok(items);
+        }
+        self.scoped(read_items_with_args(
+            feed_id,
+            options,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// See [crate::api::YupdatesV0::read_items_conditional]
+    pub async fn read_items_conditional<S>(
+        &self,
+        feed_id: S,
+        options: &ReadOptions,
+        etag: Option<&str>,
+    ) -> Result<ReadOutcome>
+    where
+        S: AsRef<str>,
+    {
+        self.scoped(read_items_conditional_with_args(
+            feed_id.as_ref(),
+            Some(options),
+            etag,
+            &self.http_client,
+            &self.base_url,
+            &self.token,
+        ))
+        .await
+    }
+
+    /// Create a [CachedReader] that remembers the ETag of each read so that repeated reads of
+    /// an unchanged feed come back as [ReadOutcome::NotModified].
+    pub fn cached_reader(&self) -> CachedReader<'_> {
+        CachedReader {
+            client: self,
+            etags: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a [FeedWriter] that batches pushed items into [AsyncYupdatesClient::new_items]
+    /// calls, sending a batch when it is full or once `interval` has passed since its first item.
+    pub fn feed_writer(&self, interval: Duration) -> FeedWriter {
+        FeedWriter {
+            client: self.clone(),
+            interval,
+            buffer: Vec::new(),
+            oldest: None,
+            feed_id: None,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// CONDITIONAL READS
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// Reads that remember the last ETag per feed and query, see [AsyncYupdatesClient::cached_reader]
+///
+/// Only the ETags are kept, not the items: on [ReadOutcome::NotModified], keep using the items
+/// you got from the last [ReadOutcome::Modified].
+///
+/// The query includes the cursor, so paging with `item_time_before` gets an ETag per page, and
+/// moving the cursor never sends the ETag of another page.
+pub struct CachedReader<'a> {
+    client: &'a AsyncYupdatesClient,
+    // Keyed by the feed ID and the canonical query string
+    etags: Mutex<HashMap<(String, String), String>>,
+}
+
+impl CachedReader<'_> {
+    /// Read items, sending the ETag from the last read of this feed with the same options.
+    pub async fn read_items<S>(&self, feed_id: S, options: &ReadOptions) -> Result<ReadOutcome>
+    where
+        S: AsRef<str>,
+    {
+        let key = Self::key(feed_id.as_ref(), options)?;
+        let etag = self.etag_for(&key);
+        let outcome = self
+            .client
+            .read_items_conditional(feed_id.as_ref(), options, etag.as_deref())
+            .await?;
+        if let ReadOutcome::Modified { etag, .. } = &outcome {
+            let mut etags = self.etags.lock().unwrap_or_else(|p| p.into_inner());
+            match etag {
+                Some(etag) => etags.insert(key, etag.clone()),
+                None => etags.remove(&key),
+            };
+        }
+        Ok(outcome)
+    }
+
+    /// The ETag that the next read of this feed with these options will send, if any.
+    pub fn etag<S>(&self, feed_id: S, options: &ReadOptions) -> Result<Option<String>>
+    where
+        S: AsRef<str>,
+    {
+        Ok(self.etag_for(&Self::key(feed_id.as_ref(), options)?))
+    }
+
+    fn etag_for(&self, key: &(String, String)) -> Option<String> {
+        let etags = self.etags.lock().unwrap_or_else(|p| p.into_inner());
+        etags.get(key).cloned()
+    }
+
+    fn key(feed_id: &str, options: &ReadOptions) -> Result<(String, String)> {
+        Ok((
+            feed_id.trim().to_string(),
+            options.canonical_query_string()?,
+        ))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// POLLING
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// The longest that [NewItemsPoller] waits between attempts after consecutive transient errors
+pub const MAX_POLL_BACKOFF: Duration = Duration::from_secs(300);
+
+/// A stream of new items from a feed, see [AsyncYupdatesClient::poll_new_items]
+pub struct NewItemsPoller<'a> {
+    cursor: Arc<Mutex<Option<String>>>,
+    inner: Pin<Box<dyn Stream<Item = Result<FeedItem>> + Send + 'a>>,
+}
+
+impl NewItemsPoller<'_> {
+    /// The item time of the newest item that has been yielded (or the starting point, if nothing
+    /// has been yielded yet). Persist this and pass it as `item_time_after` to resume later.
+    pub fn cursor(&self) -> Option<String> {
+        lock_cursor(&self.cursor).clone()
+    }
+}
+
+impl Stream for NewItemsPoller<'_> {
+    type Item = Result<FeedItem>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+struct PollState<'a> {
+    client: &'a AsyncYupdatesClient,
+    feed_id: String,
+    options: ReadOptions,
+    interval: Duration,
+    cursor: Arc<Mutex<Option<String>>>,
+    // Item IDs already yielded whose item time equals the cursor, in case of item time ties
+    seen_at_cursor: HashSet<ItemId>,
+    pending: VecDeque<FeedItem>,
+    next_wait: Option<Duration>,
+    failures: u32,
+    done: bool,
+}
+
+impl PollState<'_> {
+    async fn next_item(mut self) -> Option<(Result<FeedItem>, Self)> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some((Ok(item), self));
+            }
+            if self.done {
+                return None;
+            }
+            if let Some(wait) = self.next_wait.take() {
+                sleep(wait).await;
+            }
+
+            let cursor = lock_cursor(&self.cursor).clone();
+            let options = ReadOptions {
+                item_time_after: cursor.clone(),
+                item_time_before: None,
+                order: Order::Oldest,
+                // A cached page would only delay new items
+                cache_bypass: true,
+                ..self.options.clone()
+            };
+            match self.read_new(cursor.as_deref(), &options).await {
+                Ok((items, full_page)) => {
+                    self.failures = 0;
+                    self.accept(cursor, items);
+                    // If the page was full there are probably more waiting, don't sleep
+                    self.next_wait = if full_page { None } else { Some(self.interval) };
+                }
+                Err(e) => {
+                    if e.is_retryable() {
+                        self.failures += 1;
+                        let factor = 2u32.saturating_pow(self.failures.min(16));
+                        let backoff = self.interval.saturating_mul(factor);
+                        self.next_wait = Some(backoff.min(MAX_POLL_BACKOFF));
+                    } else {
+                        self.done = true;
+                    }
+                    return Some((Err(e), self));
+                }
+            }
+        }
+    }
+
+    // Reads the items after the cursor, oldest first, and whether the page was full. A full page
+    // can hold only the newest items after the cursor, so then the older ones are read too, back
+    // to the cursor, and nothing that arrived in a burst between ticks is skipped.
+    async fn read_new(
+        &self,
+        cursor: Option<&str>,
+        options: &ReadOptions,
+    ) -> Result<(Vec<FeedItem>, bool)> {
+        let items = self
+            .client
+            .read_items_with_options(&self.feed_id, options)
+            .await?;
+        let full_page = items.len() >= options.max_items;
+        let (cursor, newest) = match (cursor, items.last()) {
+            (Some(cursor), Some(newest)) if full_page => (cursor, newest.item_time.clone()),
+            _ => return Ok((items, full_page)),
+        };
+        if normalize_item_time(cursor)? >= normalize_item_time(&newest)? {
+            return Ok((items, full_page));
+        }
+        let range_options = ReadOptions {
+            item_time_after: None,
+            ..options.clone()
+        };
+        // Inclusive, for the items that tie with the cursor, see accept
+        let range = RangeOptions {
+            inclusive_after: true,
+            limit: None,
+        };
+        let mut all = self
+            .client
+            .read_items_in_range(
+                &self.feed_id,
+                cursor,
+                newest.as_str(),
+                &range_options,
+                &range,
+            )
+            .await?;
+        let ids: HashSet<ItemId> = all.iter().map(|item| item.item_id.clone()).collect();
+        all.extend(
+            items
+                .into_iter()
+                .filter(|item| !ids.contains(&item.item_id)),
+        );
+        Ok((all, full_page))
+    }
+
+    // Queue the unseen items (they are already oldest-first) and advance the cursor
+    fn accept(&mut self, cursor: Option<String>, items: Vec<FeedItem>) {
+        let cursor_time = cursor.as_deref().and_then(|c| normalize_item_time(c).ok());
+        for item in items {
+            let item_time = match normalize_item_time(&item.item_time) {
+                Ok(it) => it,
+                Err(_) => continue,
+            };
+            if let Some(ct) = &cursor_time {
+                if item_time < *ct {
+                    continue;
+                }
+                if item_time == *ct && self.seen_at_cursor.contains(&item.item_id) {
+                    continue;
+                }
+            }
+            let mut current = lock_cursor(&self.cursor);
+            let newer = match current.as_deref() {
+                None => true,
+                Some(c) => normalize_item_time(c).map_or(true, |c| item_time > c),
+            };
+            if newer {
+                *current = Some(item_time);
+                self.seen_at_cursor.clear();
+            }
+            self.seen_at_cursor.insert(item.item_id.clone());
+            self.pending.push_back(item);
+        }
+    }
+}
+
+fn lock_cursor(cursor: &Mutex<Option<String>>) -> MutexGuard<'_, Option<String>> {
+    cursor
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// BUFFERED WRITES
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+/// Batches items pushed one at a time into calls to the API, see
+/// [AsyncYupdatesClient::feed_writer]
+///
+/// A batch is sent when it reaches [MAX_ITEMS_PER_CALL] items, or on the first push (or
+/// [FeedWriter::flush_if_due]) after the interval has passed since the oldest buffered item.
+/// There is no background timer: if pushes stop, call [FeedWriter::flush_if_due] periodically
+/// or [FeedWriter::flush].
+///
+/// Always finish with `close().await`, which is the only way to be sure the last batch was
+/// sent. Dropping a writer that still has items makes a best-effort attempt to send them on
+/// the current Tokio runtime, if there is one, and any error is lost.
+pub struct FeedWriter {
+    client: AsyncYupdatesClient,
+    interval: Duration,
+    buffer: Vec<InputItem>,
+    oldest: Option<Instant>,
+    feed_id: Option<String>,
+}
+
+impl FeedWriter {
+    /// Buffer an item, and send the batch if it is full or due.
+    ///
+    /// If sending fails, the items stay buffered and the next flush tries again. A buffer that
+    /// grew past [MAX_ITEMS_PER_CALL] meanwhile is sent in several calls.
+    pub async fn push(&mut self, item: InputItem) -> Result<()> {
+        self.buffer.push(item);
+        self.oldest.get_or_insert_with(Instant::now);
+        if self.buffer.len() >= MAX_ITEMS_PER_CALL {
+            self.flush().await
+        } else {
+            self.flush_if_due().await
+        }
+    }
+
+    /// Send the buffered items if the interval has passed since the oldest of them.
+    pub async fn flush_if_due(&mut self) -> Result<()> {
+        match self.oldest {
+            Some(oldest) if oldest.elapsed() >= self.interval => self.flush().await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Send the buffered items now, if there are any, up to [MAX_ITEMS_PER_CALL] at a time. If a
+    /// call fails, the items of the calls that succeeded are no longer buffered.
+    pub async fn flush(&mut self) -> Result<()> {
+        send_buffered(&self.client, &mut self.buffer, &mut self.feed_id).await?;
+        self.oldest = None;
+        Ok(())
+    }
+
+    /// Send any remaining items and return the feed ID that was written to, if anything was.
+    pub async fn close(mut self) -> Result<Option<String>> {
+        self.flush().await?;
+        Ok(self.feed_id.take())
+    }
+
+    /// The number of items waiting to be sent
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// The feed ID reported by the first successful batch
+    pub fn feed_id(&self) -> Option<&str> {
+        self.feed_id.as_deref()
+    }
+}
+
+impl Drop for FeedWriter {
+    fn drop(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let client = self.client.clone();
+            let mut items = std::mem::take(&mut self.buffer);
+            handle.spawn(async move {
+                let _ = send_buffered(&client, &mut items, &mut None).await;
+            });
+        }
+    }
+}
+
+// Send the items a call at a time, [MIN_PACING] apart, removing each batch from the buffer once
+// it was accepted
+async fn send_buffered(
+    client: &AsyncYupdatesClient,
+    buffer: &mut Vec<InputItem>,
+    feed_id: &mut Option<String>,
+) -> Result<()> {
+    while !buffer.is_empty() {
+        let batch_len = buffer.len().min(MAX_ITEMS_PER_CALL);
+        let response = client.new_items(&buffer[..batch_len]).await?;
+        feed_id.get_or_insert(response.feed_id);
+        buffer.drain(..batch_len);
+        if !buffer.is_empty() {
+            sleep(MIN_PACING).await;
+        }
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+// SYNC CLIENT
+// ─────────────────────────────────────────────────────────────────────────────────────────────────
+
+// In the future, we would like this to be optional: #[cfg(feature = "sync_client")]
+/// Alternative client that sets up and hides a [tokio::runtime::Runtime](https://docs.rs/tokio/latest/tokio/runtime/index.html)
+pub mod sync {
+    #[cfg(feature = "csv")]
+    use crate::api::YupdatesV0Csv;
+    use crate::api::{
+        BatchOptions, BatchProgress, BatchSummary, BulkProgress, DedupKey, DedupSummary,
+        DeleteConfirmation, DeleteFeedResponse, DeleteItemResponse, FilteredItems, ItemResult,
+        LenientNewItemsResponse, NewFeedOptions, NewFeedResponse, NewInputItemsResponse,
+        PingResponse, RangeOptions, ReadFeedItemsResponse, ReadOptions, ReadOutcome, RetryPolicy,
+        UpsertMode, UpsertSummary, YupdatesV0,
+    };
+    use crate::clients::{
+        checked_base_url, new_async_client, AsyncYupdatesClient, ClientExtensions,
+    };
+    use crate::errors::{BulkError, Error, Result};
+    #[cfg(feature = "csv")]
+    use crate::items::CsvMapping;
+    use crate::models::{FeedInfo, FeedItem, InputItem, ItemPatch};
+    use crate::{api_token, env_or_default_url, validate_token, Kind};
+    use serde::de::DeserializeOwned;
+    use serde_json::Value;
+    use std::io::{BufRead, Write};
+    #[cfg(feature = "csv")]
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::runtime::Runtime;
+    use tokio_util::sync::CancellationToken;
+
+    /// Wraps everything needed to make sync calls to the API, encapsulating a Tokio runtime.
+    ///
+    /// This allows you to make one-off CLIs more easily. You can list just `yupdates` as a
+    /// dependency and write code like `new_sync_client()?.ping()`.
+    pub struct SyncYupdatesClient {
+        pub client: AsyncYupdatesClient,
+        pub rt: Runtime,
+    }
+
+    /// Create a [SyncYupdatesClient] instance using the default configuration sources.
+    ///
+    /// Each client gets a runtime of its own, with its own threads. A program that creates
+    /// several clients can share one lazily created runtime instead, see [shared_client].
+    pub fn new_sync_client() -> Result<SyncYupdatesClient> {
+        let rt = match Runtime::new() {
+            Ok(rt) => rt,
+            Err(e) => {
+                return Err(Error {
+                    kind: Kind::Config(format!("Could not create Tokio runtime: {}", e)),
+                })
+            }
+        };
+        Ok(SyncYupdatesClient {
+            client: new_async_client()?,
+            rt,
+        })
+    }
+
+    impl private::SyncBackend for SyncYupdatesClient {
+        fn runtime(&self) -> &Runtime {
+            &self.rt
+        }
+
+        fn async_client(&self) -> &AsyncYupdatesClient {
+            &self.client
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────────────────
+    // SHARED SYNC CLIENT
+    // ─────────────────────────────────────────────────────────────────────────────────────────
+
+    // The runtime and HTTP client behind every [SharedSyncClient], created on first use
+    static SHARED_BACKEND: Mutex<Option<Arc<SharedBackend>>> = Mutex::new(None);
+
+    struct SharedBackend {
+        http_client: reqwest::Client,
+        rt: Runtime,
+    }
+
+    /// A cheap handle over a process-wide Tokio runtime and HTTP client.
+    ///
+    /// Constructing a [SyncYupdatesClient] creates a multi-threaded runtime and a fresh HTTP
+    /// client (with its own TLS setup and connection pool) every time. That is fine for a CLI,
+    /// but adds noticeable latency when a client is created per request. A [SharedSyncClient]
+    /// reuses one runtime and one HTTP client for the whole process, so creating one is just a
+    /// lock and a configuration lookup. Handles can be used from many threads at once.
+    ///
+    /// The tradeoffs: the runtime and its connection pool live until [shutdown_shared] is called
+    /// and every handle is dropped, and all handles share the same HTTP client configuration.
+    /// Use a [SyncYupdatesClient] if you need an isolated runtime or a custom HTTP client.
+    pub struct SharedSyncClient {
+        pub client: AsyncYupdatesClient,
+        backend: Arc<SharedBackend>,
+    }
+
+    /// Create a [SharedSyncClient] using the default configuration sources. The shared runtime
+    /// and HTTP client are created on the first call.
+    pub fn shared_client() -> Result<SharedSyncClient> {
+        shared_client_with_args(env_or_default_url()?, api_token()?)
+    }
+
+    /// Create a [SharedSyncClient] with the given base URL and token. The shared runtime and
+    /// HTTP client are created on the first call.
+    pub fn shared_client_with_args(base_url: String, token: String) -> Result<SharedSyncClient> {
+        let base_url = checked_base_url(base_url)?;
+        let backend = shared_backend()?;
+        Ok(SharedSyncClient {
+            client: AsyncYupdatesClient {
+                base_url,
+                http_client: backend.http_client.clone(),
+                token: validate_token(token)?,
+                extensions: ClientExtensions::default(),
+            },
+            backend,
+        })
+    }
+
+    /// Release the shared runtime and HTTP client.
+    ///
+    /// Handles that already exist keep working, and the runtime is dropped when the last of
+    /// them is dropped. The next call to [shared_client] creates a new backend. Call this from
+    /// synchronous code: dropping a runtime from within an async context panics.
+    pub fn shutdown_shared() {
+        let backend = match SHARED_BACKEND.lock() {
+            Ok(mut guard) => guard.take(),
+            Err(poisoned) => poisoned.into_inner().take(),
+        };
+        drop(backend);
+    }
+
+    fn shared_backend() -> Result<Arc<SharedBackend>> {
+        let mut guard = SHARED_BACKEND.lock().map_err(|_| Error {
+            kind: Kind::Config("The shared client lock is poisoned".to_string()),
+        })?;
+        if let Some(backend) = guard.as_ref() {
+            return Ok(backend.clone());
+        }
+        let rt = Runtime::new().map_err(|e| Error {
+            kind: Kind::Config(format!("Could not create Tokio runtime: {}", e)),
+        })?;
+        let backend = Arc::new(SharedBackend {
+            http_client: reqwest::Client::new(),
+            rt,
+        });
+        *guard = Some(backend.clone());
+        Ok(backend)
+    }
+
+    impl private::SyncBackend for SharedSyncClient {
+        fn runtime(&self) -> &Runtime {
+            &self.backend.rt
+        }
+
+        fn async_client(&self) -> &AsyncYupdatesClient {
+            &self.client
+        }
+    }
+
+    // Both sync clients get their [YupdatesV0] implementation from this: they only differ in
+    // where the runtime comes from. The trait is sealed so this is not part of the public API.
+    mod private {
+        use crate::clients::AsyncYupdatesClient;
+        use tokio::runtime::Runtime;
+
+        pub trait SyncBackend {
+            fn runtime(&self) -> &Runtime;
+            fn async_client(&self) -> &AsyncYupdatesClient;
+        }
+    }
+
+    impl<B> YupdatesV0 for B
+    where
+        B: private::SyncBackend,
+    {
+        fn get_item<F, I>(&self, feed_id: F, item_id: I) -> Result<FeedItem>
+        where
+            F: AsRef<str>,
+            I: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().get_item(feed_id, item_id))
+        }
+
+        fn delete_item<F, I>(&self, feed_id: F, item_id: I) -> Result<DeleteItemResponse>
+        where
+            F: AsRef<str>,
+            I: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().delete_item(feed_id, item_id))
+        }
+
+        fn update_item<F, I>(&self, feed_id: F, item_id: I, patch: &ItemPatch) -> Result<FeedItem>
+        where
+            F: AsRef<str>,
+            I: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().update_item(feed_id, item_id, patch))
+        }
+
+        fn new_items(&self, items: &[InputItem]) -> Result<NewInputItemsResponse> {
+            self.runtime()
+                .block_on(self.async_client().new_items(items))
+        }
+
+        fn new_items_lenient(&self, items: &[InputItem]) -> Result<LenientNewItemsResponse> {
+            self.runtime()
+                .block_on(self.async_client().new_items_lenient(items))
+        }
+
+        fn feed_id(&self) -> Result<String> {
+            self.runtime().block_on(self.async_client().feed_id())
+        }
+
+        fn new_items_dry_run(&self, items: &[InputItem]) -> Result<String> {
+            self.runtime()
+                .block_on(self.async_client().new_items_dry_run(items))
+        }
+
+        fn new_items_unchecked(&self, items: &[InputItem]) -> Result<NewInputItemsResponse> {
+            self.runtime()
+                .block_on(self.async_client().new_items_unchecked(items))
+        }
+
+        fn new_items_idempotent(
+            &self,
+            items: &[InputItem],
+            key: &str,
+        ) -> Result<NewInputItemsResponse> {
+            self.runtime()
+                .block_on(self.async_client().new_items_idempotent(items, key))
+        }
+
+        fn new_items_all(&self, items: &[InputItem], sleep_ms: u64) -> Result<String> {
+            self.runtime()
+                .block_on(self.async_client().new_items_all(items, sleep_ms))
+        }
+
+        fn new_items_all_with_pacing(
+            &self,
+            items: &[InputItem],
+            pacing: Duration,
+        ) -> Result<String> {
+            self.runtime()
+                .block_on(self.async_client().new_items_all_with_pacing(items, pacing))
+        }
+
+        fn new_items_all_with_options(
+            &self,
+            items: &[InputItem],
+            options: &BatchOptions,
+        ) -> Result<String> {
+            self.runtime().block_on(
+                self.async_client()
+                    .new_items_all_with_options(items, options),
+            )
+        }
+
+        fn new_items_all_with_retry(
+            &self,
+            items: &[InputItem],
+            sleep_ms: u64,
+            retry: &RetryPolicy,
+        ) -> Result<String> {
+            self.runtime().block_on(
+                self.async_client()
+                    .new_items_all_with_retry(items, sleep_ms, retry),
+            )
+        }
+
+        fn new_items_all_with_results(
+            &self,
+            items: &[InputItem],
+            sleep_ms: u64,
+        ) -> Result<(String, Option<Vec<ItemResult>>)> {
+            self.runtime().block_on(
+                self.async_client()
+                    .new_items_all_with_results(items, sleep_ms),
+            )
+        }
+
+        fn new_items_from_ndjson<R>(&self, reader: R, sleep_ms: u64) -> Result<BatchSummary>
+        where
+            R: BufRead,
+        {
+            self.runtime()
+                .block_on(self.async_client().new_items_from_ndjson(reader, sleep_ms))
+        }
+
+        fn new_items_all_detailed(
+            &self,
+            items: &[InputItem],
+            sleep_ms: u64,
+        ) -> Result<Vec<NewInputItemsResponse>> {
+            self.runtime()
+                .block_on(self.async_client().new_items_all_detailed(items, sleep_ms))
+        }
+
+        fn new_items_all_with_progress<F>(
+            &self,
+            items: &[InputItem],
+            sleep_ms: u64,
+            progress: F,
+        ) -> Result<String>
+        where
+            F: FnMut(BatchProgress),
+        {
+            self.runtime().block_on(
+                self.async_client()
+                    .new_items_all_with_progress(items, sleep_ms, progress),
+            )
+        }
+
+        fn new_items_all_resume(
+            &self,
+            items: &[InputItem],
+            progress: &BulkProgress,
+            sleep_ms: u64,
+        ) -> std::result::Result<String, BulkError> {
+            self.runtime().block_on(
+                self.async_client()
+                    .new_items_all_resume(items, progress, sleep_ms),
+            )
+        }
+
+        fn new_items_all_cancellable(
+            &self,
+            items: &[InputItem],
+            sleep_ms: u64,
+            token: CancellationToken,
+        ) -> Result<String> {
+            self.runtime().block_on(
+                self.async_client()
+                    .new_items_all_cancellable(items, sleep_ms, token),
+            )
+        }
+
+        fn new_items_all_concurrent(
+            &self,
+            items: &[InputItem],
+            concurrency: usize,
+            pacing: Duration,
+        ) -> Result<BatchSummary> {
+            self.runtime()
+                .block_on(
+                    self.async_client()
+                        .new_items_all_concurrent(items, concurrency, pacing),
+                )
+        }
+
+        fn new_items_all_dedup(
+            &self,
+            items: &[InputItem],
+            sleep_ms: u64,
+        ) -> Result<(String, usize)> {
+            self.runtime()
+                .block_on(self.async_client().new_items_all_dedup(items, sleep_ms))
+        }
+
+        fn upsert_items<S>(
+            &self,
+            feed_id: S,
+            items: &[InputItem],
+            mode: UpsertMode,
+            lookback: usize,
+        ) -> Result<UpsertSummary>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime().block_on(
+                self.async_client()
+                    .upsert_items(feed_id, items, mode, lookback),
+            )
+        }
+
+        fn new_items_all_skip_existing(
+            &self,
+            items: &[InputItem],
+            lookback: usize,
+            key: DedupKey,
+        ) -> Result<DedupSummary> {
+            self.runtime().block_on(
+                self.async_client()
+                    .new_items_all_skip_existing(items, lookback, key),
+            )
+        }
+
+        fn new_feed(&self, options: &NewFeedOptions) -> Result<NewFeedResponse> {
+            self.runtime()
+                .block_on(self.async_client().new_feed(options))
+        }
+
+        fn delete_feed<S>(
+            &self,
+            feed_id: S,
+            confirmation: DeleteConfirmation,
+        ) -> Result<DeleteFeedResponse>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().delete_feed(feed_id, confirmation))
+        }
+
+        fn delete_feed_dry_run<S>(&self, feed_id: S) -> Result<FeedInfo>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().delete_feed_dry_run(feed_id))
+        }
+
+        fn list_feeds(&self) -> Result<Vec<FeedInfo>> {
+            self.runtime().block_on(self.async_client().list_feeds())
+        }
+
+        fn ping(&self) -> Result<PingResponse> {
+            self.runtime().block_on(self.async_client().ping())
+        }
+
+        fn ping_bool(&self) -> bool {
+            self.runtime().block_on(self.async_client().ping_bool())
+        }
+
+        fn ping_timed(&self) -> Result<(PingResponse, Duration)> {
+            self.runtime().block_on(self.async_client().ping_timed())
+        }
+
+        fn read_items<S>(&self, feed_id: S) -> Result<Vec<FeedItem>>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().read_items(feed_id))
+        }
+
+        fn read_items_with_options<S>(
+            &self,
+            feed_id: S,
+            options: &ReadOptions,
+        ) -> Result<Vec<FeedItem>>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime().block_on(
+                self.async_client()
+                    .read_items_with_options(feed_id, options),
+            )
+        }
+
+        fn read_items_with_content<S>(&self, feed_id: S) -> Result<Vec<FeedItem>>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().read_items_with_content(feed_id))
+        }
+
+        fn read_items_with_content_n<S>(
+            &self,
+            feed_id: S,
+            max_items: usize,
+        ) -> Result<Vec<FeedItem>>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime().block_on(
+                self.async_client()
+                    .read_items_with_content_n(feed_id, max_items),
+            )
+        }
+
+        fn read_items_many<S>(
+            &self,
+            feed_ids: &[S],
+            options: &ReadOptions,
+            concurrency: usize,
+        ) -> Result<Vec<(String, Result<Vec<FeedItem>>)>>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime().block_on(self.async_client().read_items_many(
+                feed_ids,
+                options,
+                concurrency,
+            ))
+        }
+
+        fn read_items_response<S>(
+            &self,
+            feed_id: S,
+            options: &ReadOptions,
+        ) -> Result<ReadFeedItemsResponse>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().read_items_response(feed_id, options))
+        }
+
+        fn read_items_as<T, S>(&self, feed_id: S, options: &ReadOptions) -> Result<Vec<T>>
+        where
+            T: DeserializeOwned,
+            S: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().read_items_as(feed_id, options))
+        }
+
+        fn read_items_raw<S>(&self, feed_id: S, options: &ReadOptions) -> Result<Value>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().read_items_raw(feed_id, options))
+        }
+
+        fn read_items_direct<S>(&self, feed_id: S, options: &ReadOptions) -> Result<Vec<FeedItem>>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().read_items_direct(feed_id, options))
+        }
+
+        fn read_items_conditional<S>(
+            &self,
+            feed_id: S,
+            options: &ReadOptions,
+            etag: Option<&str>,
+        ) -> Result<ReadOutcome>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime().block_on(
+                self.async_client()
+                    .read_items_conditional(feed_id, options, etag),
+            )
+        }
+
+        fn read_items_filtered<S, F>(
+            &self,
+            feed_id: S,
+            options: &ReadOptions,
+            predicate: F,
+            stop_after: Option<usize>,
+        ) -> Result<FilteredItems>
+        where
+            S: AsRef<str>,
+            F: Fn(&FeedItem) -> bool,
+        {
+            self.runtime().block_on(
+                self.async_client()
+                    .read_items_filtered(feed_id, options, predicate, stop_after),
+            )
+        }
+
+        fn read_items_in_range<S, T>(
+            &self,
+            feed_id: S,
+            after: T,
+            before: T,
+            options: &ReadOptions,
+            range: &RangeOptions,
+        ) -> Result<Vec<FeedItem>>
+        where
+            S: AsRef<str>,
+            T: AsRef<str>,
+        {
+            self.runtime().block_on(
+                self.async_client()
+                    .read_items_in_range(feed_id, after, before, options, range),
+            )
+        }
+
+        fn search_items<S>(
+            &self,
+            feed_id: S,
+            query: &str,
+            options: &ReadOptions,
+        ) -> Result<Vec<FeedItem>>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().search_items(feed_id, query, options))
+        }
+
+        fn count_items<S>(&self, feed_id: S) -> Result<u64>
+        where
+            S: AsRef<str>,
+        {
+            self.runtime()
+                .block_on(self.async_client().count_items(feed_id))
+        }
+
+        fn dump_feed_ndjson<S, W>(
+            &self,
+            feed_id: S,
+            writer: W,
+            limit: Option<usize>,
+        ) -> Result<usize>
+        where
+            S: AsRef<str>,
+            W: Write,
+        {
+            self.runtime()
+                .block_on(self.async_client().dump_feed_ndjson(feed_id, writer, limit))
+        }
+
+        fn wait_for_item<S, F>(
+            &self,
+            feed_id: S,
+            predicate: F,
+            timeout: Duration,
+            poll_interval: Duration,
+        ) -> Result<FeedItem>
+        where
+            S: AsRef<str>,
+            F: Fn(&FeedItem) -> bool,
+        {
+            self.runtime().block_on(self.async_client().wait_for_item(
+                feed_id,
+                predicate,
+                timeout,
+                poll_interval,
+            ))
+        }
+    }
+
+    #[cfg(feature = "csv")]
+    impl<B> YupdatesV0Csv for B
+    where
+        B: private::SyncBackend,
+    {
+        fn new_items_from_csv<P>(
+            &self,
+            path: P,
+            mapping: CsvMapping,
+            sleep_ms: u64,
+        ) -> Result<String>
+        where
+            P: AsRef<Path>,
         {
-            self.rt
-                .block_on(self.client.read_items_with_options(feed_id, options))
+            self.runtime().block_on(
+                self.async_client()
+                    .new_items_from_csv(path, mapping, sleep_ms),
+            )
         }
     }
 }
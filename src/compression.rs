@@ -0,0 +1,58 @@
+//! Gzip compression of request bodies
+//!
+//! Batches of items with large HTML content can be hundreds of kilobytes of JSON. With
+//! [crate::clients::AsyncYupdatesClient::compress_requests], the bodies of the client's POST
+//! requests that are at least [RequestCompression::min_bytes] long are sent gzipped, with a
+//! `Content-Encoding: gzip` header. Smaller bodies are sent as plain JSON, since compressing them
+//! saves little.
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::future::Future;
+use std::io::Write;
+
+tokio::task_local! {
+    // The compression of the client whose call is running
+    static REQUEST_COMPRESSION: RequestCompression;
+}
+
+/// The smallest body that is compressed by default, in bytes
+pub const DEFAULT_MIN_COMPRESSED_BYTES: usize = 8 * 1024;
+
+/// When request bodies are compressed, see [crate::compression]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RequestCompression {
+    /// Bodies shorter than this many bytes are sent uncompressed
+    pub min_bytes: usize,
+}
+
+impl Default for RequestCompression {
+    fn default() -> Self {
+        RequestCompression {
+            min_bytes: DEFAULT_MIN_COMPRESSED_BYTES,
+        }
+    }
+}
+
+// Run `fut` with the request bodies it sends compressed as configured, if they are
+pub(crate) async fn compressed<F: Future>(
+    compression: Option<RequestCompression>,
+    fut: F,
+) -> F::Output {
+    match compression {
+        None => fut.await,
+        Some(compression) => REQUEST_COMPRESSION.scope(compression, fut).await,
+    }
+}
+
+// The body to send, gzipped if the running call compresses bodies of its size
+pub(crate) fn encode_body(body: Vec<u8>) -> std::io::Result<(Vec<u8>, bool)> {
+    let compress = REQUEST_COMPRESSION
+        .try_with(|compression| body.len() >= compression.min_bytes)
+        .unwrap_or(false);
+    if !compress {
+        return Ok((body, false));
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&body)?;
+    Ok((encoder.finish()?, true))
+}
@@ -1,8 +1,11 @@
 //! Error and result types
+use crate::api::BulkProgress;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
 use reqwest::Error as ReqwestError;
 use serde::{Deserialize, Serialize};
 use serde_json::from_str as json_from_str;
 use std::fmt;
+use std::time::{Duration, SystemTime};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -20,16 +23,169 @@ pub struct Error {
 
 #[derive(Debug)]
 pub enum Kind {
+    /// Sending input items in batches failed: the 0-based index of the batch that failed, how
+    /// many items had already been sent, and why the batch failed. See
+    /// [crate::api::YupdatesV0::new_items_all].
+    BatchFailed(usize, usize, Box<Error>),
+    /// Sending input items in batches was cancelled: how many items had already been sent, and
+    /// the feed they were added to, `None` if none were. See
+    /// [crate::api::YupdatesV0::new_items_all_cancellable].
+    Cancelled {
+        items_sent: usize,
+        feed_id: Option<String>,
+    },
     Config(String),
+    /// Could not connect to the API, for example because DNS resolution, the TCP connection, or
+    /// the TLS handshake failed
+    Connect(ReqwestError),
     Deserialization(String),
     DetailedHttpCode(u16, String),
+    /// There is no such feed, see [crate::api::YupdatesV0::delete_feed]
+    FeedNotFound(String),
+    /// The token may not make the call (an HTTP 403), with the API's message if it sent one. See
+    /// [crate::api::YupdatesV0::delete_feed] and [crate::api::YupdatesV0::delete_item].
+    Forbidden(String),
     HttpCode(u16),
     IllegalParameter(String),
     IllegalResult(String),
+    Io(std::io::Error),
+    /// The item was already deleted, see [crate::api::YupdatesV0::delete_item]
+    ItemDeleted(String),
+    /// There is no such item, see [crate::api::YupdatesV0::get_item]
+    ItemNotFound(String),
+    /// A middleware of the HTTP client failed the call, see [crate::middleware]
+    Middleware(String),
+    /// The API throttled the call (an HTTP 429), with how long it asked to wait before trying
+    /// again, if it sent a `Retry-After` header, and its message, if it sent one
+    RateLimited {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// Any other problem with the HTTP call
     Reqwest(ReqwestError),
+    /// An HTTP call that exceeded the HTTP client's timeout
+    RequestTimeout(ReqwestError),
+    /// A wait like [crate::api::YupdatesV0::wait_for_item] that ran out of time
+    Timeout(String),
 }
 
+impl Error {
+    /// The HTTP status code of the response that caused this error, if there was one.
+    ///
+    /// This is the code of [Kind::HttpCode] and [Kind::DetailedHttpCode], of the HTTP client's
+    /// error when it has one, and of the failed batch for [Kind::BatchFailed]. The errors that
+    /// the SDK makes from particular codes have theirs: 403 for [Kind::Forbidden], 404 for
+    /// [Kind::FeedNotFound] and [Kind::ItemNotFound], 410 for [Kind::ItemDeleted], and 429 for
+    /// [Kind::RateLimited].
+    pub fn status_code(&self) -> Option<u16> {
+        match &self.kind {
+            Kind::HttpCode(code) | Kind::DetailedHttpCode(code, _) => Some(*code),
+            Kind::Connect(e) | Kind::Reqwest(e) | Kind::RequestTimeout(e) => {
+                e.status().map(|status| status.as_u16())
+            }
+            Kind::BatchFailed(_, _, e) => e.status_code(),
+            Kind::Forbidden(_) => Some(403),
+            Kind::FeedNotFound(_) | Kind::ItemNotFound(_) => Some(404),
+            Kind::ItemDeleted(_) => Some(410),
+            Kind::RateLimited { .. } => Some(429),
+            _ => None,
+        }
+    }
+
+    /// Whether the [Error::status_code] is a 4XX, a problem with the request
+    pub fn is_client_error(&self) -> bool {
+        self.status_code()
+            .is_some_and(|code| (400..500).contains(&code))
+    }
+
+    /// Whether the [Error::status_code] is a 5XX, a problem on the server
+    pub fn is_server_error(&self) -> bool {
+        self.status_code()
+            .is_some_and(|code| (500..600).contains(&code))
+    }
+
+    /// Whether the same call may succeed if it is made again later.
+    ///
+    /// That is the case for problems connecting to the API, requests that timed out, throttling
+    /// (HTTP 429), and the server errors HTTP 500, 502, 503, and 504. Other errors, like the rest
+    /// of the 4XX codes, configuration problems, and responses that could not be deserialized,
+    /// will fail the same way again. A [Kind::Timeout] is not retryable: the wait it gave up on
+    /// already polled until its deadline. A [Kind::BatchFailed] is retryable if the failed batch is. The retries
+    /// that the SDK makes itself, like [crate::api::YupdatesV0::new_items_all_with_retry], use
+    /// this too.
+    pub fn is_retryable(&self) -> bool {
+        match &self.kind {
+            Kind::Connect(_) | Kind::RequestTimeout(_) | Kind::RateLimited { .. } => true,
+            Kind::Reqwest(e) if e.is_connect() || e.is_timeout() => true,
+            Kind::BatchFailed(_, _, e) => e.is_retryable(),
+            Kind::HttpCode(_) | Kind::DetailedHttpCode(_, _) | Kind::Reqwest(_) => {
+                matches!(self.status_code(), Some(429 | 500 | 502 | 503 | 504))
+            }
+            _ => false,
+        }
+    }
+
+    /// How long the server asked to wait before trying again, with a `Retry-After` header. Only
+    /// a [Kind::RateLimited] error has it, and a [Kind::BatchFailed] has the one of the failed
+    /// batch.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match &self.kind {
+            Kind::RateLimited { retry_after, .. } => *retry_after,
+            Kind::BatchFailed(_, _, e) => e.retry_after(),
+            _ => None,
+        }
+    }
+}
+
+/// An error from [crate::api::YupdatesV0::new_items_all_resume], with the progress to resume from
+#[derive(Debug)]
+pub struct BulkError {
+    pub progress: BulkProgress,
+    pub error: Error,
+}
+
+impl fmt::Display for BulkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (resume at item {})",
+            self.error, self.progress.next_index
+        )
+    }
+}
+
+/// A [Kind::BatchFailed] error, so that `?` works in functions that return a [Result]
+impl From<BulkError> for Error {
+    fn from(e: BulkError) -> Self {
+        Error {
+            kind: Kind::BatchFailed(
+                e.progress.batches_sent,
+                e.progress.next_index,
+                Box::new(e.error),
+            ),
+        }
+    }
+}
+
+/// The error for an API response with status `code` and body `text`. See
+/// [api_error_with_headers] for a [Kind::RateLimited] error with the `Retry-After` of a 429.
 pub fn api_error(code: u16, text: &str) -> Error {
+    api_error_with_headers(code, &HeaderMap::new(), text)
+}
+
+/// Like [api_error], with the response's `headers`. A 429 is a [Kind::RateLimited] error.
+pub fn api_error_with_headers(code: u16, headers: &HeaderMap, text: &str) -> Error {
+    if code == 429 {
+        let message = json_from_str::<ApiErrorData>(text)
+            .map(|data| msg_from_api_error_data(&data))
+            .unwrap_or_default();
+        return Error {
+            kind: Kind::RateLimited {
+                retry_after: retry_after(headers),
+                message,
+            },
+        };
+    }
     match json_from_str::<ApiErrorData>(text) {
         Ok(data) => {
             let msg = msg_from_api_error_data(&data);
@@ -43,6 +199,20 @@ pub fn api_error(code: u16, text: &str) -> Error {
     }
 }
 
+// The `Retry-After` header, in either its delay-seconds or HTTP-date form. A date in the past
+// is no wait at all.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
 pub fn msg_from_api_error_data(data: &ApiErrorData) -> String {
     let err = data
         .error
@@ -62,9 +232,20 @@ pub fn msg_from_api_error_data(data: &ApiErrorData) -> String {
 
 impl From<reqwest::Error> for Error {
     fn from(e: reqwest::Error) -> Self {
-        Error {
-            kind: Kind::Reqwest(e),
-        }
+        let kind = if e.is_timeout() {
+            Kind::RequestTimeout(e)
+        } else if e.is_connect() {
+            Kind::Connect(e)
+        } else {
+            Kind::Reqwest(e)
+        };
+        Error { kind }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error { kind: Kind::Io(e) }
     }
 }
 
@@ -79,12 +260,39 @@ impl From<serde_json::Error> for Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let msg = match &self.kind {
+            Kind::BatchFailed(batch_index, items_sent, e) => {
+                format!(
+                    "Batch at index {} failed after {} items were sent: {}",
+                    batch_index, items_sent, e
+                )
+            }
+            Kind::Cancelled {
+                items_sent,
+                feed_id: Some(feed_id),
+            } => {
+                format!(
+                    "Cancelled after {} items were sent to feed {}",
+                    items_sent, feed_id
+                )
+            }
+            Kind::Cancelled { items_sent, .. } => {
+                format!("Cancelled after {} items were sent", items_sent)
+            }
             Kind::Config(s) => {
                 format!("Configuration issue: {}", s)
             }
+            Kind::Connect(e) => {
+                format!("Could not connect to the API: {}", e)
+            }
             Kind::DetailedHttpCode(code, s) => {
                 format!("HTTP {}: {}", code, s)
             }
+            Kind::FeedNotFound(feed_id) => {
+                format!("Feed not found: {}", feed_id)
+            }
+            Kind::Forbidden(s) => {
+                format!("Forbidden: {}", s)
+            }
             Kind::HttpCode(code) => {
                 format!("HTTP {}", code)
             }
@@ -97,10 +305,54 @@ impl fmt::Display for Error {
             Kind::IllegalParameter(s) => {
                 format!("Illegal parameter: {}", s)
             }
+            Kind::Io(e) => {
+                format!("I/O problem: {}", e)
+            }
+            Kind::ItemDeleted(item_id) => {
+                format!("Item already deleted: {}", item_id)
+            }
+            Kind::ItemNotFound(item_id) => {
+                format!("Item not found: {}", item_id)
+            }
+            Kind::Middleware(s) => {
+                format!("HTTP middleware problem: {}", s)
+            }
+            Kind::RateLimited {
+                retry_after,
+                message,
+            } => {
+                let mut msg = String::from("HTTP 429");
+                if let Some(retry_after) = retry_after {
+                    // In whole seconds, rounded up like the header's own
+                    let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+                    msg.push_str(&format!(", retry after {}s", seconds));
+                }
+                if !message.is_empty() {
+                    msg.push_str(&format!(": {}", message));
+                }
+                msg
+            }
             Kind::Reqwest(e) => {
                 format!("Problem with API call: {}", e)
             }
+            Kind::RequestTimeout(e) => {
+                format!("Timed out: {}", e)
+            }
+            Kind::Timeout(s) => {
+                format!("Timed out: {}", s)
+            }
         };
         write!(f, "{}", msg)
     }
 }
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            Kind::BatchFailed(_, _, e) => Some(e.as_ref()),
+            Kind::Connect(e) | Kind::Reqwest(e) | Kind::RequestTimeout(e) => Some(e),
+            Kind::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
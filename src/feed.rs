@@ -0,0 +1,82 @@
+//! Feed items as CSV, enabled with the `csv` feature
+//!
+//! [write_csv] writes a header row and one row per item, for analysis in a spreadsheet. Fields
+//! with commas, quotes, or newlines are quoted. The content is left out unless
+//! [CsvExportOptions::include_content] is set, since it can be large.
+//!
+//! ```rust
+//! use yupdates::feed::write_csv;
+//! use yupdates::models::FeedItem;
+//!
+//! let item: FeedItem = serde_json::from_value(serde_json::json!({
+//!     "item_id": "abc",
+//!     "title": "Hello, world",
+//!     "canonical_url": "https://www.example.com/hello",
+//!     "item_time": "1661564013000.00000",
+//! }))
+//! .unwrap();
+//! let mut csv = Vec::new();
+//! write_csv(&[item], &mut csv).unwrap();
+//! assert_eq!(
+//!     String::from_utf8(csv).unwrap(),
+//!     "item_id,item_time,title,canonical_url,file_count\n\
+//!      abc,1661564013000.00000,\"Hello, world\",https://www.example.com/hello,0\n"
+//! );
+//! ```
+use crate::errors::Result;
+use crate::models::FeedItem;
+use std::io::Write;
+
+/// What [write_csv_with_options] writes
+#[derive(Clone, Debug, Default)]
+pub struct CsvExportOptions {
+    /// Add a last `content` column. An item whose content was not read has an empty cell (see
+    /// [crate::api::ReadOptions::include_item_content]).
+    pub include_content: bool,
+}
+
+/// Write the items as CSV with the columns `item_id`, `item_time`, `title`, `canonical_url`, and
+/// `file_count` (how many associated files the item has), using the default [CsvExportOptions]
+pub fn write_csv<W: Write>(items: &[FeedItem], w: &mut W) -> Result<()> {
+    write_csv_with_options(items, w, &Default::default())
+}
+
+/// Like [write_csv], with your own [CsvExportOptions]
+pub fn write_csv_with_options<W: Write>(
+    items: &[FeedItem],
+    w: &mut W,
+    options: &CsvExportOptions,
+) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(w);
+    let mut header = vec![
+        "item_id",
+        "item_time",
+        "title",
+        "canonical_url",
+        "file_count",
+    ];
+    if options.include_content {
+        header.push("content");
+    }
+    writer.write_record(&header).map_err(std::io::Error::from)?;
+    for item in items {
+        let file_count = item
+            .associated_files
+            .as_ref()
+            .map_or(0, Vec::len)
+            .to_string();
+        let mut record = vec![
+            item.item_id.as_str(),
+            item.item_time.as_str(),
+            item.title.as_str(),
+            item.canonical_url.as_str(),
+            file_count.as_str(),
+        ];
+        if options.include_content {
+            record.push(item.content.as_deref().unwrap_or_default());
+        }
+        writer.write_record(&record).map_err(std::io::Error::from)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
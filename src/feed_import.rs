@@ -0,0 +1,143 @@
+//! Input items from an external RSS 2.0 or Atom feed, enabled with the `feed-import` feature
+//!
+//! [import_from_feed_url] fetches and parses a feed and returns its entries as [InputItem]s. It
+//! does not add them to a Yupdates feed: filter out the items you already have, then pass the
+//! rest to [crate::api::YupdatesV0::new_items_all].
+//!
+//! RSS items are converted like `InputItem::try_from(&rss::Item)` does (see the `rss-interop`
+//! feature). Atom entries are converted the same way: the title, the `alternate` link as the
+//! canonical URL, the content (or the summary if there is no content), the first author's name,
+//! and each `enclosure` link as an [AssociatedFile] (with its type guessed from the URL if it has
+//! none).
+use crate::errors::{Error, Kind, Result};
+use crate::models::{guess_mime_type, AssociatedFile, InputItem};
+use atom_syndication::{Entry, Feed, Link};
+use rss::Channel;
+
+/// Fetch the RSS 2.0 or Atom feed at `feed_url` and convert up to `limit` of its entries, in the
+/// order the feed lists them. Entries without a link are skipped.
+///
+/// An entry with a link but no title, or with an enclosure whose length is missing or not a
+/// number, is a [Kind::IllegalParameter] error. A response that is not an RSS 2.0 or Atom feed
+/// is a [Kind::Deserialization] error, and an HTTP error status is a [Kind::HttpCode] error.
+pub async fn import_from_feed_url(feed_url: &str, limit: usize) -> Result<Vec<InputItem>> {
+    import_from_feed_url_with_args(feed_url, limit, &reqwest::Client::new()).await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(feed_url = feed_url, limit = limit))
+)]
+pub async fn import_from_feed_url_with_args(
+    feed_url: &str,
+    limit: usize,
+    http_client: &reqwest::Client,
+) -> Result<Vec<InputItem>> {
+    let res = http_client.get(feed_url).send().await?;
+    let status = res.status();
+    if !status.is_success() {
+        return Err(Error {
+            kind: Kind::HttpCode(status.as_u16()),
+        });
+    }
+    let body = res.bytes().await?;
+    items_from_feed(&body, limit)
+}
+
+/// Convert up to `limit` entries of an RSS 2.0 or Atom document, like [import_from_feed_url]
+/// does with the feed it fetches
+pub fn items_from_feed(feed: &[u8], limit: usize) -> Result<Vec<InputItem>> {
+    if let Ok(channel) = Channel::read_from(feed) {
+        return channel
+            .items()
+            .iter()
+            .filter(|item| item.link().is_some_and(|link| !link.trim().is_empty()))
+            .take(limit)
+            .map(InputItem::try_from)
+            .collect();
+    }
+    match Feed::read_from(feed) {
+        Ok(feed) => feed
+            .entries()
+            .iter()
+            .filter(|entry| alternate_link(entry).is_some())
+            .take(limit)
+            .map(InputItem::try_from)
+            .collect(),
+        Err(e) => Err(Error {
+            kind: Kind::Deserialization(format!("not an RSS 2.0 or Atom feed: {}", e)),
+        }),
+    }
+}
+
+impl TryFrom<&Entry> for InputItem {
+    type Error = Error;
+
+    /// Fails with [Kind::IllegalParameter] if the entry has no title or `alternate` link, or if
+    /// an enclosure's length is missing or not a number.
+    fn try_from(entry: &Entry) -> Result<Self> {
+        let title = entry.title().trim();
+        if title.is_empty() {
+            return Err(Error {
+                kind: Kind::IllegalParameter("Atom entry has no `title`".to_string()),
+            });
+        }
+        let canonical_url = alternate_link(entry).ok_or_else(|| Error {
+            kind: Kind::IllegalParameter("Atom entry has no `alternate` link".to_string()),
+        })?;
+        let files = entry
+            .links()
+            .iter()
+            .filter(|link| link.rel() == "enclosure")
+            .map(enclosure_file)
+            .collect::<Result<Vec<_>>>()?;
+        let content = match entry.content().and_then(|content| content.value()) {
+            Some(content) => content.to_string(),
+            None => entry
+                .summary()
+                .map(|summary| summary.value.clone())
+                .unwrap_or_default(),
+        };
+        Ok(InputItem {
+            title: title.to_string(),
+            content,
+            canonical_url: canonical_url.to_string(),
+            author: entry
+                .authors()
+                .first()
+                .map(|person| person.name().to_string()),
+            associated_files: Some(files).filter(|files| !files.is_empty()),
+            input_id: None,
+            item_time: None,
+            extra: Default::default(),
+        })
+    }
+}
+
+// A link without a `rel` is an alternate link, and the parser reads it as one
+fn alternate_link(entry: &Entry) -> Option<&str> {
+    entry
+        .links()
+        .iter()
+        .find(|link| link.rel() == "alternate" && !link.href().trim().is_empty())
+        .map(|link| link.href().trim())
+}
+
+fn enclosure_file(link: &Link) -> Result<AssociatedFile> {
+    let length = link.length().unwrap_or_default();
+    let length = length.trim().parse().map_err(|_| Error {
+        kind: Kind::IllegalParameter(format!(
+            "Atom enclosure `length` is not a number: '{}'",
+            length
+        )),
+    })?;
+    Ok(AssociatedFile {
+        url: link.href().to_string(),
+        length,
+        type_str: link
+            .mime_type()
+            .or_else(|| guess_mime_type(link.href()))
+            .unwrap_or("application/octet-stream")
+            .to_string(),
+    })
+}
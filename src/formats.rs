@@ -0,0 +1,2 @@
+//! Render feed items in other feed formats, enabled with the `rss` feature
+pub mod rss;
@@ -0,0 +1,152 @@
+//! RSS 2.0 and Atom documents from feed items
+//!
+//! This maps `title`, `content`, `canonical_url`, `author`, the item time, and `associated_files`
+//! (as enclosures) into the document. RSS allows one enclosure per item, so only the first file is
+//! written there, while Atom gets them all. Items without a canonical URL get no link. The content is
+//! escaped and carried as HTML, which is what feed readers expect. Items are written in the order
+//! given.
+use crate::models::{FeedId, FeedItem};
+use crate::time::{item_ms, rfc3339, rfc822};
+
+/// Render the items as an RSS 2.0 document. If `skip_deleted` is true, deleted items are left
+/// out.
+pub fn to_rss(
+    channel_title: &str,
+    channel_link: &str,
+    items: &[FeedItem],
+    skip_deleted: bool,
+) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\">\n");
+    xml.push_str("  <channel>\n");
+    push_element(&mut xml, 4, "title", channel_title);
+    push_element(&mut xml, 4, "link", channel_link);
+    // Required by RSS 2.0, there is nothing more specific to say
+    push_element(&mut xml, 4, "description", channel_title);
+    for item in items.iter().filter(|item| !(skip_deleted && item.deleted)) {
+        xml.push_str("    <item>\n");
+        push_element(&mut xml, 6, "title", &item.title);
+        if !item.canonical_url.is_empty() {
+            push_element(&mut xml, 6, "link", &item.canonical_url);
+        }
+        xml.push_str(&format!(
+            "      <guid isPermaLink=\"false\">{}</guid>\n",
+            escape(item.item_id.as_str())
+        ));
+        push_element(&mut xml, 6, "pubDate", &rfc822(item_ms(item)));
+        if let Some(author) = &item.author {
+            push_element(&mut xml, 6, "author", author);
+        }
+        if let Some(content) = &item.content {
+            push_element(&mut xml, 6, "description", content);
+        }
+        if let Some(file) = item.associated_files.iter().flatten().next() {
+            xml.push_str(&format!(
+                "      <enclosure url=\"{}\" length=\"{}\" type=\"{}\"/>\n",
+                escape(&file.url),
+                file.length,
+                escape(&file.type_str)
+            ));
+        }
+        xml.push_str("    </item>\n");
+    }
+    xml.push_str("  </channel>\n");
+    xml.push_str("</rss>\n");
+    xml
+}
+
+/// Render the items as an Atom document. If `skip_deleted` is true, deleted items are left out.
+///
+/// The feed is identified by `channel_link`, and each entry by its feed ID and item ID. The
+/// feed's `updated` time is the newest item time.
+pub fn to_atom(
+    channel_title: &str,
+    channel_link: &str,
+    items: &[FeedItem],
+    skip_deleted: bool,
+) -> String {
+    let items: Vec<&FeedItem> = items
+        .iter()
+        .filter(|item| !(skip_deleted && item.deleted))
+        .collect();
+    let updated = items.iter().map(|item| item_ms(item)).max().unwrap_or(0);
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    push_element(&mut xml, 2, "title", channel_title);
+    xml.push_str(&format!("  <link href=\"{}\"/>\n", escape(channel_link)));
+    push_element(&mut xml, 2, "id", channel_link);
+    push_element(&mut xml, 2, "updated", &rfc3339(updated));
+    for item in items {
+        xml.push_str("  <entry>\n");
+        push_element(&mut xml, 4, "title", &item.title);
+        if !item.canonical_url.is_empty() {
+            xml.push_str(&format!(
+                "    <link href=\"{}\"/>\n",
+                escape(&item.canonical_url)
+            ));
+        }
+        push_element(
+            &mut xml,
+            4,
+            "id",
+            &format!(
+                "urn:yupdates:{}:{}",
+                item.feed_id.as_ref().map_or("", FeedId::as_str),
+                item.item_id
+            ),
+        );
+        push_element(&mut xml, 4, "updated", &rfc3339(item_ms(item)));
+        if let Some(author) = &item.author {
+            xml.push_str("    <author>\n");
+            push_element(&mut xml, 6, "name", author);
+            xml.push_str("    </author>\n");
+        }
+        if let Some(content) = &item.content {
+            xml.push_str(&format!(
+                "    <content type=\"html\">{}</content>\n",
+                escape(content)
+            ));
+        }
+        for file in item.associated_files.iter().flatten() {
+            xml.push_str(&format!(
+                "    <link rel=\"enclosure\" href=\"{}\" length=\"{}\" type=\"{}\"/>\n",
+                escape(&file.url),
+                file.length,
+                escape(&file.type_str)
+            ));
+        }
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+fn push_element(xml: &mut String, indent: usize, name: &str, text: &str) {
+    xml.push_str(&format!(
+        "{:indent$}<{}>{}</{}>\n",
+        "",
+        name,
+        escape(text),
+        name,
+        indent = indent
+    ));
+}
+
+// Escapes text for element content and attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,170 @@
+//! Plain text from item content, enabled with the `html-text` feature
+//!
+//! [FeedItem::content_text] gives the text that the item's HTML `content` shows, for sinks that
+//! cannot show HTML (SMS, a terminal, a chat message). Tags are removed, entities are decoded,
+//! and runs of whitespace become one space like a browser shows them. Paragraphs and other blocks
+//! are separated by an empty line, and `<br>` and list items start a new line. Whitespace in
+//! `<pre>` is kept. The contents of `<script>`, `<style>`, `<template>`, `<head>`, and `<title>`
+//! are left out.
+//!
+//! ```rust
+//! use yupdates::html_text::html_to_text;
+//!
+//! let text = html_to_text("<h1>News</h1><p>Fish &amp;   chips<br>today</p><ul><li>a</li><li>b");
+//! assert_eq!(text, "News\n\nFish & chips\ntoday\n\na\nb");
+//! ```
+use crate::models::FeedItem;
+use html_escape::decode_html_entities;
+use tl::{Node, NodeHandle, Parser, ParserOptions};
+
+// Elements that are separated from what is around them by an empty line
+const BLOCKS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "ul",
+];
+
+// Elements that start on a new line
+const LINES: &[&str] = &["li", "tr", "caption"];
+
+// Elements whose contents are not shown
+const HIDDEN: &[&str] = &["script", "style", "template", "head", "title"];
+
+impl FeedItem {
+    /// The text that the content shows, see [crate::html_text]. `None` if the content was not
+    /// read (see [crate::api::ReadOptions::include_item_content]).
+    pub fn content_text(&self) -> Option<String> {
+        self.content.as_deref().map(html_to_text)
+    }
+}
+
+/// The text that `html` shows, like [FeedItem::content_text] gives for the content
+pub fn html_to_text(html: &str) -> String {
+    let mut text = Text::default();
+    // Parsing only fails for input longer than 4 GiB
+    if let Ok(dom) = tl::parse(html, ParserOptions::default()) {
+        for handle in dom.children() {
+            text.push_node(*handle, dom.parser());
+        }
+    }
+    // A <pre> can end with a newline
+    let len = text.out.trim_end_matches(['\n', '\r']).len();
+    text.out.truncate(len);
+    text.out
+}
+
+#[derive(Default)]
+struct Text {
+    out: String,
+    // Newlines to write before the next text, at most 2
+    newlines: usize,
+    // A space to write before the next text, if there are no newlines
+    space: bool,
+    // Inside <pre>
+    preformatted: usize,
+}
+
+impl Text {
+    fn push_node(&mut self, handle: NodeHandle, parser: &Parser) {
+        let Some(node) = handle.get(parser) else {
+            return;
+        };
+        match node {
+            Node::Raw(raw) => self.push_text(&decode_html_entities(&raw.as_utf8_str())),
+            Node::Comment(_) => {}
+            Node::Tag(tag) => {
+                let name = tag.name().as_utf8_str().to_ascii_lowercase();
+                // The parser keeps the slash of `<br/>` in the name
+                let name = name.trim_end_matches('/');
+                if HIDDEN.contains(&name) {
+                    return;
+                }
+                match name {
+                    "br" => self.newlines = (self.newlines + 1).min(2),
+                    "td" | "th" => self.space = true,
+                    _ if BLOCKS.contains(&name) => self.newlines = 2,
+                    _ if LINES.contains(&name) => self.newlines = self.newlines.max(1),
+                    _ => {}
+                }
+                if name == "pre" {
+                    self.preformatted += 1;
+                }
+                for child in tag.children().top().iter() {
+                    self.push_node(*child, parser);
+                }
+                if name == "pre" {
+                    self.preformatted -= 1;
+                }
+                if BLOCKS.contains(&name) {
+                    self.newlines = 2;
+                } else if LINES.contains(&name) {
+                    self.newlines = self.newlines.max(1);
+                }
+            }
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if self.preformatted > 0 {
+            self.push_word(text);
+            return;
+        }
+        // Browsers collapse ASCII whitespace only, a non-breaking space stays
+        let html_whitespace = |c: char| c.is_ascii_whitespace();
+        if text.starts_with(html_whitespace) {
+            self.space = true;
+        }
+        for word in text.split(html_whitespace).filter(|word| !word.is_empty()) {
+            self.push_word(word);
+            self.space = true;
+        }
+        if !text.ends_with(html_whitespace) && !text.is_empty() {
+            self.space = false;
+        }
+    }
+
+    fn push_word(&mut self, word: &str) {
+        if word.is_empty() {
+            return;
+        }
+        // Nothing goes before the first text
+        if !self.out.is_empty() {
+            if self.newlines > 0 {
+                self.out.extend(std::iter::repeat_n('\n', self.newlines));
+            } else if self.space {
+                self.out.push(' ');
+            }
+        }
+        self.out.push_str(word);
+        self.newlines = 0;
+        self.space = false;
+    }
+}
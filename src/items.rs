@@ -0,0 +1,191 @@
+//! Input items from CSV, enabled with the `csv` feature
+//!
+//! Content prepared in a spreadsheet can be exported as CSV and read with [read_csv]. The first
+//! row is the header, and [CsvMapping] says which columns hold which fields. To read a file and
+//! add its items in one call, see [crate::api::YupdatesV0Csv::new_items_from_csv].
+//!
+//! ```rust
+//! use yupdates::items::{read_csv, CsvMapping};
+//!
+//! let csv = "title,content,url\n\
+//!            Hello,<p>World</p>,https://www.example.com/hello\n";
+//! let items = read_csv(csv.as_bytes(), CsvMapping::default()).unwrap();
+//! assert_eq!(items[0].title, "Hello");
+//! assert_eq!(items[0].canonical_url, "https://www.example.com/hello");
+//! ```
+use crate::api::problems_to_result;
+use crate::errors::{Error, Kind, Result};
+use crate::models::{AssociatedFile, InputItem};
+use csv::StringRecord;
+use std::io::Read;
+
+/// Which CSV columns hold the fields of an item, by their header. Headers are matched ignoring
+/// case and surrounding whitespace.
+///
+/// The default reads the columns `title`, `content`, and `url`, and no file. An item can have
+/// one associated file: set `file_url` and `file_length` to read it from the row. A row whose
+/// file URL cell is empty has no file. If `file_type` is not set, or its cell is empty, the type
+/// is guessed from the URL, see [AssociatedFile::new].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvMapping {
+    pub title: String,
+    pub content: String,
+    pub canonical_url: String,
+    pub file_url: Option<String>,
+    pub file_length: Option<String>,
+    pub file_type: Option<String>,
+}
+
+impl Default for CsvMapping {
+    fn default() -> Self {
+        CsvMapping {
+            title: "title".to_string(),
+            content: "content".to_string(),
+            canonical_url: "url".to_string(),
+            file_url: None,
+            file_length: None,
+            file_type: None,
+        }
+    }
+}
+
+/// Read one item from each row after the header, see [CsvMapping].
+///
+/// Every row is checked before this returns. Rows with a missing title, content, or URL, or a
+/// file that is not valid, are all reported in one [Kind::IllegalParameter] error. Rows are
+/// numbered like a spreadsheet numbers them: the header is row 1, so the first item is row 2.
+/// A column in the mapping that is not in the header is a [Kind::IllegalParameter] error too,
+/// and CSV that cannot be read is a [Kind::Deserialization] error.
+pub fn read_csv<R: Read>(r: R, mapping: CsvMapping) -> Result<Vec<InputItem>> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(r);
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let columns = Columns::new(&headers, &mapping)?;
+
+    let mut items = Vec::new();
+    let mut problems = Vec::new();
+    for (idx, record) in reader.records().enumerate() {
+        let record = record.map_err(csv_error)?;
+        let row = idx + 2;
+        match columns.item(&record) {
+            Ok(item) => items.push(item),
+            Err(row_problems) => problems.extend(
+                row_problems
+                    .into_iter()
+                    .map(|problem| format!("row {}: {}", row, problem)),
+            ),
+        }
+    }
+    problems_to_result(problems)?;
+    Ok(items)
+}
+
+// The index of each mapped column in the header
+struct Columns {
+    title: usize,
+    content: usize,
+    canonical_url: usize,
+    file_url: Option<usize>,
+    file_length: Option<usize>,
+    file_type: Option<usize>,
+}
+
+impl Columns {
+    fn new(headers: &StringRecord, mapping: &CsvMapping) -> Result<Columns> {
+        let find = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.trim().eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| Error {
+                    kind: Kind::IllegalParameter(format!("the CSV has no '{}' column", name)),
+                })
+        };
+        let find_optional = |name: &Option<String>| name.as_deref().map(find).transpose();
+        if mapping.file_url.is_some() && mapping.file_length.is_none() {
+            return Err(Error {
+                kind: Kind::IllegalParameter(
+                    "a CSV mapping with a file URL column needs a file length column".to_string(),
+                ),
+            });
+        }
+        Ok(Columns {
+            title: find(&mapping.title)?,
+            content: find(&mapping.content)?,
+            canonical_url: find(&mapping.canonical_url)?,
+            file_url: find_optional(&mapping.file_url)?,
+            file_length: find_optional(&mapping.file_length)?,
+            file_type: find_optional(&mapping.file_type)?,
+        })
+    }
+
+    // The item in the row, or everything that is wrong with the row
+    fn item(&self, record: &StringRecord) -> std::result::Result<InputItem, Vec<String>> {
+        let cell = |idx: usize| record.get(idx).map(str::trim).unwrap_or_default();
+        let mut problems = Vec::new();
+        for (idx, field) in [
+            (self.title, "title"),
+            (self.content, "content"),
+            (self.canonical_url, "URL"),
+        ] {
+            if cell(idx).is_empty() {
+                problems.push(format!("missing {}", field));
+            }
+        }
+        let file = match self.file_url.map(cell).filter(|url| !url.is_empty()) {
+            None => None,
+            Some(url) => match self.file(url, record) {
+                Ok(file) => Some(file),
+                Err(problem) => {
+                    problems.push(problem);
+                    None
+                }
+            },
+        };
+        if !problems.is_empty() {
+            return Err(problems);
+        }
+        InputItem::builder()
+            .title(cell(self.title))
+            .content(cell(self.content))
+            .canonical_url(cell(self.canonical_url))
+            .files(file)
+            .build()
+            .map_err(|e| vec![problem(e)])
+    }
+
+    fn file(
+        &self,
+        url: &str,
+        record: &StringRecord,
+    ) -> std::result::Result<AssociatedFile, String> {
+        let cell = |idx: Option<usize>| {
+            idx.and_then(|idx| record.get(idx))
+                .map(str::trim)
+                .unwrap_or_default()
+        };
+        let length = cell(self.file_length);
+        let length = match length.parse::<u64>() {
+            Ok(length) if length > 0 => length,
+            _ if length.is_empty() => return Err("missing file length".to_string()),
+            _ => return Err(format!("file length '{}' is not a positive number", length)),
+        };
+        let file = AssociatedFile::new(url, length).map_err(problem)?;
+        Ok(match cell(self.file_type) {
+            "" => file,
+            type_str => file.with_type(type_str),
+        })
+    }
+}
+
+// The message of an error about the row, without the kind that the whole error repeats
+fn problem(e: Error) -> String {
+    match e.kind {
+        Kind::IllegalParameter(s) => s,
+        _ => e.to_string(),
+    }
+}
+
+fn csv_error(e: csv::Error) -> Error {
+    Error {
+        kind: Kind::Deserialization(format!("CSV: {}", e)),
+    }
+}
@@ -46,30 +46,86 @@
 //! }
 //! ```
 //!
+//! To add items, use a feed-specific API token. [models::InputItem::builder] checks each item as
+//! it is built:
+//! ```rust,no_run
+//! use yupdates::clients::new_async_client;
+//! use yupdates::errors::Error;
+//! use yupdates::models::InputItem;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Error> {
+//!     let yup = new_async_client()?;
+//!     let item = InputItem::builder()
+//!         .title("Hello")
+//!         .content("<p>World</p>")
+//!         .canonical_url("https://www.example.com/hello")
+//!         .build()?;
+//!     let feed_id = yup.new_items_all(&[item], 5).await?;
+//!     println!("Added to feed {}", feed_id);
+//!     Ok(())
+//! }
+//! ```
+//!
 //! See the [README](https://github.com/yupdates/yupdates-sdk-rs/blob/main/README.md).
 //! The SDK is distributed under the MIT license, see [LICENSE](https://github.com/yupdates/yupdates-sdk-rs/blob/main/LICENSE).
 
 pub mod api;
+#[cfg(feature = "cache")]
+pub mod cache;
 pub mod clients;
+pub mod compression;
 pub mod errors;
+#[cfg(feature = "csv")]
+pub mod feed;
+#[cfg(feature = "feed-import")]
+pub mod feed_import;
+#[cfg(feature = "rss")]
+pub mod formats;
+#[cfg(feature = "html-text")]
+pub mod html_text;
+#[cfg(feature = "csv")]
+pub mod items;
+pub mod limits;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+#[cfg(feature = "reqwest-middleware")]
+pub mod middleware;
 pub mod models;
+pub mod ndjson;
+pub mod rate_limit;
+#[cfg(feature = "rss-interop")]
+mod rss_interop;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod time;
 
 use crate::errors::{Error, Kind, Result};
 
 use std::env;
 use std::env::VarError;
+use std::fs;
+use std::path::Path;
 
 /// The HTTP header we need on every API call
 pub const X_AUTH_TOKEN_HEADER: &str = "X-Auth-Token";
+/// The HTTP header that carries the key of an idempotent new items call, see
+/// [api::YupdatesV0::new_items_idempotent]
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
 /// Environment variable to consult for the API token (you can bypass this by passing the token
 /// directly to certain functions)
 pub const YUPDATES_API_TOKEN: &str = "YUPDATES_API_TOKEN";
+/// Environment variable to consult for the path of a file containing the API token, if
+/// [YUPDATES_API_TOKEN] is not set. This follows the `_FILE` convention of Docker secrets.
+pub const YUPDATES_API_TOKEN_FILE: &str = "YUPDATES_API_TOKEN_FILE";
 /// Environment variable to consult for the base API URL. It's not usually needed: you would
 /// typically only use this to exercise against an alternative API endpoint, or if you wanted
 /// to downgrade API versions in the future (right now, there is only `/api/v0/`).
 pub const YUPDATES_API_URL: &str = "YUPDATES_API_URL";
 /// The default base URL
 pub const YUPDATES_DEFAULT_API_URL: &str = "https://feeds.yupdates.com/api/v0/";
+/// Every feed ID is this many characters
+pub const FEED_ID_LEN: usize = 45;
 
 /// Retrieve the API URL from the environment or use the default.
 ///
@@ -77,13 +133,7 @@ pub const YUPDATES_DEFAULT_API_URL: &str = "https://feeds.yupdates.com/api/v0/";
 /// `AsyncYupdatesClient` or use the functions in the `api` module directly.
 pub fn env_or_default_url() -> Result<String> {
     match env::var(YUPDATES_API_URL) {
-        Ok(s) => {
-            if s.ends_with('/') {
-                Ok(s)
-            } else {
-                Ok(format!("{}/", s))
-            }
-        }
+        Ok(s) => Ok(normalize_base_url(s)),
         Err(e) => match e {
             VarError::NotPresent => Ok(YUPDATES_DEFAULT_API_URL.to_string()),
             VarError::NotUnicode(_) => Err(Error {
@@ -93,18 +143,36 @@ pub fn env_or_default_url() -> Result<String> {
     }
 }
 
+/// The API URL with the trailing slash that the calls in the `api` module expect, added if it is
+/// missing. [env_or_default_url] does this for [YUPDATES_API_URL].
+pub fn normalize_base_url<S: Into<String>>(base_url: S) -> String {
+    let base_url = base_url.into();
+    if base_url.ends_with('/') {
+        base_url
+    } else {
+        format!("{}/", base_url)
+    }
+}
+
 /// Retrieve the API token from the environment.
 ///
 /// This is the default source; you can override by bypassing the default setup methods. You can
 /// instantiate your own `AsyncYupdatesClient` or use the functions in the `api` module directly.
+///
+/// If [YUPDATES_API_TOKEN] is not set, the token is read from the file named by
+/// [YUPDATES_API_TOKEN_FILE] (see [api_token_from_file]).
 pub fn api_token() -> Result<String> {
     match env::var(YUPDATES_API_TOKEN) {
-        Ok(s) => Ok(s),
+        Ok(s) => validate_token(&s),
         Err(e) => {
             let err = match e {
-                VarError::NotPresent => {
-                    format!("API token is missing, set {}", YUPDATES_API_TOKEN)
-                }
+                VarError::NotPresent => match env::var_os(YUPDATES_API_TOKEN_FILE) {
+                    Some(path) => return api_token_from_file(path),
+                    None => format!(
+                        "API token is missing, set {} or {}",
+                        YUPDATES_API_TOKEN, YUPDATES_API_TOKEN_FILE
+                    ),
+                },
                 VarError::NotUnicode(_) => {
                     format!("{} is not valid unicode", YUPDATES_API_TOKEN)
                 }
@@ -116,6 +184,51 @@ pub fn api_token() -> Result<String> {
     }
 }
 
+/// Read the API token from a file, for example a mounted secret. Surrounding whitespace is
+/// removed.
+pub fn api_token_from_file<P>(path: P) -> Result<String>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    match fs::read_to_string(path) {
+        Ok(s) => validate_token(&s),
+        Err(e) => Err(Error {
+            kind: Kind::Config(format!(
+                "could not read the API token from '{}': {}",
+                path.display(),
+                e
+            )),
+        }),
+    }
+}
+
+/// Trim surrounding whitespace (like the trailing newline from `$(cat token.txt)`) from an API
+/// token and sanity check what is left.
+///
+/// Returns a [Kind::Config] error if the token is empty or contains characters that can never be
+/// part of a token (anything other than visible ASCII characters).
+pub fn validate_token<S>(token: S) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    let trimmed = token.as_ref().trim();
+    if trimmed.is_empty() {
+        return Err(Error {
+            kind: Kind::Config("API token is empty".to_string()),
+        });
+    }
+    if let Some(c) = trimmed.chars().find(|c| !c.is_ascii_graphic()) {
+        return Err(Error {
+            kind: Kind::Config(format!(
+                "API token contains an unexpected character: {:?}",
+                c
+            )),
+        });
+    }
+    Ok(trimmed.to_string())
+}
+
 /// Accept many forms of item time, validate it, and return a normalized version.
 ///
 /// An item time is a unix ms from 0 to 9_999_999_999_999. It has an optional 5 digit suffix.
@@ -124,20 +237,29 @@ pub fn normalize_item_time<S>(item_time: S) -> Result<String>
 where
     S: AsRef<str>,
 {
-    let it = item_time.as_ref();
-    let parts = it.split('.').collect::<Vec<&str>>();
+    let (base_ms, slot) = parse_item_time(item_time.as_ref())?;
+    Ok(format!("{:0>13}.{:0>5}", base_ms, slot))
+}
+
+/// Validate an item time like [normalize_item_time] does and return its components: the unix ms
+/// and the suffix (the slot, 0 if there is none).
+///
+/// `parse_item_time("1661564013555.00003")` is `(1661564013555, 3)`.
+pub fn parse_item_time(item_time: &str) -> Result<(u64, u32)> {
+    let parts = item_time.split('.').collect::<Vec<&str>>();
     let (base_str, slot_str) = match parts.len() {
-        1 => (it, "0"),
+        1 => (item_time, "0"),
         2 => (parts[0], parts[1]),
         _ => {
             return Err(Error {
-                kind: Kind::Deserialization(format!("invalid item time: '{}'", it)),
+                kind: Kind::Deserialization(format!("invalid item time: '{}'", item_time)),
             });
         }
     };
     let base_ms = parse_bounded_int(base_str, "base ms", 9_999_999_999_999)?;
     let slot = parse_bounded_int(slot_str, "suffix", 99_999)?;
-    Ok(format!("{:0>13}.{:0>5}", base_ms, slot))
+    // The bound keeps the slot well within u32
+    Ok((base_ms, slot as u32))
 }
 
 /// This is [normalize_item_time] for when you are using integer timestamps.
@@ -145,6 +267,42 @@ pub fn normalize_item_time_ms(item_time_ms: u64) -> Result<String> {
     normalize_item_time(item_time_ms.to_string())
 }
 
+/// Check that a feed ID looks legal: [FEED_ID_LEN] alphanumeric characters, ignoring any
+/// surrounding whitespace. This is the same check that every call taking a feed ID makes, and
+/// that [models::FeedId] makes on construction.
+pub fn validate_feed_id(feed_id: &str) -> Result<()> {
+    validated_feed_id(feed_id).map(|_| ())
+}
+
+// Returns the trimmed feed ID if it looks legal
+pub(crate) fn validated_feed_id(feed_id: &str) -> Result<&str> {
+    let feed_id_str = feed_id.trim();
+    if feed_id_str.len() != FEED_ID_LEN {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "`feed_id` is expected to be {} characters ('{}')",
+                FEED_ID_LEN, feed_id
+            )),
+        });
+    }
+    if !feed_id_str.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "`feed_id` is expected to be alphanumeric ('{}')",
+                feed_id
+            )),
+        });
+    }
+    Ok(feed_id_str)
+}
+
+// An absolute http(s) URL with a host
+pub(crate) fn is_http_url(url: &str) -> bool {
+    reqwest::Url::parse(url).is_ok_and(|url| {
+        (url.scheme() == "http" || url.scheme() == "https") && url.host_str().is_some()
+    })
+}
+
 fn parse_bounded_int(int_str: &str, name: &str, upper_bound: u64) -> Result<u64> {
     let parsed = int_str.parse::<u64>().map_err(|_| Error {
         kind: Kind::IllegalParameter(format!("invalid u64: '{}'", int_str)),
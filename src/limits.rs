@@ -0,0 +1,72 @@
+//! Client-side limits on input items
+//!
+//! The calls that add items ([crate::api::YupdatesV0::new_items], `new_items_all` and its
+//! variants) check every item against these before anything is sent, so an oversized item fails
+//! fast with a [Kind::IllegalParameter] that names the item index, the field, and its actual size,
+//! rather than with an HTTP 400 for the whole batch. Sizes are in bytes of UTF-8.
+//!
+//! To let the server be the judge, send with [crate::api::YupdatesV0::new_items_unchecked].
+use crate::errors::{Error, Kind, Result};
+use crate::models::{AssociatedFile, InputItem};
+
+/// The largest `title`, in bytes
+pub const MAX_TITLE_BYTES: usize = 1024;
+
+/// The largest `content`, in bytes
+pub const MAX_CONTENT_BYTES: usize = 1024 * 1024;
+
+/// The most associated files one item can have
+pub const MAX_FILES_PER_ITEM: usize = 10;
+
+/// Check the items against the limits in this module. The error lists every problem.
+pub fn validate_item_sizes(items: &[InputItem]) -> Result<()> {
+    let mut problems = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        size_problems(index, item, &mut problems);
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(Error {
+            kind: Kind::IllegalParameter(problems.join("; ")),
+        })
+    }
+}
+
+pub(crate) fn size_problems(index: usize, item: &InputItem, problems: &mut Vec<String>) {
+    field_size_problems(
+        &format!("item {}", index),
+        Some(&item.title),
+        Some(&item.content),
+        item.associated_files.as_deref(),
+        problems,
+    );
+}
+
+// Like size_problems for the fields that are set, for example those of an ItemPatch. Each
+// problem starts with `subject`.
+pub(crate) fn field_size_problems(
+    subject: &str,
+    title: Option<&str>,
+    content: Option<&str>,
+    files: Option<&[AssociatedFile]>,
+    problems: &mut Vec<String>,
+) {
+    let mut too_large = |field: &str, size: usize, limit: usize, unit: &str| {
+        if size > limit {
+            problems.push(format!(
+                "{}: `{}` is {} {}, more than the limit of {}",
+                subject, field, size, unit, limit
+            ));
+        }
+    };
+    if let Some(title) = title {
+        too_large("title", title.len(), MAX_TITLE_BYTES, "bytes");
+    }
+    if let Some(content) = content {
+        too_large("content", content.len(), MAX_CONTENT_BYTES, "bytes");
+    }
+    if let Some(files) = files {
+        too_large("associated_files", files.len(), MAX_FILES_PER_ITEM, "files");
+    }
+}
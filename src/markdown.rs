@@ -0,0 +1,206 @@
+//! Input items from Markdown, enabled with the `markdown` feature
+//!
+//! [InputItem::from_markdown] renders the Markdown as HTML for the item's `content`. The HTML is
+//! safe to show in a feed reader: raw HTML in the Markdown is escaped so that it shows as text,
+//! `<script>` and `<style>` blocks are left out, and links and images only keep `http`, `https`,
+//! and `mailto` URLs (or relative ones).
+//!
+//! ```rust
+//! use yupdates::markdown::MarkdownOptions;
+//! use yupdates::models::InputItem;
+//!
+//! let item = InputItem::from_markdown(
+//!     "Release notes",
+//!     "Fixed *three* bugs, see [the changelog](https://www.example.com/changelog).",
+//!     "https://www.example.com/release",
+//! );
+//! assert_eq!(
+//!     item.content,
+//!     "<p>Fixed <em>three</em> bugs, see <a href=\"https://www.example.com/changelog\">the \
+//!      changelog</a>.</p>\n"
+//! );
+//! ```
+use crate::models::{guess_mime_type, AssociatedFile, InputItem};
+use pulldown_cmark::html::push_html;
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, TagEnd};
+use serde_json::Map;
+
+/// How Markdown is rendered, see [InputItem::from_markdown_with_options]
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownOptions {
+    /// Render GitHub-style tables
+    pub tables: bool,
+    /// Render footnotes (`[^1]`) and their definitions
+    pub footnotes: bool,
+    /// Also add the first image with an http(s) URL and a recognized file extension (png, jpg,
+    /// jpeg, gif, webp, svg, or avif) as an associated file. Its `length` is not known from the
+    /// Markdown, so it is 0: set it before sending the item, since
+    /// [crate::api::validate_associated_files] rejects a 0 length.
+    pub first_image_as_file: bool,
+}
+
+impl InputItem {
+    /// An item with `markdown` rendered as safe HTML for its `content`, using the default
+    /// [MarkdownOptions]
+    pub fn from_markdown<S, T>(title: S, markdown: &str, canonical_url: T) -> InputItem
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        Self::from_markdown_with_options(title, markdown, canonical_url, &Default::default())
+    }
+
+    /// Like [InputItem::from_markdown], with your own [MarkdownOptions]
+    pub fn from_markdown_with_options<S, T>(
+        title: S,
+        markdown: &str,
+        canonical_url: T,
+        options: &MarkdownOptions,
+    ) -> InputItem
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        let (content, first_image) = render(markdown, options);
+        InputItem {
+            title: title.into(),
+            content,
+            canonical_url: canonical_url.into(),
+            author: None,
+            associated_files: first_image.map(|file| vec![file]),
+            input_id: None,
+            item_time: None,
+            extra: Map::new(),
+        }
+    }
+}
+
+/// Render Markdown as safe HTML, like [InputItem::from_markdown] does for the content
+pub fn markdown_to_html(markdown: &str, options: &MarkdownOptions) -> String {
+    render(markdown, options).0
+}
+
+// The HTML, and the first image as a file if the options ask for it
+fn render(markdown: &str, options: &MarkdownOptions) -> (String, Option<AssociatedFile>) {
+    let mut parser_options = Options::empty();
+    parser_options.set(Options::ENABLE_TABLES, options.tables);
+    parser_options.set(Options::ENABLE_FOOTNOTES, options.footnotes);
+
+    let mut first_image = None;
+    let mut events = Vec::new();
+    let mut parser = Parser::new_ext(markdown, parser_options).peekable();
+    while let Some(event) = parser.next() {
+        match event {
+            Event::Start(Tag::HtmlBlock) => {
+                let closing_tag = match parser.peek() {
+                    Some(Event::Html(html)) => script_or_style_closing_tag(html),
+                    _ => None,
+                };
+                let Some(closing_tag) = closing_tag else {
+                    events.push(Event::Start(Tag::Paragraph));
+                    continue;
+                };
+                // Drop the element, the block can go on after it
+                let mut rest = Vec::new();
+                let mut dropping = true;
+                for event in parser.by_ref() {
+                    match event {
+                        Event::End(TagEnd::HtmlBlock) => break,
+                        Event::Html(html) if dropping => {
+                            dropping = !html.to_ascii_lowercase().contains(closing_tag);
+                        }
+                        Event::Html(html) if !html.trim().is_empty() => {
+                            rest.push(Event::Text(html))
+                        }
+                        _ => {}
+                    }
+                }
+                if !rest.is_empty() {
+                    events.push(Event::Start(Tag::Paragraph));
+                    events.append(&mut rest);
+                    events.push(Event::End(TagEnd::Paragraph));
+                }
+            }
+            Event::End(TagEnd::HtmlBlock) => events.push(Event::End(TagEnd::Paragraph)),
+            // Text is escaped when it is rendered
+            Event::Html(html) | Event::InlineHtml(html) => events.push(Event::Text(html)),
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => events.push(Event::Start(Tag::Link {
+                link_type,
+                dest_url: safe_url(dest_url),
+                title,
+                id,
+            })),
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                let dest_url = safe_url(dest_url);
+                if options.first_image_as_file && first_image.is_none() {
+                    first_image = image_file(&dest_url);
+                }
+                events.push(Event::Start(Tag::Image {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }));
+            }
+            event => events.push(event),
+        }
+    }
+    let mut html = String::new();
+    push_html(&mut html, events.into_iter());
+    (html, first_image)
+}
+
+// An HTML block that starts with a script or style element holds the whole element, and maybe
+// more. Returns the start of the closing tag.
+fn script_or_style_closing_tag(html: &str) -> Option<&'static str> {
+    let html = html.trim_start().to_ascii_lowercase();
+    [("<script", "</script"), ("<style", "</style")]
+        .into_iter()
+        .find(|(tag, _)| {
+            html.strip_prefix(tag)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['>', ' ', '\t', '\n']))
+        })
+        .map(|(_, closing_tag)| closing_tag)
+}
+
+// Browsers ignore whitespace and control characters in a scheme, so they are ignored here too
+fn safe_url(url: CowStr) -> CowStr {
+    let cleaned: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .collect();
+    let scheme = cleaned
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .filter(|scheme| !scheme.contains(['/', '?', '#']));
+    match scheme {
+        Some(scheme)
+            if !["http", "https", "mailto"].contains(&scheme.to_ascii_lowercase().as_str()) =>
+        {
+            CowStr::Borrowed("")
+        }
+        _ => url,
+    }
+}
+
+fn image_file(url: &str) -> Option<AssociatedFile> {
+    if !crate::is_http_url(url) {
+        return None;
+    }
+    let type_str = guess_mime_type(url).filter(|mime| mime.starts_with("image/"))?;
+    Some(AssociatedFile {
+        url: url.to_string(),
+        length: 0,
+        type_str: type_str.to_string(),
+    })
+}
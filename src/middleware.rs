@@ -0,0 +1,78 @@
+//! Sending the API calls through `reqwest-middleware`, enabled with the `reqwest-middleware`
+//! feature
+//!
+//! If your HTTP clients share a middleware stack (tracing, caching, retries), create the SDK
+//! client with [crate::clients::new_async_client_with_middleware], or call
+//! [crate::clients::AsyncYupdatesClient::with_middleware], and every API call the client makes is
+//! sent through your [ClientWithMiddleware]. The `reqwest::Client` of the SDK client still builds
+//! the requests, so its default headers and timeout do not apply; configure those on the
+//! middleware client instead.
+//!
+//! The `_with_args` functions in [crate::api] take a plain `reqwest::Client`. To call one of them
+//! through middleware, run it inside [with_middleware]:
+//!
+//! ```rust,no_run
+//! use reqwest_middleware::ClientBuilder;
+//! use yupdates::api::read_items_with_args;
+//! use yupdates::middleware::with_middleware;
+//!
+//! # async fn read() -> yupdates::errors::Result<()> {
+//! let middleware = ClientBuilder::new(reqwest::Client::new()).build();
+//! let items = with_middleware(
+//!     &middleware,
+//!     read_items_with_args(
+//!         "02fb24a4478462a4491067224b66d9a8b2338ddca2737",
+//!         None,
+//!         &reqwest::Client::new(),
+//!         "https://feeds.yupdates.com/api/v0/",
+//!         "the-token",
+//!     ),
+//! )
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requests that are not API calls, like the HEAD requests of [crate::api::verify_files] or
+//! fetching a feed to import, are sent by the plain `reqwest::Client`.
+use crate::errors::{Error, Kind, Result};
+use reqwest_middleware::ClientWithMiddleware;
+use std::future::Future;
+
+tokio::task_local! {
+    // The middleware client of the call that is running
+    static MIDDLEWARE: ClientWithMiddleware;
+}
+
+/// Run `fut`, a call like one of the `_with_args` functions in [crate::api], with the API requests
+/// it makes sent through `client`
+pub async fn with_middleware<F: Future>(client: &ClientWithMiddleware, fut: F) -> F::Output {
+    MIDDLEWARE.scope(client.clone(), fut).await
+}
+
+// Run `fut` through `client`, if there is one
+pub(crate) async fn through<F: Future>(client: Option<&ClientWithMiddleware>, fut: F) -> F::Output {
+    match client {
+        None => fut.await,
+        Some(client) => with_middleware(client, fut).await,
+    }
+}
+
+// Send the request, through the middleware of the running call if there is one
+pub(crate) async fn send(request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+    match MIDDLEWARE.try_with(Clone::clone) {
+        Ok(client) => Ok(client.execute(request.build()?).await?),
+        Err(_) => Ok(request.send().await?),
+    }
+}
+
+impl From<reqwest_middleware::Error> for Error {
+    fn from(e: reqwest_middleware::Error) -> Self {
+        match e {
+            reqwest_middleware::Error::Reqwest(e) => e.into(),
+            reqwest_middleware::Error::Middleware(e) => Error {
+                kind: Kind::Middleware(format!("{:#}", e)),
+            },
+        }
+    }
+}
@@ -1,31 +1,943 @@
 //! Clean structs for API objects, marshalled to and from JSON via serde
+use crate::errors::{Error, Kind, Result};
+use crate::limits::MAX_FILES_PER_ITEM;
+use crate::time::{human, item_ms};
+use crate::{is_http_url, validated_feed_id};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::str::FromStr;
 
-#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Deserialize, Serialize)]
 pub struct AssociatedFile {
     pub url: String,
     pub length: u64,
+    /// The MIME type, for example "audio/mpeg". This is the `type` field on the wire (`type` is a
+    /// Rust keyword); `type_str` is also accepted when deserializing.
+    #[serde(rename = "type", alias = "type_str")]
     pub type_str: String,
 }
 
+impl AssociatedFile {
+    /// A file with its MIME type guessed from the extension of the URL's path, ignoring case.
+    /// Common audio, video, image, and document extensions are known, anything else is
+    /// `application/octet-stream`. Use [AssociatedFile::with_type] to set the type yourself.
+    ///
+    /// The URL must be an absolute http(s) URL, or this returns a [Kind::IllegalParameter] error.
+    pub fn new<S: Into<String>>(url: S, length: u64) -> Result<AssociatedFile> {
+        let url = url.into();
+        if !is_http_url(&url) {
+            return Err(Error {
+                kind: Kind::IllegalParameter(format!(
+                    "an associated file `url` must be an absolute http(s) URL ('{}')",
+                    url
+                )),
+            });
+        }
+        let type_str = guess_mime_type(&url).unwrap_or("application/octet-stream");
+        Ok(AssociatedFile {
+            url,
+            length,
+            type_str: type_str.to_string(),
+        })
+    }
+
+    /// The same file with the given MIME type instead
+    pub fn with_type<S: Into<String>>(mut self, type_str: S) -> AssociatedFile {
+        self.type_str = type_str.into();
+        self
+    }
+
+    /// Classify the file by its MIME type, for example to pick a renderer. See [MediaKind].
+    pub fn media_kind(&self) -> MediaKind {
+        MediaKind::from_mime(&self.type_str)
+    }
+}
+
+// The MIME type for the extension of an http(s) URL's path, if it is a known one
+pub(crate) fn guess_mime_type(url: &str) -> Option<&'static str> {
+    let url = reqwest::Url::parse(url).ok()?;
+    let file_name = url.path_segments()?.next_back()?;
+    let extension = file_name.rsplit_once('.')?.1.to_ascii_lowercase();
+    let mime = match extension.as_str() {
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "aac" => "audio/aac",
+        "ogg" | "oga" => "audio/ogg",
+        "opus" => "audio/opus",
+        "wav" => "audio/wav",
+        "flac" => "audio/flac",
+        "mp4" => "video/mp4",
+        "m4v" => "video/x-m4v",
+        "mov" => "video/quicktime",
+        "webm" => "video/webm",
+        "mkv" => "video/x-matroska",
+        "ogv" => "video/ogg",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "avif" => "image/avif",
+        "pdf" => "application/pdf",
+        "epub" => "application/epub+zip",
+        "zip" => "application/zip",
+        "txt" => "text/plain",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// The broad kind of media an [AssociatedFile] holds, based on its MIME type.
+///
+/// `audio/*`, `video/*`, and `image/*` are classified by their prefix. `text/*` and common
+/// document formats like PDF, EPUB, and office documents are [MediaKind::Document]. Anything
+/// else, including a malformed type, is [MediaKind::Other] with the type as it was given.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum MediaKind {
+    Audio,
+    Video,
+    Image,
+    Document,
+    Other(String),
+}
+
+// The `application/*` subtypes that are documents
+const DOCUMENT_SUBTYPES: &[&str] = &[
+    "epub+zip",
+    "msword",
+    "pdf",
+    "rtf",
+    "vnd.ms-excel",
+    "vnd.ms-powerpoint",
+];
+
+// The `application/*` subtype prefixes that are documents (OOXML and ODF formats)
+const DOCUMENT_SUBTYPE_PREFIXES: &[&str] = &[
+    "vnd.oasis.opendocument.",
+    "vnd.openxmlformats-officedocument.",
+];
+
+impl MediaKind {
+    /// Classify a MIME type. Case and any parameters (like `; charset=utf-8`) are ignored.
+    pub fn from_mime(mime: &str) -> MediaKind {
+        let essence = mime.split(';').next().unwrap_or_default().trim();
+        let essence = essence.to_ascii_lowercase();
+        match essence.split_once('/') {
+            Some((_, "")) | None => MediaKind::Other(mime.to_string()),
+            Some(("audio", _)) => MediaKind::Audio,
+            Some(("video", _)) => MediaKind::Video,
+            Some(("image", _)) => MediaKind::Image,
+            Some(("text", _)) => MediaKind::Document,
+            Some(("application", subtype))
+                if DOCUMENT_SUBTYPES.contains(&subtype)
+                    || DOCUMENT_SUBTYPE_PREFIXES
+                        .iter()
+                        .any(|prefix| subtype.starts_with(prefix)) =>
+            {
+                MediaKind::Document
+            }
+            Some(_) => MediaKind::Other(mime.to_string()),
+        }
+    }
+}
+
+/// An item read from a feed.
+///
+/// `item_id` and `item_time` are always present. When only some fields are requested (see
+/// `fields` in [crate::api::ReadOptions]), the others are empty, zero, false, or `None`.
+///
+/// The IDs are [FeedId], [ItemId], and [InputId] so they cannot be passed in the wrong order.
+/// They are plain strings in JSON.
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
 pub struct FeedItem {
-    pub feed_id: String,
-    pub item_id: String,
-    pub input_id: String,
+    /// `None` if it was not requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feed_id: Option<FeedId>,
+    pub item_id: ItemId,
+    #[serde(default)]
+    pub input_id: InputId,
+    #[serde(default)]
     pub title: String,
     pub content: Option<String>,
+    #[serde(default)]
     pub canonical_url: String,
+    /// The item's author or byline, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
     pub item_time: String,
+    #[serde(default)]
     pub item_time_ms: u64,
+    #[serde(default)]
     pub deleted: bool,
     pub associated_files: Option<Vec<AssociatedFile>>,
+    /// Any fields the API returned that this SDK does not model yet. They are written back out
+    /// when the item is serialized.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The [FeedItem] field names that can be requested from the API
+pub const FEED_ITEM_FIELDS: &[&str] = &[
+    "associated_files",
+    "author",
+    "canonical_url",
+    "content",
+    "deleted",
+    "feed_id",
+    "input_id",
+    "item_id",
+    "item_time",
+    "item_time_ms",
+    "title",
+];
+
+/// The [FeedItem] fields that are returned even if they are not requested
+pub const FEED_ITEM_REQUIRED_FIELDS: &[&str] = &["item_id", "item_time"];
+
+impl FeedItem {
+    /// The start of the content for a preview, at most `max_chars` characters followed by "…"
+    /// if it was cut. `None` if the content was not read (see
+    /// [crate::api::ReadOptions::include_item_content]).
+    ///
+    /// The cut is never inside a character, between a character and the combining marks or
+    /// joiners that belong to it, or inside an HTML tag or entity. It is made earlier instead.
+    /// Tags that were opened before the cut are not closed.
+    pub fn content_preview(&self, max_chars: usize) -> Option<String> {
+        let content = self.content.as_deref()?;
+        let Some((mut cut, _)) = content.char_indices().nth(max_chars) else {
+            return Some(content.to_string());
+        };
+        while let Some(previous) = content[..cut].chars().next_back() {
+            let next = content[cut..].chars().next();
+            if !(next.is_some_and(is_extending) || previous == ZERO_WIDTH_JOINER) {
+                break;
+            }
+            cut -= previous.len_utf8();
+        }
+        let head = &content[..cut];
+        if let Some(open) = head.rfind('<').filter(|&open| !head[open..].contains('>')) {
+            cut = open;
+        } else if let Some(amp) = head.rfind('&') {
+            // Only if the cut is inside the entity
+            if entity_len(&content[amp..]).is_some_and(|len| amp + len > cut) {
+                cut = amp;
+            }
+        }
+        Some(format!("{}…", &content[..cut]))
+    }
+
+    /// An [InputItem] with this item's title, content, canonical URL, author, and associated
+    /// files, for re-posting it or copying it to another feed. See `From<FeedItem> for InputItem`.
+    pub fn to_input_item(&self) -> InputItem {
+        self.clone().into()
+    }
+}
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+// Characters that attach to the one before them: combining marks, variation selectors, emoji
+// skin tone modifiers, and joiners
+fn is_extending(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FE20}'..='\u{FE2F}'
+        | '\u{1F3FB}'..='\u{1F3FF}'
+        | '\u{E0100}'..='\u{E01EF}'
+        | ZERO_WIDTH_JOINER)
+}
+
+// The length of the entity like "&amp;" or "&#8212;" that `text` starts with, if it does
+fn entity_len(text: &str) -> Option<usize> {
+    let end = text[1..].find(';')? + 1;
+    let name = &text[1..end];
+    let legal = !name.is_empty()
+        && name.len() <= 10
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '#');
+    legal.then_some(end + 1)
+}
+
+/// Items are ordered by item time: the base milliseconds, then the 5 digit suffix. Items with
+/// the same item time are ordered by `item_id`. Copies of the same item that differ in other
+/// fields are ordered by those fields, so that the order agrees with `==`.
+impl Ord for FeedItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        item_time_key(&self.item_time)
+            .cmp(&item_time_key(&other.item_time))
+            .then_with(|| self.item_id.cmp(&other.item_id))
+            .then_with(|| self.item_time.cmp(&other.item_time))
+            .then_with(|| self.feed_id.cmp(&other.feed_id))
+            .then_with(|| self.input_id.cmp(&other.input_id))
+            .then_with(|| self.title.cmp(&other.title))
+            .then_with(|| self.content.cmp(&other.content))
+            .then_with(|| self.canonical_url.cmp(&other.canonical_url))
+            .then_with(|| self.author.cmp(&other.author))
+            .then_with(|| self.item_time_ms.cmp(&other.item_time_ms))
+            .then_with(|| self.deleted.cmp(&other.deleted))
+            .then_with(|| self.associated_files.cmp(&other.associated_files))
+            .then_with(|| cmp_json_maps(&self.extra, &other.extra))
+    }
+}
+
+impl PartialOrd for FeedItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The base ms and suffix of an item time. An unparseable item time sorts first, the string
+// comparison in `cmp` still orders it.
+fn item_time_key(item_time: &str) -> (u64, u64) {
+    let (base, suffix) = item_time.split_once('.').unwrap_or((item_time, "0"));
+    (base.parse().unwrap_or(0), suffix.parse().unwrap_or(0))
+}
+
+// JSON values have no order of their own. This one agrees with their `==`, whichever order the
+// object keys are stored in.
+fn cmp_json(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) => 4,
+            Value::Object(_) => 5,
+        }
+    }
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => a.to_string().cmp(&b.to_string()),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| cmp_json(a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        (Value::Object(a), Value::Object(b)) => cmp_json_maps(a, b),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+fn cmp_json_maps(a: &Map<String, Value>, b: &Map<String, Value>) -> Ordering {
+    let mut a: Vec<(&String, &Value)> = a.iter().collect();
+    let mut b: Vec<(&String, &Value)> = b.iter().collect();
+    a.sort_by_key(|(key, _)| *key);
+    b.sort_by_key(|(key, _)| *key);
+    a.iter()
+        .zip(&b)
+        .map(|((a_key, a_value), (b_key, b_value))| {
+            a_key.cmp(b_key).then_with(|| cmp_json(a_value, b_value))
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+/// A one-line summary for logs and CLI output: the item time (UTC), title, and canonical URL,
+/// for example `2022-08-27 01:33:33 UTC  Hello, world  <https://www.example.com/hello>`. Deleted
+/// items end with ` (deleted)`, and items without a canonical URL leave it out. Line breaks in
+/// the title are shown as spaces.
+impl fmt::Display for FeedItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let title: String = self
+            .title
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        write!(f, "{}  {}", human(item_ms(self)), title)?;
+        if !self.canonical_url.is_empty() {
+            write!(f, "  <{}>", self.canonical_url)?;
+        }
+        if self.deleted {
+            write!(f, " (deleted)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Copies `title`, `content` (an empty string if it is `None`), `canonical_url`, `author`, and
+/// `associated_files`.
+///
+/// The fields the server assigns, like `feed_id`, `item_id`, and `input_id`, are necessarily
+/// dropped: a new input item gets new ones when it is added, and a kept `input_id` would make the
+/// feed skip the item as a repeat (see [InputItem::input_id]). So are any `extra` fields, which
+/// the API returned rather than accepts. The item time is not copied either, so the new item gets
+/// the time it is added; set [InputItem::item_time] to keep it. Read with `include_item_content`
+/// set or the content and files will be missing.
+impl From<FeedItem> for InputItem {
+    fn from(item: FeedItem) -> Self {
+        InputItem {
+            title: item.title,
+            content: item.content.unwrap_or_default(),
+            canonical_url: item.canonical_url,
+            author: item.author,
+            associated_files: item.associated_files,
+            input_id: None,
+            item_time: None,
+            extra: Map::new(),
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
 pub struct InputItem {
     pub title: String,
     pub content: String,
+    /// The item's URL elsewhere. Empty for items that have none, like status updates, and then it
+    /// is left out of the request (see [InputItem::without_url]).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub canonical_url: String,
+    /// The item's author or byline. Left out of the request when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
     pub associated_files: Option<Vec<AssociatedFile>>,
+    /// Your own ID for the item, returned as [FeedItem::input_id]. An item with an input ID that
+    /// is already in the feed is not added again, so a submission that is retried or repeated
+    /// does not create duplicates. At most [crate::api::MAX_INPUT_ID_LEN] ASCII letters, digits,
+    /// `-`, `_`, and `.`. When `None` (the default), the server assigns one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_id: Option<String>,
+    /// The item time to publish the item at, for example to keep the original publication time
+    /// when importing history. Anything [crate::normalize_item_time] accepts, it is normalized
+    /// before it is sent, and anything else (like a time past year 2286) is rejected with a
+    /// [crate::errors::Kind::IllegalParameter] before any call is made. When `None` (the default),
+    /// the item gets the time it was added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_time: Option<String>,
+    /// Any other fields to send, for API fields that this SDK does not model yet. These are
+    /// flattened into the item when it is serialized.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl InputItem {
+    /// Read input items from a file that holds a JSON array of them, in the format they are sent
+    /// in. Only `title` and `content` are required, the other fields (like `associated_files`)
+    /// can be left out.
+    ///
+    /// A file that is not a JSON array of input items is a [Kind::Deserialization] error that
+    /// names the file. The items are not checked: see [crate::api::validate_items], or let
+    /// [crate::api::YupdatesV0::new_items_all] check them before anything is sent.
+    ///
+    /// ```rust,no_run
+    /// use yupdates::clients::new_async_client;
+    /// use yupdates::errors::Error;
+    /// use yupdates::models::InputItem;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let items = InputItem::from_json_file("items.json")?;
+    ///     let feed_id = new_async_client()?.new_items_all(&items, 5).await?;
+    ///     println!("Added {} items to feed {}", items.len(), feed_id);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Vec<InputItem>> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        serde_json::from_reader(BufReader::new(file)).map_err(|e| Error {
+            kind: Kind::Deserialization(format!("{}: {}", path.display(), e)),
+        })
+    }
+
+    /// Start building an input item. See [InputItemBuilder].
+    pub fn builder() -> InputItemBuilder {
+        InputItemBuilder {
+            item: InputItem {
+                title: String::new(),
+                content: String::new(),
+                canonical_url: String::new(),
+                author: None,
+                associated_files: None,
+                input_id: None,
+                item_time: None,
+                extra: Map::new(),
+            },
+        }
+    }
+
+    /// Add an associated file after any the item already has. Fails with a
+    /// [Kind::IllegalParameter] error if the item would have more than [MAX_FILES_PER_ITEM].
+    pub fn attach_file(self, file: AssociatedFile) -> Result<InputItem> {
+        self.attach_files([file])
+    }
+
+    /// Add associated files after any the item already has, see [InputItem::attach_file]
+    pub fn attach_files<I>(mut self, files: I) -> Result<InputItem>
+    where
+        I: IntoIterator<Item = AssociatedFile>,
+    {
+        self.files_mut().extend(files);
+        files_limit_problem(self.files().len())?;
+        Ok(self)
+    }
+
+    /// The associated files, empty if there are none
+    pub fn files(&self) -> &[AssociatedFile] {
+        self.associated_files.as_deref().unwrap_or_default()
+    }
+
+    /// The associated files, to change in place. The limit of [MAX_FILES_PER_ITEM] is checked
+    /// when the item is sent, not here.
+    pub fn files_mut(&mut self) -> &mut Vec<AssociatedFile> {
+        self.associated_files.get_or_insert_with(Vec::new)
+    }
+
+    /// An item without a canonical URL, for items that have no page of their own, like status
+    /// updates and alerts. Feed readers then show no link instead of a broken one.
+    pub fn without_url<S, T>(title: S, content: T) -> InputItem
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        InputItem {
+            title: title.into(),
+            content: content.into(),
+            canonical_url: String::new(),
+            author: None,
+            associated_files: None,
+            input_id: None,
+            item_time: None,
+            extra: Map::new(),
+        }
+    }
+}
+
+fn files_limit_problem(files: usize) -> Result<()> {
+    if files > MAX_FILES_PER_ITEM {
+        return Err(Error {
+            kind: Kind::IllegalParameter(format!(
+                "`associated_files` is {} files, more than the limit of {}",
+                files, MAX_FILES_PER_ITEM
+            )),
+        });
+    }
+    Ok(())
+}
+
+/// Builds an [InputItem] one field at a time, see [InputItem::builder].
+///
+/// Fields that are not set are empty, and an item without files has `associated_files: None`.
+/// [InputItemBuilder::build] checks that the title is not blank, that the canonical URL, if set,
+/// is an absolute http(s) URL, and that there are at most [MAX_FILES_PER_ITEM] files.
+#[derive(Clone, Debug)]
+pub struct InputItemBuilder {
+    item: InputItem,
+}
+
+impl InputItemBuilder {
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.item.title = title.into();
+        self
+    }
+
+    pub fn content<S: Into<String>>(mut self, content: S) -> Self {
+        self.item.content = content.into();
+        self
+    }
+
+    pub fn canonical_url<S: Into<String>>(mut self, canonical_url: S) -> Self {
+        self.item.canonical_url = canonical_url.into();
+        self
+    }
+
+    pub fn author<S: Into<String>>(mut self, author: S) -> Self {
+        self.item.author = Some(author.into());
+        self
+    }
+
+    /// See [InputItem::input_id]
+    pub fn input_id<S: Into<String>>(mut self, input_id: S) -> Self {
+        self.item.input_id = Some(input_id.into());
+        self
+    }
+
+    /// See [InputItem::item_time]
+    pub fn item_time<S: Into<String>>(mut self, item_time: S) -> Self {
+        self.item.item_time = Some(item_time.into());
+        self
+    }
+
+    /// Add an associated file after any that were already added
+    pub fn file(mut self, file: AssociatedFile) -> Self {
+        self.item
+            .associated_files
+            .get_or_insert_with(Vec::new)
+            .push(file);
+        self
+    }
+
+    /// Add associated files after any that were already added
+    pub fn files<I>(self, files: I) -> Self
+    where
+        I: IntoIterator<Item = AssociatedFile>,
+    {
+        files
+            .into_iter()
+            .fold(self, |builder, file| builder.file(file))
+    }
+
+    /// Set a field that this SDK does not model yet, see [InputItem::extra]
+    pub fn extra<S: Into<String>>(mut self, key: S, value: Value) -> Self {
+        self.item.extra.insert(key.into(), value);
+        self
+    }
+
+    pub fn build(self) -> Result<InputItem> {
+        if self.item.title.trim().is_empty() {
+            return Err(Error {
+                kind: Kind::IllegalParameter("`title` must not be blank".to_string()),
+            });
+        }
+        if !self.item.canonical_url.is_empty() && !is_http_url(&self.item.canonical_url) {
+            return Err(Error {
+                kind: Kind::IllegalParameter(format!(
+                    "`canonical_url` must be an absolute http(s) URL ('{}')",
+                    self.item.canonical_url
+                )),
+            });
+        }
+        files_limit_problem(self.item.files().len())?;
+        Ok(self.item)
+    }
+}
+
+/// The changes to make to an existing item, see [crate::api::YupdatesV0::update_item].
+///
+/// Only the fields that are `Some` are sent and changed, the others are left as they are. Set
+/// `canonical_url` to an empty string to remove the item's URL. The feed ID, item ID, and input ID
+/// of an item cannot be changed.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ItemPatch {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Replaces all of the item's files. `Some` with an empty list removes them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub associated_files: Option<Vec<AssociatedFile>>,
+    /// Anything [crate::normalize_item_time] accepts, it is normalized before it is sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_time: Option<String>,
+    /// Any other fields to change, for API fields that this SDK does not model yet
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ItemPatch {
+    /// True if the patch would not change anything
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.content.is_none()
+            && self.canonical_url.is_none()
+            && self.author.is_none()
+            && self.associated_files.is_none()
+            && self.item_time.is_none()
+            && self.extra.is_empty()
+    }
+}
+
+/// Replace an item with the given one: every field is sent, so an empty URL or a missing list of
+/// files removes them. The author and item time are only changed if the input item has them, and
+/// the input ID is ignored.
+impl From<&InputItem> for ItemPatch {
+    fn from(item: &InputItem) -> Self {
+        ItemPatch {
+            title: Some(item.title.clone()),
+            content: Some(item.content.clone()),
+            canonical_url: Some(item.canonical_url.clone()),
+            author: item.author.clone(),
+            associated_files: Some(item.associated_files.clone().unwrap_or_default()),
+            item_time: item.item_time.clone(),
+            extra: item.extra.clone(),
+        }
+    }
+}
+
+/// A feed in the account, see [crate::api::YupdatesV0::list_feeds].
+///
+/// Only `feed_id` is always present, the other fields are empty or `None` if the API did not
+/// return them.
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct FeedInfo {
+    pub feed_id: String,
+    #[serde(default)]
+    pub title: String,
+    /// When the feed was created, in unix epoch milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time_ms: Option<u64>,
+    /// How many items the feed has
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_count: Option<u64>,
+    /// Any fields the API returned that this SDK does not model yet
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl FeedInfo {
+    /// The `feed_id` as a validated [FeedId]
+    pub fn typed_feed_id(&self) -> Result<FeedId> {
+        FeedId::new(&self.feed_id)
+    }
+}
+
+/// A validated feed ID.
+///
+/// The API functions accept anything that is `AsRef<str>`, so you can pass a `&FeedId` wherever
+/// a feed ID string is expected. Validating up front catches mistakes before any call is made.
+/// It is a plain string in JSON, and is validated when it is deserialized.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FeedId(String);
+
+impl FeedId {
+    /// Trim and validate a feed ID: it must be [crate::FEED_ID_LEN] alphanumeric characters.
+    pub fn new<S>(feed_id: S) -> Result<FeedId>
+    where
+        S: AsRef<str>,
+    {
+        Ok(FeedId(validated_feed_id(feed_id.as_ref())?.to_string()))
+    }
+
+    /// Extract and validate the feed ID from a feed URL, like the ones you can copy from the
+    /// application: `https://feeds.yupdates.com/rss/{feed_id}/` (or `/atom/` or `/json/`).
+    ///
+    /// The query string and any trailing slash are ignored.
+    pub fn from_url<S>(url: S) -> Result<FeedId>
+    where
+        S: AsRef<str>,
+    {
+        let url = url.as_ref().trim();
+        let parsed = reqwest::Url::parse(url).map_err(|e| Error {
+            kind: Kind::IllegalParameter(format!("invalid feed URL '{}': {}", url, e)),
+        })?;
+        let segments = parsed
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect::<Vec<&str>>())
+            .unwrap_or_default();
+        let candidate = segments
+            .windows(2)
+            .find(|pair| FEED_URL_KINDS.contains(&pair[0]))
+            .map(|pair| pair[1]);
+        match candidate {
+            Some(segment) => FeedId::new(segment).map_err(|e| Error {
+                kind: Kind::IllegalParameter(format!(
+                    "examined '{}' in the path of feed URL '{}': {}",
+                    segment, url, e
+                )),
+            }),
+            None => Err(Error {
+                kind: Kind::IllegalParameter(format!(
+                    "expected the path of feed URL '{}' to contain /{}/ followed by a feed ID, \
+                     examined '{}'",
+                    url,
+                    FEED_URL_KINDS.join("/, /"),
+                    parsed.path()
+                )),
+            }),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+// The path segments that precede the feed ID in a feed URL
+const FEED_URL_KINDS: &[&str] = &["rss", "atom", "json"];
+
+impl FromStr for FeedId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        FeedId::new(s)
+    }
+}
+
+impl TryFrom<&str> for FeedId {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        FeedId::new(s)
+    }
+}
+
+impl TryFrom<String> for FeedId {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        FeedId::new(s)
+    }
+}
+
+impl fmt::Display for FeedId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for FeedId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<FeedId> for String {
+    fn from(feed_id: FeedId) -> Self {
+        feed_id.0
+    }
+}
+
+impl PartialEq<str> for FeedId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for FeedId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// The ID the API gives an item, see [FeedItem::item_id].
+///
+/// Holding IDs as [FeedId], [ItemId], and [InputId] instead of strings keeps them from being
+/// mixed up in your own code. Like [FeedId], it can be passed as `AsRef<str>`, and it is a plain
+/// string in JSON. It is not validated, the API decides what an item ID looks like.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ItemId(String);
+
+impl ItemId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for ItemId {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Infallible> {
+        Ok(ItemId::from(s))
+    }
+}
+
+impl From<String> for ItemId {
+    fn from(item_id: String) -> Self {
+        ItemId(item_id)
+    }
+}
+
+impl From<&str> for ItemId {
+    fn from(item_id: &str) -> Self {
+        ItemId(item_id.to_string())
+    }
+}
+
+impl fmt::Display for ItemId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for ItemId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<ItemId> for String {
+    fn from(item_id: ItemId) -> Self {
+        item_id.0
+    }
+}
+
+impl PartialEq<str> for ItemId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ItemId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// The input ID of an item, yours or the one the API generated, see [FeedItem::input_id]
+/// and [InputItem::input_id]. Like [ItemId], it is a plain string in JSON and is not validated
+/// (see [crate::api::validate_items] for the rules the API applies).
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct InputId(String);
+
+impl InputId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for InputId {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Infallible> {
+        Ok(InputId::from(s))
+    }
+}
+
+impl From<String> for InputId {
+    fn from(input_id: String) -> Self {
+        InputId(input_id)
+    }
+}
+
+impl From<&str> for InputId {
+    fn from(input_id: &str) -> Self {
+        InputId(input_id.to_string())
+    }
+}
+
+impl fmt::Display for InputId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for InputId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<InputId> for String {
+    fn from(input_id: InputId) -> Self {
+        input_id.0
+    }
+}
+
+impl PartialEq<str> for InputId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for InputId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
 }
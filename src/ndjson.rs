@@ -0,0 +1,81 @@
+//! Newline-delimited JSON (NDJSON) for archiving feed items
+//!
+//! Each line is one [FeedItem] as a JSON object, in the same format that the API uses. To write
+//! a whole feed without holding it in memory, see [crate::api::YupdatesV0::dump_feed_ndjson].
+//!
+//! Input items can be read from NDJSON too (the same format is also called JSON Lines), see
+//! [read_input_items] and [crate::api::YupdatesV0::new_items_from_ndjson].
+use crate::errors::{Error, Kind, Result};
+use crate::models::{FeedItem, InputItem};
+use std::io::{BufRead, Write};
+
+/// How many items [export_ndjson] writes between flushes
+pub const NDJSON_FLUSH_INTERVAL: usize = 100;
+
+/// Write the items as NDJSON, one item per line, and return how many were written.
+///
+/// The writer is flushed every [NDJSON_FLUSH_INTERVAL] items and at the end. Pass a
+/// [std::io::BufWriter] if the writer is unbuffered, like a [std::fs::File].
+pub fn export_ndjson<W>(items: &[FeedItem], mut w: W) -> Result<usize>
+where
+    W: Write,
+{
+    for (idx, item) in items.iter().enumerate() {
+        write_ndjson_line(&mut w, item)?;
+        if (idx + 1) % NDJSON_FLUSH_INTERVAL == 0 {
+            w.flush()?;
+        }
+    }
+    w.flush()?;
+    Ok(items.len())
+}
+
+/// Read NDJSON written by [export_ndjson] back into items. Blank lines are skipped.
+///
+/// A line that is not a valid item is a [Kind::Deserialization] error that gives its line
+/// number, counting from 1.
+pub fn import_ndjson<R>(r: R) -> Result<Vec<FeedItem>>
+where
+    R: BufRead,
+{
+    let mut items = Vec::new();
+    for (idx, line) in r.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let item = serde_json::from_str(&line).map_err(|e| Error {
+            kind: Kind::Deserialization(format!("NDJSON line {}: {}", idx + 1, e)),
+        })?;
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// Read input items from NDJSON, one [InputItem] per line, as the iterator is advanced. Blank
+/// lines are skipped.
+///
+/// A line that is not a valid item is a [Kind::Deserialization] error that gives its line
+/// number, counting from 1. The iterator goes on after an error, so you can skip bad lines or
+/// stop at the first one (for example by collecting into a `Result<Vec<InputItem>>`).
+pub fn read_input_items<R>(r: R) -> impl Iterator<Item = Result<InputItem>>
+where
+    R: BufRead,
+{
+    r.lines().enumerate().filter_map(|(idx, line)| match line {
+        Err(e) => Some(Err(e.into())),
+        Ok(line) if line.trim().is_empty() => None,
+        Ok(line) => Some(serde_json::from_str(&line).map_err(|e| Error {
+            kind: Kind::Deserialization(format!("NDJSON line {}: {}", idx + 1, e)),
+        })),
+    })
+}
+
+pub(crate) fn write_ndjson_line<W>(w: &mut W, item: &FeedItem) -> Result<()>
+where
+    W: Write,
+{
+    serde_json::to_writer(&mut *w, item)?;
+    w.write_all(b"\n")?;
+    Ok(())
+}
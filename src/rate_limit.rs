@@ -0,0 +1,102 @@
+//! A client-wide limit on how fast calls are made to the API
+//!
+//! Set a [RateLimiter] on an [crate::clients::AsyncYupdatesClient] with
+//! [crate::clients::AsyncYupdatesClient::max_requests_per_second] or
+//! [crate::clients::AsyncYupdatesClient::with_rate_limiter]. Every HTTP request that the client
+//! makes then waits for a token first, so the total rate stays under the limit however many tasks
+//! share the client (or its clones, which share the limiter). Requests are let through in the
+//! order they started waiting.
+//!
+//! A client without a limiter does not wait at all. The `sleep_ms` pacing of calls like
+//! [crate::api::YupdatesV0::new_items_all] still applies on top of the limit.
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Instant};
+
+tokio::task_local! {
+    // The limiter of the client whose call is running
+    static RATE_LIMITER: Arc<RateLimiter>;
+}
+
+/// A token bucket shared by every request of the clients that hold it
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    burst: u32,
+    // A tokio mutex queues its waiters in order, which makes the limiter fair
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl RateLimiter {
+    /// At most `requests_per_second` requests each second, evenly spaced. Values below 1 are
+    /// treated as 1.
+    pub fn per_second(requests_per_second: u32) -> RateLimiter {
+        let interval = Duration::from_secs(1) / requests_per_second.max(1);
+        RateLimiter {
+            interval,
+            burst: 1,
+            bucket: Mutex::new(Bucket {
+                tokens: 1.0,
+                refilled: Instant::now(),
+            }),
+        }
+    }
+
+    /// Let up to `burst` requests through at once after a quiet period, while keeping the same
+    /// average rate. Values below 1 are treated as 1. The bucket starts full.
+    pub fn with_burst(mut self, burst: u32) -> RateLimiter {
+        self.burst = burst.max(1);
+        self.bucket.get_mut().tokens = self.burst as f64;
+        self
+    }
+
+    /// The time between two requests at the limit
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Wait for a token. Waiters get their tokens in the order they called this.
+    pub async fn acquire(&self) {
+        // The lock is held while waiting, so that a later caller cannot take the token first
+        let mut bucket = self.bucket.lock().await;
+        bucket.refill(self.interval, self.burst);
+        if bucket.tokens < 1.0 {
+            let missing = 1.0 - bucket.tokens;
+            sleep(self.interval.mul_f64(missing)).await;
+            bucket.refill(self.interval, self.burst);
+        }
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+}
+
+impl Bucket {
+    fn refill(&mut self, interval: Duration, burst: u32) {
+        let now = Instant::now();
+        let earned = now.duration_since(self.refilled).as_secs_f64() / interval.as_secs_f64();
+        self.tokens = (self.tokens + earned).min(burst as f64);
+        self.refilled = now;
+    }
+}
+
+// Run `fut` with the requests it makes limited by `limiter`, if there is one
+pub(crate) async fn limited<F: Future>(limiter: Option<&Arc<RateLimiter>>, fut: F) -> F::Output {
+    match limiter {
+        None => fut.await,
+        Some(limiter) => RATE_LIMITER.scope(limiter.clone(), fut).await,
+    }
+}
+
+// Called before each request: waits for a token if the running call is limited
+pub(crate) async fn acquire() {
+    if let Ok(limiter) = RATE_LIMITER.try_with(Arc::clone) {
+        limiter.acquire().await;
+    }
+}
@@ -0,0 +1,91 @@
+//! Conversions to and from the `rss` crate's [Item](::rss::Item), enabled with the `rss-interop`
+//! feature
+//!
+//! An RSS item becomes an [InputItem]: the content is `content:encoded` if present, otherwise the
+//! description, and the enclosure becomes an [AssociatedFile]. A [FeedItem] becomes an RSS item
+//! with its content as the description, its item ID as a (non-permalink) guid, and its first
+//! associated file as the enclosure, since RSS allows only one.
+use crate::errors::{Error, Kind, Result};
+use crate::models::{AssociatedFile, FeedItem, InputItem};
+use crate::time::{item_ms, rfc822};
+use ::rss::{Enclosure, Guid, Item};
+
+impl TryFrom<&Item> for InputItem {
+    type Error = Error;
+
+    /// Fails with [Kind::IllegalParameter] if the item has no title or link, or if the
+    /// enclosure length is not a number.
+    fn try_from(item: &Item) -> Result<Self> {
+        let associated_files = match item.enclosure() {
+            None => None,
+            Some(enclosure) => Some(vec![AssociatedFile::try_from(enclosure)?]),
+        };
+        Ok(InputItem {
+            title: required(item.title(), "title")?,
+            content: item
+                .content()
+                .or_else(|| item.description())
+                .unwrap_or_default()
+                .to_string(),
+            canonical_url: required(item.link(), "link")?,
+            author: item.author().map(str::to_string),
+            associated_files,
+            input_id: None,
+            item_time: None,
+            extra: Default::default(),
+        })
+    }
+}
+
+impl TryFrom<&Enclosure> for AssociatedFile {
+    type Error = Error;
+
+    fn try_from(enclosure: &Enclosure) -> Result<Self> {
+        let length = enclosure.length().trim().parse().map_err(|_| Error {
+            kind: Kind::IllegalParameter(format!(
+                "RSS enclosure `length` is not a number: '{}'",
+                enclosure.length()
+            )),
+        })?;
+        Ok(AssociatedFile {
+            url: required(Some(enclosure.url()), "enclosure url")?,
+            length,
+            type_str: enclosure.mime_type().to_string(),
+        })
+    }
+}
+
+impl From<&FeedItem> for Item {
+    fn from(feed_item: &FeedItem) -> Self {
+        let mut item = Item::default();
+        item.set_title(feed_item.title.clone());
+        if !feed_item.canonical_url.is_empty() {
+            item.set_link(feed_item.canonical_url.clone());
+        }
+        item.set_description(feed_item.content.clone());
+        item.set_author(feed_item.author.clone());
+        let mut guid = Guid::default();
+        guid.set_value(feed_item.item_id.clone());
+        guid.set_permalink(false);
+        item.set_guid(guid);
+        item.set_pub_date(rfc822(item_ms(feed_item)));
+        if let Some(file) = feed_item.associated_files.iter().flatten().next() {
+            let mut enclosure = Enclosure::default();
+            enclosure.set_url(file.url.clone());
+            enclosure.set_length(file.length.to_string());
+            enclosure.set_mime_type(file.type_str.clone());
+            item.set_enclosure(enclosure);
+        }
+        item
+    }
+}
+
+// Missing and blank values are both errors that name the field
+fn required(value: Option<&str>, field: &str) -> Result<String> {
+    match value.map(str::trim) {
+        Some(v) if !v.is_empty() => Ok(v.to_string()),
+        _ => Err(Error {
+            kind: Kind::IllegalParameter(format!("RSS item has no `{}`", field)),
+        }),
+    }
+}
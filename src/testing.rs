@@ -0,0 +1,376 @@
+//! An in-process mock of the API for testing code that uses this SDK, enabled with the `testing`
+//! feature
+//!
+//! [MockYupdates] starts a local HTTP server that answers `ping/`, `items/`, `feeds/{feed_id}/`,
+//! and deletes and updates of `feeds/{feed_id}/items/{item_id}/` like the API does, keeping the
+//! items of one feed in memory. Point a client at it with [MockYupdates::client] (or
+//! [client_with_base_url] for any other server) and your tests run deterministically, without API
+//! tokens or network access.
+//!
+//! ```rust
+//! use yupdates::errors::Error;
+//! use yupdates::models::InputItem;
+//! use yupdates::testing::MockYupdates;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Error> {
+//!     let mock = MockYupdates::start().await;
+//!     let yup = mock.client();
+//!     let item = InputItem {
+//!         title: "Hello".to_string(),
+//!         content: "World".to_string(),
+//!         canonical_url: "https://www.example.com/hello".to_string(),
+//!         author: None,
+//!         associated_files: None,
+//!         input_id: None,
+//!         item_time: None,
+//!         extra: Default::default(),
+//!     };
+//!     let feed_id = yup.new_items_all(&[item], 5).await?;
+//!     let items = yup.read_items(&feed_id).await?;
+//!     assert_eq!(items[0].title, "Hello");
+//!     Ok(())
+//! }
+//! ```
+use crate::clients::AsyncYupdatesClient;
+use crate::models::{FeedId, FeedItem, InputId, InputItem, ItemId, ItemPatch};
+use crate::{normalize_item_time, parse_item_time};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// The feed that [MockYupdates] serves and adds items to
+pub const MOCK_FEED_ID: &str = "02fb24a4478462a4491067224b66d9a8b2338ddca2737";
+
+/// The token that clients created by this module send. The mock accepts any token.
+pub const MOCK_TOKEN: &str = "mock-token";
+
+/// Create a client for the server at `base_url`, using [MOCK_TOKEN]. A trailing slash is added
+/// to the base URL if it is missing.
+pub fn client_with_base_url<S>(base_url: S) -> AsyncYupdatesClient
+where
+    S: Into<String>,
+{
+    let mut base_url = base_url.into();
+    if !base_url.ends_with('/') {
+        base_url.push('/');
+    }
+    AsyncYupdatesClient {
+        base_url,
+        http_client: reqwest::Client::new(),
+        token: MOCK_TOKEN.to_string(),
+        extensions: Default::default(),
+    }
+}
+
+/// A local mock of the API with one feed, [MOCK_FEED_ID].
+///
+/// Reads honor `max_items`, `include_item_content`, `item_time_after`, and `item_time_before`,
+/// and a search query `q` matches items whose title or content contains it, ignoring case.
+/// Items added with `new_items` get the next item time, unless they set their own, and items with
+/// an input ID that is already in the feed are skipped, as the `item_results` say. Use
+/// [MockYupdates::server] to mount extra mocks (which take priority) or to inspect the requests
+/// that were received.
+pub struct MockYupdates {
+    server: MockServer,
+    feed_items: Arc<Mutex<Vec<FeedItem>>>,
+}
+
+impl MockYupdates {
+    pub async fn start() -> MockYupdates {
+        let server = MockServer::start().await;
+        let feed_items = Arc::new(Mutex::new(Vec::new()));
+        Mock::given(method("GET"))
+            .and(path("/ping/"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"code": 200, "message": "pong"})),
+            )
+            .with_priority(u8::MAX)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/items/"))
+            .respond_with(NewItemsResponder {
+                feed_items: feed_items.clone(),
+            })
+            .with_priority(u8::MAX)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex("^/feeds/[^/]+/$"))
+            .respond_with(ReadItemsResponder {
+                feed_items: feed_items.clone(),
+            })
+            .with_priority(u8::MAX)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path_regex("^/feeds/[^/]+/items/[^/]+/$"))
+            .respond_with(DeleteItemResponder {
+                feed_items: feed_items.clone(),
+            })
+            .with_priority(u8::MAX)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path_regex("^/feeds/[^/]+/items/[^/]+/$"))
+            .respond_with(UpdateItemResponder {
+                feed_items: feed_items.clone(),
+            })
+            .with_priority(u8::MAX)
+            .mount(&server)
+            .await;
+        MockYupdates { server, feed_items }
+    }
+
+    /// The base URL of the mock, with a trailing slash
+    pub fn base_url(&self) -> String {
+        format!("{}/", self.server.uri())
+    }
+
+    /// A client pointed at this mock
+    pub fn client(&self) -> AsyncYupdatesClient {
+        client_with_base_url(self.base_url())
+    }
+
+    /// Add items to the feed directly, as if they had been published. Their `feed_id` is set to
+    /// [MOCK_FEED_ID].
+    pub fn add_feed_items(&self, items: Vec<FeedItem>) {
+        let mut feed_items = lock(&self.feed_items);
+        for mut item in items {
+            item.feed_id = Some(mock_feed_id());
+            feed_items.push(item);
+        }
+    }
+
+    /// Every item in the feed, in the order they were added
+    pub fn feed_items(&self) -> Vec<FeedItem> {
+        lock(&self.feed_items).clone()
+    }
+
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+}
+
+// The body of a new items call, as the API receives it
+#[derive(Deserialize)]
+struct ReceivedItems {
+    items: Vec<InputItem>,
+}
+
+struct NewItemsResponder {
+    feed_items: Arc<Mutex<Vec<FeedItem>>>,
+}
+
+impl Respond for NewItemsResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: ReceivedItems = match serde_json::from_slice(&request.body) {
+            Ok(body) => body,
+            Err(e) => return error_response(400, &format!("invalid body: {}", e)),
+        };
+        let mut feed_items = lock(&self.feed_items);
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        // Item times keep increasing even if the clock does not
+        let first_ms = feed_items
+            .iter()
+            .map(|item| item.item_time_ms + 1)
+            .max()
+            .unwrap_or(0)
+            .max(now_ms);
+        let mut item_results = Vec::new();
+        for (index, (next_ms, input)) in (first_ms..).zip(body.items).enumerate() {
+            let num = feed_items.len() + 1;
+            let input_id = InputId::from(input.input_id.unwrap_or_else(|| format!("input{}", num)));
+            // A repeated input ID is a retry of an item that was already added
+            if let Some(item) = feed_items.iter().find(|item| item.input_id == input_id) {
+                item_results.push(json!({
+                    "index": index,
+                    "status": "duplicate",
+                    "item_id": item.item_id,
+                    "input_id": input_id,
+                }));
+                continue;
+            }
+            item_results.push(json!({
+                "index": index,
+                "status": "accepted",
+                "item_id": format!("item{}", num),
+                "input_id": input_id,
+            }));
+            // An explicit item time is kept, like when importing history
+            let item_time = input
+                .item_time
+                .and_then(|it| normalize_item_time(it).ok())
+                .unwrap_or_else(|| format!("{:0>13}.00000", next_ms));
+            let item_time_ms = parse_item_time(&item_time).map_or(next_ms, |(ms, _)| ms);
+            feed_items.push(FeedItem {
+                feed_id: Some(mock_feed_id()),
+                item_id: ItemId::from(format!("item{}", num)),
+                input_id,
+                title: input.title,
+                content: Some(input.content),
+                canonical_url: input.canonical_url,
+                author: input.author,
+                item_time,
+                item_time_ms,
+                deleted: false,
+                associated_files: input.associated_files,
+                extra: input.extra,
+            });
+        }
+        ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_id": MOCK_FEED_ID,
+            "message": "items received",
+            "item_results": item_results,
+        }))
+    }
+}
+
+struct ReadItemsResponder {
+    feed_items: Arc<Mutex<Vec<FeedItem>>>,
+}
+
+impl Respond for ReadItemsResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        if request.url.path() != format!("/feeds/{}/", MOCK_FEED_ID) {
+            return error_response(404, "feed not found");
+        }
+        let param = |name: &str| {
+            request
+                .url
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.to_string())
+        };
+        let max_items = param("max_items")
+            .and_then(|m| m.parse::<usize>().ok())
+            .unwrap_or(10);
+        let include_content = param("include_item_content").as_deref() == Some("true");
+        let after = param("item_time_after").and_then(|it| normalize_item_time(it).ok());
+        let before = param("item_time_before").and_then(|it| normalize_item_time(it).ok());
+        let search_query = param("q").map(|q| q.to_lowercase());
+
+        let mut feed_items = lock(&self.feed_items).clone();
+        feed_items.sort_by(|a, b| b.item_time.cmp(&a.item_time));
+        let selected: Vec<FeedItem> = feed_items
+            .into_iter()
+            .filter(|item| after.as_ref().is_none_or(|after| item.item_time > *after))
+            .filter(|item| {
+                before
+                    .as_ref()
+                    .is_none_or(|before| item.item_time < *before)
+            })
+            .filter(|item| {
+                search_query.as_ref().is_none_or(|q| {
+                    item.title.to_lowercase().contains(q)
+                        || item
+                            .content
+                            .as_ref()
+                            .is_some_and(|content| content.to_lowercase().contains(q))
+                })
+            })
+            .take(max_items)
+            .map(|mut item| {
+                if !include_content {
+                    item.content = None;
+                    item.associated_files = None;
+                }
+                item
+            })
+            .collect();
+        ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_items": selected,
+        }))
+    }
+}
+
+struct DeleteItemResponder {
+    feed_items: Arc<Mutex<Vec<FeedItem>>>,
+}
+
+impl Respond for DeleteItemResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let prefix = format!("/feeds/{}/items/", MOCK_FEED_ID);
+        let Some(item_id) = request.url.path().strip_prefix(&prefix) else {
+            return error_response(404, "feed not found");
+        };
+        let item_id = item_id.trim_end_matches('/');
+        let mut feed_items = lock(&self.feed_items);
+        match feed_items.iter_mut().find(|item| item.item_id == item_id) {
+            None => error_response(404, "item not found"),
+            Some(item) if item.deleted => error_response(410, "item already deleted"),
+            Some(item) => {
+                item.deleted = true;
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"code": 200, "message": "item deleted"}))
+            }
+        }
+    }
+}
+
+struct UpdateItemResponder {
+    feed_items: Arc<Mutex<Vec<FeedItem>>>,
+}
+
+impl Respond for UpdateItemResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let prefix = format!("/feeds/{}/items/", MOCK_FEED_ID);
+        let Some(item_id) = request.url.path().strip_prefix(&prefix) else {
+            return error_response(404, "feed not found");
+        };
+        let item_id = item_id.trim_end_matches('/');
+        let patch: ItemPatch = match serde_json::from_slice(&request.body) {
+            Ok(patch) => patch,
+            Err(e) => return error_response(400, &format!("invalid body: {}", e)),
+        };
+        let mut feed_items = lock(&self.feed_items);
+        let item = match feed_items.iter_mut().find(|item| item.item_id == item_id) {
+            None => return error_response(404, "item not found"),
+            Some(item) if item.deleted => return error_response(410, "item deleted"),
+            Some(item) => item,
+        };
+        if let Some(title) = patch.title {
+            item.title = title;
+        }
+        if let Some(content) = patch.content {
+            item.content = Some(content);
+        }
+        if let Some(canonical_url) = patch.canonical_url {
+            item.canonical_url = canonical_url;
+        }
+        if patch.author.is_some() {
+            item.author = patch.author;
+        }
+        if let Some(files) = patch.associated_files {
+            item.associated_files = (!files.is_empty()).then_some(files);
+        }
+        if let Some(item_time) = patch.item_time.and_then(|it| normalize_item_time(it).ok()) {
+            item.item_time_ms = parse_item_time(&item_time).map_or(item.item_time_ms, |(ms, _)| ms);
+            item.item_time = item_time;
+        }
+        item.extra.extend(patch.extra);
+        ResponseTemplate::new(200).set_body_json(json!({"code": 200, "feed_item": item}))
+    }
+}
+
+fn error_response(code: u16, error: &str) -> ResponseTemplate {
+    ResponseTemplate::new(code).set_body_json(json!({"code": code, "error": error}))
+}
+
+fn mock_feed_id() -> FeedId {
+    FeedId::new(MOCK_FEED_ID).expect("MOCK_FEED_ID is a valid feed ID")
+}
+
+fn lock(feed_items: &Mutex<Vec<FeedItem>>) -> MutexGuard<'_, Vec<FeedItem>> {
+    feed_items
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
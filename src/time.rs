@@ -0,0 +1,93 @@
+//! Calendar formatting of item times, without a date library
+// Only the RSS and Atom formats use the weekdays, months, and milliseconds
+#![cfg_attr(not(feature = "rss"), allow(dead_code))]
+use crate::models::FeedItem;
+use crate::parse_item_time;
+
+// `item_time_ms` is missing when only some fields were read, the item time always has it
+pub(crate) fn item_ms(item: &FeedItem) -> u64 {
+    if item.item_time_ms > 0 {
+        return item.item_time_ms;
+    }
+    parse_item_time(&item.item_time).map_or(0, |(ms, _)| ms)
+}
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// For example "Sat, 27 Aug 2022 01:33:33 GMT"
+pub(crate) fn rfc822(ms: u64) -> String {
+    let t = UtcTime::from_ms(ms);
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[t.weekday],
+        t.day,
+        MONTHS[t.month as usize - 1],
+        t.year,
+        t.hour,
+        t.minute,
+        t.second
+    )
+}
+
+// For example "2022-08-27 01:33:33 UTC"
+pub(crate) fn human(ms: u64) -> String {
+    let t = UtcTime::from_ms(ms);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        t.year, t.month, t.day, t.hour, t.minute, t.second
+    )
+}
+
+// For example "2022-08-27T01:33:33.001Z"
+pub(crate) fn rfc3339(ms: u64) -> String {
+    let t = UtcTime::from_ms(ms);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        t.year, t.month, t.day, t.hour, t.minute, t.second, t.millis
+    )
+}
+
+struct UtcTime {
+    year: u64,
+    month: u64,
+    day: u64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+    millis: u64,
+    // 0 is Sunday
+    weekday: usize,
+}
+
+impl UtcTime {
+    // The days-to-civil conversion from http://howardhinnant.github.io/date_algorithms.html
+    fn from_ms(ms: u64) -> UtcTime {
+        let days = ms / 86_400_000;
+        let ms_of_day = ms % 86_400_000;
+
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + u64::from(month <= 2);
+
+        UtcTime {
+            year,
+            month,
+            day,
+            hour: ms_of_day / 3_600_000,
+            minute: ms_of_day / 60_000 % 60,
+            second: ms_of_day / 1000 % 60,
+            millis: ms_of_day % 1000,
+            // 1970-01-01 was a Thursday
+            weekday: ((days + 4) % 7) as usize,
+        }
+    }
+}
@@ -1,16 +1,78 @@
+//! Most of these tests run against a local mock server and do not require API tokens. The
+//! modules that call the API say which tokens they need.
 extern crate core;
 
 use rand::distributions::Alphanumeric;
 use rand::Rng;
+use serde_json::{json, Value};
 use std::env;
 use std::env::VarError;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
 use yupdates::clients::AsyncYupdatesClient;
 use yupdates::env_or_default_url;
 use yupdates::errors::{Error, Kind, Result};
 use yupdates::models::{AssociatedFile, InputItem};
 
+mod test_api_surface;
+mod test_batch_options;
+mod test_client_builder;
+mod test_compression;
+#[cfg(feature = "csv")]
+mod test_csv;
+mod test_delete_feed;
+mod test_errors;
+#[cfg(feature = "feed-import")]
+mod test_feed_import;
+mod test_feed_writer;
+#[cfg(feature = "rss")]
+mod test_formats;
+mod test_get_item;
+#[cfg(feature = "html-text")]
+mod test_html_text;
 mod test_input_items;
+mod test_item_time;
+mod test_limits;
+mod test_list_feeds;
+#[cfg(feature = "markdown")]
+mod test_markdown;
+#[cfg(feature = "reqwest-middleware")]
+mod test_middleware;
+mod test_models;
+mod test_ndjson;
+mod test_new_feed;
+mod test_new_items_body;
+mod test_new_items_concurrent;
+mod test_new_items_dedup;
+mod test_new_items_dry_run;
+mod test_new_items_idempotent;
+mod test_new_items_lenient;
+mod test_new_items_progress;
+mod test_new_items_resume;
+mod test_new_items_retry;
+#[cfg(feature = "testing")]
+mod test_new_items_skip_existing;
+mod test_ping;
+mod test_poll;
+mod test_rate_limit;
+#[cfg(feature = "cache")]
+mod test_read_cache;
 mod test_read_items;
+mod test_read_items_mock;
+mod test_read_options;
+#[cfg(feature = "response-compression")]
+mod test_response_compression;
+#[cfg(feature = "rss-interop")]
+mod test_rss_interop;
+mod test_sync_shared;
+#[cfg(feature = "testing")]
+mod test_testing;
+#[cfg(feature = "tracing")]
+mod test_tracing;
+#[cfg(feature = "testing")]
+mod test_upsert_items;
+
+pub const MOCK_FEED_ID: &str = "02fb24a4478462a4491067224b66d9a8b2338ddca2737";
 
 pub const YUPDATES_TEST_FEED_SPECIFIC_TOKEN: &str = "YUPDATES_TEST_FEED_SPECIFIC_TOKEN";
 pub const YUPDATES_TEST_RO_TOKEN: &str = "YUPDATES_TEST_RO_TOKEN";
@@ -22,15 +84,94 @@ pub fn test_clients() -> Result<(AsyncYupdatesClient, AsyncYupdatesClient)> {
         base_url: base_url.clone(),
         http_client: Default::default(),
         token: read_only_token,
+        extensions: Default::default(),
     };
     let feed_client = AsyncYupdatesClient {
         base_url,
         http_client: Default::default(),
         token: feed_token,
+        extensions: Default::default(),
     };
     Ok((ro_client, feed_client))
 }
 
+/// Client pointed at a local mock server, these tests do not need any API tokens.
+pub fn mock_client(server: &MockServer) -> AsyncYupdatesClient {
+    AsyncYupdatesClient {
+        base_url: format!("{}/", server.uri()),
+        http_client: Default::default(),
+        token: "mock-token".to_string(),
+        extensions: Default::default(),
+    }
+}
+
+/// The response to a new items call whose items were added to `feed_id`.
+pub fn items_received(feed_id: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "code": 200,
+        "feed_id": feed_id,
+        "message": "items received"
+    }))
+}
+
+/// A local mock server that adds the items of every new items call to [MOCK_FEED_ID].
+pub async fn mock_items_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .mount(&server)
+        .await;
+    server
+}
+
+/// JSON for a feed item as the API would return it, `num` determines the IDs and item time.
+pub fn mock_feed_item(num: u64) -> Value {
+    json!({
+        "feed_id": MOCK_FEED_ID,
+        "item_id": format!("item{}", num),
+        "input_id": format!("input{}", num),
+        "title": format!("title-{}", num),
+        "content": format!("content-{}", num),
+        "canonical_url": format!("https://www.example.com/{}", num),
+        "item_time": format!("{:0>13}.00000", 1661564013000 + num),
+        "item_time_ms": 1661564013000 + num,
+        "deleted": false,
+        "associated_files": null
+    })
+}
+
+/// JSON for a read items response, newest first like the API returns them.
+pub fn mock_feed_items_response(nums: &[u64]) -> Value {
+    let mut sorted = nums.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+    let feed_items: Vec<Value> = sorted.into_iter().map(mock_feed_item).collect();
+    json!({ "code": 200, "feed_items": feed_items })
+}
+
+/// Serves a feed of items 1..=count, honoring `max_items` and `item_time_before`
+pub struct PagedFeed {
+    pub count: u64,
+}
+
+impl Respond for PagedFeed {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let param = |name: &str| {
+            request
+                .url
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.to_string())
+        };
+        let max_items: u64 = param("max_items").unwrap().parse().unwrap();
+        let before = param("item_time_before")
+            .map(|it| it[..13].parse::<u64>().unwrap() - 1661564013000)
+            .unwrap_or(self.count + 1);
+        let nums: Vec<u64> = (1..before).rev().take(max_items as usize).collect();
+        ResponseTemplate::new(200).set_body_json(mock_feed_items_response(&nums))
+    }
+}
+
 pub fn test_tokens() -> Result<(String, String)> {
     let read_only = one_env("read-only API test token", YUPDATES_TEST_RO_TOKEN)?;
     let feed_specific = one_env(
@@ -66,7 +207,11 @@ pub fn random_test_items(num: usize) -> (Vec<InputItem>, Vec<String>) {
             title: format!("title-{}", suffix),
             content: format!("content-{}", suffix),
             canonical_url: format!("https://www.example.com/{}", suffix),
+            author: None,
             associated_files,
+            input_id: None,
+            item_time: None,
+            extra: Default::default(),
         };
         suffixes.push(suffix);
         input_items.push(input_item);
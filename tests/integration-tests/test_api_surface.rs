@@ -0,0 +1,207 @@
+//! A guard against accidental changes to the public API.
+//!
+//! This renders the public items in `src/` (signatures, public fields, enum variants, trait
+//! methods, and trait impls) and compares them to the snapshot in `tests/api_surface.txt`. It
+//! relies on the source being formatted with `cargo fmt`. It is a deliberately simple scan, not
+//! a compiler-accurate view, but it catches changed bounds, removed impls, and new variants.
+//!
+//! It does not see:
+//! - `#[cfg]` and `#[cfg_attr]` attributes. Feature-gated items are listed like the others, so
+//!   moving an item behind a feature, or out from behind one, is not a change.
+//! - Attributes other than derives and `#[non_exhaustive]`, like `#[serde]` or `#[deprecated]`.
+//! - Auto traits. A new field that makes a type no longer `Send` or `Sync` is not a change.
+//! - Items that macros generate, or what a `pub use` re-exports. Only the `use` line is recorded.
+//! - Layouts other than `cargo fmt`'s. A signature ends at the first line that ends with `{`,
+//!   `;`, or `}` (or `,` for fields and variants), so a multi-line bound or default that ends a
+//!   line that way, like a closure, cuts the signature short.
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const UPDATE_API_SURFACE: &str = "UPDATE_API_SURFACE";
+
+// The blind spots listed above, for the failure message, so the diff is not taken as complete
+const BLIND_SPOTS: &str = "This scan does not see cfg attributes (feature-gated items are \
+    listed like the others), attributes other than derives and non_exhaustive, auto traits \
+    like Send and Sync, items generated by macros or re-exported, or signatures that cargo fmt \
+    did not lay out. Review those by hand.";
+
+#[test]
+fn public_api_surface() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let snapshot_path = root.join("tests").join("api_surface.txt");
+    let current = render_api_surface(&root.join("src"));
+
+    if env::var(UPDATE_API_SURFACE).is_ok() {
+        fs::write(&snapshot_path, &current).unwrap();
+        return;
+    }
+    let snapshot = fs::read_to_string(&snapshot_path).unwrap_or_default();
+    if snapshot == current {
+        return;
+    }
+
+    let before: BTreeSet<&str> = snapshot.lines().collect();
+    let after: BTreeSet<&str> = current.lines().collect();
+    let mut diff = String::new();
+    for removed in before.difference(&after) {
+        diff.push_str(&format!("- {}\n", removed));
+    }
+    for added in after.difference(&before) {
+        diff.push_str(&format!("+ {}\n", added));
+    }
+    panic!(
+        "The public API changed:\n\n{}\n\
+         Removals and changed signatures are breaking changes, make sure the version is bumped \
+         accordingly. If the change is deliberate, update the snapshot and commit it:\n\n    \
+         {}=1 cargo test --test integration-tests public_api_surface\n\n{}\n",
+        diff, UPDATE_API_SURFACE, BLIND_SPOTS
+    );
+}
+
+fn render_api_surface(src: &Path) -> String {
+    let mut files = Vec::new();
+    collect_source_files(src, &mut files);
+    files.sort();
+
+    let mut lines = Vec::new();
+    for path in files {
+        // Nested modules are named by their path, like `formats::rss`
+        let module = path
+            .strip_prefix(src)
+            .unwrap()
+            .with_extension("")
+            .iter()
+            .map(|part| part.to_string_lossy().to_string())
+            .collect::<Vec<String>>()
+            .join("::");
+        let text = fs::read_to_string(&path).unwrap();
+        lines.extend(render_file(&module, &text));
+    }
+    lines.sort();
+    lines.dedup();
+    let mut rendered = lines.join("\n");
+    rendered.push('\n');
+    rendered
+}
+
+fn collect_source_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_source_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+// The enclosing item for the lines that follow it at a deeper indentation
+struct Scope {
+    indent: usize,
+    header: String,
+}
+
+fn render_file(module: &str, text: &str) -> Vec<String> {
+    let source: Vec<&str> = text.lines().collect();
+    let mut rendered = Vec::new();
+    let mut scopes: Vec<Scope> = Vec::new();
+    let mut derives = String::new();
+    let mut i = 0;
+    while i < source.len() {
+        let line = source[i];
+        let trimmed = line.trim();
+        i += 1;
+        if trimmed.starts_with("#[derive") || trimmed == "#[non_exhaustive]" {
+            derives.push_str(trimmed);
+            derives.push(' ');
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with("//") || trimmed.starts_with('#') {
+            continue;
+        }
+        let attributes = std::mem::take(&mut derives);
+        let indent = line.len() - line.trim_start().len();
+        while scopes.last().is_some_and(|s| s.indent >= indent) {
+            scopes.pop();
+        }
+
+        let is_pub = trimmed.starts_with("pub ");
+        let is_header = trimmed.starts_with("impl")
+            || trimmed.starts_with("pub trait ")
+            || trimmed.starts_with("pub enum ")
+            || trimmed.starts_with("pub struct ")
+            || trimmed.starts_with("pub mod ")
+            || trimmed.starts_with("mod ");
+        let parent = scopes.last().map(|s| s.header.as_str()).unwrap_or("");
+        let in_trait = parent.starts_with("pub trait ");
+        let is_trait_fn = in_trait && trimmed.starts_with("fn ");
+        let is_variant = parent.starts_with("pub enum ")
+            && trimmed.starts_with(|c: char| c.is_ascii_uppercase());
+        let in_fn_body = scopes.iter().any(|s| s.header.contains("fn "));
+        if in_fn_body || !(is_pub || is_header || is_trait_fn || is_variant) {
+            continue;
+        }
+
+        // Join a multi-line signature into one line. Fields and variants end with a comma,
+        // everything else ends with a brace or a semicolon.
+        let is_member = is_variant || parent.starts_with("pub struct ");
+        let is_complete = |signature: &str| {
+            signature.ends_with('{')
+                || signature.ends_with(';')
+                || signature.ends_with('}')
+                || (is_member && signature.ends_with(','))
+        };
+        let mut signature = trimmed.to_string();
+        while !is_complete(&signature) && i < source.len() {
+            let next = source[i].trim();
+            i += 1;
+            if !next.starts_with("//") {
+                signature.push(' ');
+                signature.push_str(next);
+            }
+        }
+        let signature = normalize(&signature);
+
+        let path = scopes
+            .iter()
+            .map(|s| s.header.as_str())
+            .collect::<Vec<&str>>()
+            .join(" :: ");
+        let keep = !signature.starts_with("impl") || signature.contains(" for ");
+        if keep {
+            if path.is_empty() {
+                rendered.push(format!("{}: {}{}", module, attributes, signature));
+            } else {
+                rendered.push(format!(
+                    "{}: {} :: {}{}",
+                    module, path, attributes, signature
+                ));
+            }
+        }
+        if is_header || trimmed.contains("fn ") {
+            scopes.push(Scope {
+                indent,
+                header: signature,
+            });
+        }
+    }
+    rendered
+}
+
+fn normalize(signature: &str) -> String {
+    let collapsed = signature
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let collapsed = collapsed
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(",)", ")");
+    collapsed
+        .trim_end_matches('{')
+        .trim_end()
+        .trim_end_matches(',')
+        .to_string()
+}
@@ -0,0 +1,140 @@
+use crate::{mock_client, mock_items_server, random_test_items, MOCK_FEED_ID};
+use serde_json::{json, Value};
+use std::time::Duration;
+use yupdates::api::{chunk_items_with_options, BatchOptions, MAX_ITEMS_PER_CALL};
+use yupdates::errors::{Kind, Result};
+use yupdates::models::InputItem;
+
+// Items whose content is `sizes[i]` bytes long
+fn sized_items(sizes: &[usize]) -> Vec<InputItem> {
+    let (mut items, _) = random_test_items(sizes.len());
+    for (item, size) in items.iter_mut().zip(sizes) {
+        item.content = "x".repeat(*size);
+    }
+    items
+}
+
+fn body_len(items: &[InputItem]) -> usize {
+    serde_json::to_vec(&json!({ "items": items }))
+        .unwrap()
+        .len()
+}
+
+fn batch_lens(batches: &[&[InputItem]]) -> Vec<usize> {
+    batches.iter().map(|batch| batch.len()).collect()
+}
+
+/// The default splits by count only, like chunk_items
+#[test]
+fn chunk_items_with_options_default() -> Result<()> {
+    let items = sized_items(&[5000; 23]);
+    let batches = chunk_items_with_options(&items, &BatchOptions::default())?;
+    assert_eq!(batch_lens(&batches), [10, 10, 3]);
+
+    let options = BatchOptions {
+        max_items_per_batch: 4,
+        ..Default::default()
+    };
+    let batches = chunk_items_with_options(&items, &options)?;
+    assert_eq!(batch_lens(&batches), [4, 4, 4, 4, 4, 3]);
+    Ok(())
+}
+
+/// Mixed sizes close batches early, at uneven boundaries, and every body is within the budget
+#[test]
+fn chunk_items_with_options_mixed_sizes() -> Result<()> {
+    let items = sized_items(&[
+        100, 100, 3000, 100, 2000, 2000, 100, 100, 100, 100, 100, 100, 100,
+    ]);
+    let max_bytes = body_len(&items[..3]);
+    let options = BatchOptions {
+        max_bytes_per_batch: Some(max_bytes),
+        ..Default::default()
+    };
+    let batches = chunk_items_with_options(&items, &options)?;
+    // The first batch fills the budget exactly
+    assert_eq!(batch_lens(&batches), [3, 2, 6, 2]);
+    for batch in &batches {
+        assert!(body_len(batch) <= max_bytes);
+    }
+    let flattened: Vec<&InputItem> = batches.iter().flat_map(|batch| batch.iter()).collect();
+    assert!(flattened.into_iter().eq(items.iter()));
+
+    let options = BatchOptions {
+        max_items_per_batch: 5,
+        ..options
+    };
+    let batches = chunk_items_with_options(&items, &options)?;
+    assert_eq!(batch_lens(&batches), [3, 2, 5, 3]);
+    Ok(())
+}
+
+/// An item that is over the budget on its own is an error, as are illegal item counts
+#[test]
+fn chunk_items_with_options_oversized_item() {
+    let items = sized_items(&[100, 100, 5000, 100]);
+    let options = BatchOptions {
+        max_bytes_per_batch: Some(body_len(&items[2..3]) - 1),
+        ..Default::default()
+    };
+    let err = chunk_items_with_options(&items, &options).unwrap_err();
+    match err.kind {
+        Kind::IllegalParameter(s) => assert!(s.starts_with("item 2 alone"), "{}", s),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+    let options = BatchOptions {
+        max_bytes_per_batch: Some(body_len(&items[2..3])),
+        ..Default::default()
+    };
+    let batches = chunk_items_with_options(&items, &options).unwrap();
+    assert_eq!(batch_lens(&batches), [2, 1, 1]);
+
+    for max_items_per_batch in [0, MAX_ITEMS_PER_CALL + 1] {
+        let options = BatchOptions {
+            max_items_per_batch,
+            ..Default::default()
+        };
+        assert!(matches!(
+            chunk_items_with_options(&items, &options).unwrap_err().kind,
+            Kind::IllegalParameter { .. }
+        ));
+    }
+}
+
+/// The calls follow the batch boundaries, and nothing is sent if an item is too large
+#[tokio::test]
+async fn new_items_all_with_options_batches() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let items = sized_items(&[100, 2000, 100, 100, 100, 2000]);
+    let options = BatchOptions {
+        max_items_per_batch: 3,
+        max_bytes_per_batch: Some(body_len(&items[..2])),
+        sleep: Duration::from_millis(5),
+    };
+    assert_eq!(
+        yup.new_items_all_with_options(&items, &options).await?,
+        MOCK_FEED_ID
+    );
+    let requests = server.received_requests().await.unwrap();
+    let counts: Vec<usize> = requests
+        .iter()
+        .map(|r| {
+            let body: Value = serde_json::from_slice(&r.body).unwrap();
+            body["items"].as_array().unwrap().len()
+        })
+        .collect();
+    assert_eq!(counts, [2, 3, 1]);
+
+    let options = BatchOptions {
+        max_bytes_per_batch: Some(1000),
+        ..options
+    };
+    let result = yup.new_items_all_with_options(&items, &options).await;
+    assert!(matches!(
+        result.unwrap_err().kind,
+        Kind::IllegalParameter { .. }
+    ));
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    Ok(())
+}
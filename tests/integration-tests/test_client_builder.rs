@@ -0,0 +1,37 @@
+use crate::{mock_feed_items_response, MOCK_FEED_ID};
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::clients::AsyncYupdatesClient;
+use yupdates::errors::{Kind, Result};
+
+/// A client with pool and keep-alive options makes calls like any other, including without
+/// reusing connections
+#[tokio::test]
+async fn client_builder_pool_options() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(mock_feed_items_response(&[1, 2])))
+        .mount(&server)
+        .await;
+
+    let yup = AsyncYupdatesClient::builder()
+        .with_pool_idle_timeout(Duration::from_secs(5))
+        .with_pool_max_idle_per_host(0)
+        .with_tcp_keepalive(Duration::from_secs(30))
+        .build_with_args(server.uri(), "mock-token".to_string())?;
+    assert_eq!(yup.base_url, format!("{}/", server.uri()));
+    for _ in 0..3 {
+        assert_eq!(yup.read_items(MOCK_FEED_ID).await?.len(), 2);
+    }
+    Ok(())
+}
+
+/// The token is checked like the other constructors check it
+#[test]
+fn client_builder_empty_token() {
+    let result = AsyncYupdatesClient::builder()
+        .build_with_args("http://127.0.0.1:1/".to_string(), " ".to_string());
+    assert!(matches!(result.err().unwrap().kind, Kind::Config(_)));
+}
@@ -0,0 +1,83 @@
+use crate::{mock_client, mock_items_server, random_test_items};
+use flate2::read::GzDecoder;
+use serde_json::{json, Value};
+use std::io::Read;
+use wiremock::Request;
+use yupdates::compression::RequestCompression;
+use yupdates::errors::Result;
+
+fn header<'a>(request: &'a Request, name: &str) -> Option<&'a str> {
+    request
+        .headers
+        .iter()
+        .find(|(header, _)| header.as_str().eq_ignore_ascii_case(name))
+        .map(|(_, values)| values.last().as_str())
+}
+
+fn content_encoding(request: &Request) -> Option<&str> {
+    header(request, "content-encoding")
+}
+
+fn gunzip(body: &[u8]) -> Vec<u8> {
+    let mut json = Vec::new();
+    GzDecoder::new(body).read_to_end(&mut json).unwrap();
+    json
+}
+
+/// A body over the threshold is gzipped, and the server can gunzip it back to the JSON it would
+/// have received without compression
+#[tokio::test]
+async fn compress_requests_gzips_large_bodies() -> Result<()> {
+    let server = mock_items_server().await;
+    let (mut items, _) = random_test_items(2);
+    items[0].content = "<p>Hello, world</p>".repeat(1000);
+    let yup = mock_client(&server).compress_requests(true);
+    yup.new_items(&items).await?;
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(content_encoding(request), Some("gzip"));
+    assert_eq!(header(request, "content-type"), Some("application/json"));
+    let expected = serde_json::to_vec(&json!({ "items": items })).unwrap();
+    assert!(request.body.len() < expected.len() / 10);
+    let received: Value = serde_json::from_slice(&gunzip(&request.body)).unwrap();
+    assert_eq!(
+        received,
+        serde_json::from_slice::<Value>(&expected).unwrap()
+    );
+    Ok(())
+}
+
+/// Bodies under the threshold, and every body of a client that does not compress, are plain JSON
+#[tokio::test]
+async fn compress_requests_threshold() -> Result<()> {
+    let server = mock_items_server().await;
+    let (items, _) = random_test_items(2);
+    let body_len = serde_json::to_vec(&json!({ "items": items }))
+        .unwrap()
+        .len();
+
+    let yup = mock_client(&server).compress_requests(true);
+    yup.new_items(&items).await?;
+    let small = mock_client(&server).with_request_compression(RequestCompression {
+        min_bytes: body_len,
+    });
+    small.new_items(&items).await?;
+    small
+        .clone()
+        .compress_requests(false)
+        .new_items(&items)
+        .await?;
+
+    let requests = server.received_requests().await.unwrap();
+    let encodings: Vec<_> = requests.iter().map(content_encoding).collect();
+    assert_eq!(encodings, vec![None, Some("gzip"), None]);
+    let received: Value = serde_json::from_slice(&gunzip(&requests[1].body)).unwrap();
+    assert_eq!(
+        received,
+        serde_json::from_slice::<Value>(&requests[0].body).unwrap()
+    );
+    assert_eq!(requests[2].body, requests[0].body);
+    Ok(())
+}
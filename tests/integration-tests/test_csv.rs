@@ -0,0 +1,167 @@
+use crate::{mock_client, mock_feed_item, mock_items_server, MOCK_FEED_ID};
+use std::fs::File;
+use std::path::PathBuf;
+use yupdates::errors::{Kind, Result};
+use yupdates::feed::{write_csv, write_csv_with_options, CsvExportOptions};
+use yupdates::items::{read_csv, CsvMapping};
+use yupdates::models::{AssociatedFile, FeedItem};
+
+fn file_mapping() -> CsvMapping {
+    CsvMapping {
+        file_url: Some("audio".to_string()),
+        file_length: Some("bytes".to_string()),
+        ..Default::default()
+    }
+}
+
+/// The default mapping reads `title,content,url`, matching headers ignoring case
+#[test]
+fn read_csv_default_mapping() -> Result<()> {
+    let csv = "Title , URL,content,notes\n\
+               a,https://www.example.com/a,<p>A</p>,x\n\
+               \"b, \"\"quoted\"\"\",https://www.example.com/b,\"<p>B\nB</p>\"\n";
+    let items = read_csv(csv.as_bytes(), CsvMapping::default())?;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].title, "a");
+    assert_eq!(items[0].content, "<p>A</p>");
+    assert_eq!(items[0].canonical_url, "https://www.example.com/a");
+    assert_eq!(items[0].associated_files, None);
+    assert_eq!(items[1].title, "b, \"quoted\"");
+    assert_eq!(items[1].content, "<p>B\nB</p>");
+
+    assert!(read_csv("title,content,url\n".as_bytes(), CsvMapping::default())?.is_empty());
+    match read_csv("title,body,url\na,b,c\n".as_bytes(), CsvMapping::default()) {
+        Err(e) => match e.kind {
+            Kind::IllegalParameter(s) => assert_eq!(s, "the CSV has no 'content' column"),
+            _ => panic!("unexpected error: {}", e),
+        },
+        Ok(_) => panic!("expected an error"),
+    }
+    Ok(())
+}
+
+/// Every row with a problem is reported with its row number, the header being row 1
+#[test]
+fn read_csv_row_problems() {
+    let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("input_items.csv");
+    let err = read_csv(File::open(fixture).unwrap(), file_mapping()).unwrap_err();
+    match err.kind {
+        Kind::IllegalParameter(s) => assert_eq!(
+            s,
+            "row 4: missing title; \
+             row 5: missing content; row 5: missing URL; row 5: missing file length; \
+             row 6: `canonical_url` must be an absolute http(s) URL ('ftp://www.example.com/5')"
+        ),
+        _ => panic!("unexpected error: {}", err),
+    }
+
+    let csv = "title,content,url,audio,bytes\na,b,https://www.example.com/a,https://x/a.mp3,-1\n";
+    let err = read_csv(csv.as_bytes(), file_mapping()).unwrap_err();
+    assert!(matches!(err.kind, Kind::IllegalParameter(s)
+        if s == "row 2: file length '-1' is not a positive number"));
+}
+
+/// A file is read from the row, with its type guessed from the URL or read from a column
+#[test]
+fn read_csv_files() -> Result<()> {
+    let csv = "title,content,url,audio,bytes,type\n\
+               a,b,https://www.example.com/a,https://www.example.com/a.mp3,1234,\n\
+               c,d,https://www.example.com/c,https://www.example.com/c,99,audio/ogg\n\
+               e,f,https://www.example.com/e,,,\n";
+    let mapping = CsvMapping {
+        file_type: Some("type".to_string()),
+        ..file_mapping()
+    };
+    let items = read_csv(csv.as_bytes(), mapping)?;
+    assert_eq!(
+        items[0].files(),
+        [AssociatedFile::new("https://www.example.com/a.mp3", 1234)?]
+    );
+    assert_eq!(items[0].files()[0].type_str, "audio/mpeg");
+    assert_eq!(items[1].files()[0].type_str, "audio/ogg");
+    assert!(items[2].files().is_empty());
+
+    let no_length = CsvMapping {
+        file_length: None,
+        ..file_mapping()
+    };
+    assert!(read_csv(csv.as_bytes(), no_length).is_err());
+    Ok(())
+}
+
+/// The client reads the file and sends its items in batches
+#[tokio::test]
+async fn new_items_from_csv_file() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+
+    let rows: String = (1..=12)
+        .map(|n| format!("title-{n},content-{n},https://www.example.com/{n}\n"))
+        .collect();
+    let csv_path = std::env::temp_dir().join(format!("yupdates-{}.csv", std::process::id()));
+    std::fs::write(&csv_path, format!("title,content,url\n{}", rows))?;
+    let feed_id = yup
+        .new_items_from_csv(&csv_path, CsvMapping::default(), 5)
+        .await;
+    std::fs::remove_file(&csv_path)?;
+    assert_eq!(feed_id?, MOCK_FEED_ID);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+
+    let missing = yup
+        .new_items_from_csv(&csv_path, CsvMapping::default(), 5)
+        .await
+        .unwrap_err();
+    assert!(matches!(missing.kind, Kind::Io(_)), "{}", missing);
+    Ok(())
+}
+
+/// Feed items are written with a header, quoting where needed, and the content only on request
+#[test]
+fn write_csv_feed_items() -> Result<()> {
+    let mut first: FeedItem = serde_json::from_value(mock_feed_item(1))?;
+    first.title = "Quotes \"and\", commas".to_string();
+    first.content = Some("<p>two\nlines</p>".to_string());
+    first.associated_files = Some(vec![
+        AssociatedFile::new("https://www.example.com/1.mp3", 1234)?,
+        AssociatedFile::new("https://www.example.com/1.png", 99)?,
+    ]);
+    let mut second: FeedItem = serde_json::from_value(mock_feed_item(2))?;
+    second.content = None;
+    let items = [first, second];
+
+    let mut csv = Vec::new();
+    write_csv(&items, &mut csv)?;
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "item_id,item_time,title,canonical_url,file_count\n\
+         item1,1661564013001.00000,\"Quotes \"\"and\"\", commas\",https://www.example.com/1,2\n\
+         item2,1661564013002.00000,title-2,https://www.example.com/2,0\n"
+    );
+
+    let mut csv = Vec::new();
+    let options = CsvExportOptions {
+        include_content: true,
+    };
+    write_csv_with_options(&items, &mut csv, &options)?;
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("item_id,item_time,title,canonical_url,file_count,content\n"));
+    assert!(csv.contains(",2,\"<p>two\nlines</p>\"\n"), "{}", csv);
+    assert!(
+        csv.ends_with(",title-2,https://www.example.com/2,0,\n"),
+        "{}",
+        csv
+    );
+
+    // What was written reads back as the same rows
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+    let rows: Vec<csv::StringRecord> = reader
+        .records()
+        .collect::<std::result::Result<_, _>>()
+        .unwrap();
+    assert_eq!(&rows[0][2], "Quotes \"and\", commas");
+    Ok(())
+}
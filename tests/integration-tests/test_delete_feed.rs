@@ -0,0 +1,121 @@
+use crate::{mock_client, MOCK_FEED_ID};
+use serde_json::json;
+use wiremock::http::Method;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::api::DeleteConfirmation;
+use yupdates::errors::{Kind, Result};
+
+/// The dry run only lists the feeds, and reports the feed that deleting would remove
+#[tokio::test]
+async fn delete_feed_and_dry_run() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/feeds/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feeds": [{"feed_id": MOCK_FEED_ID, "title": "Release notes", "item_count": 33}]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"code": 200, "message": "feed deleted"})),
+        )
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let feed = yup.delete_feed_dry_run(MOCK_FEED_ID).await?;
+    assert_eq!(feed.title, "Release notes");
+    assert_eq!(feed.item_count, Some(33));
+    let other_feed = format!("{:0>45}", 1);
+    let err = yup.delete_feed_dry_run(&other_feed).await.unwrap_err();
+    assert!(matches!(err.kind, Kind::FeedNotFound(feed_id) if feed_id == other_feed));
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests.iter().all(|r| r.method == Method::Get));
+
+    let response = yup
+        .delete_feed(MOCK_FEED_ID, DeleteConfirmation::IUnderstandThisIsPermanent)
+        .await?;
+    assert_eq!(response.message, "feed deleted");
+    Ok(())
+}
+
+/// A forbidden delete and a missing feed get their own error kinds
+#[tokio::test]
+async fn delete_feed_errors() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .set_body_json(json!({"code": 403, "error": "read-only token"})),
+        )
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    match yup
+        .delete_feed(MOCK_FEED_ID, DeleteConfirmation::IUnderstandThisIsPermanent)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::Forbidden(msg) => assert_eq!(msg, "read-only token"),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    let other_feed = format!("{:0>45}", 1);
+    match yup
+        .delete_feed(&other_feed, DeleteConfirmation::IUnderstandThisIsPermanent)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::FeedNotFound(feed_id) => assert_eq!(feed_id, other_feed),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    let err = yup
+        .delete_feed("not-a-feed", DeleteConfirmation::IUnderstandThisIsPermanent)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, Kind::IllegalParameter { .. }));
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    Ok(())
+}
+
+/// A 403 without the API's JSON keeps the body as the message
+#[tokio::test]
+async fn delete_feed_forbidden_plain_body() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(403).set_body_string("Forbidden by proxy\n"))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    match yup
+        .delete_feed(MOCK_FEED_ID, DeleteConfirmation::IUnderstandThisIsPermanent)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::Forbidden(msg) => assert_eq!(msg, "Forbidden by proxy"),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    match yup
+        .delete_item(MOCK_FEED_ID, "item1")
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::Forbidden(msg) => assert_eq!(
+            msg,
+            "deleting an item needs the feed-specific API token of its feed (Forbidden by proxy)"
+        ),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
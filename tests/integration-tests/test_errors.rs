@@ -0,0 +1,246 @@
+use reqwest::header::HeaderMap;
+use std::time::{Duration, SystemTime};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::errors::{api_error, api_error_with_headers, Error, Kind};
+
+fn error(kind: Kind) -> Error {
+    Error { kind }
+}
+
+/// The status code and its class, for each kind that has one and a few that do not
+#[test]
+fn error_status_code_kinds() {
+    let cases = [
+        (error(Kind::HttpCode(502)), Some(502)),
+        (api_error(429, r#"{"error": "slow down"}"#), Some(429)),
+        (
+            error(Kind::DetailedHttpCode(401, "no".to_string())),
+            Some(401),
+        ),
+        (error(Kind::Forbidden(String::new())), Some(403)),
+        (error(Kind::FeedNotFound("f".to_string())), Some(404)),
+        (error(Kind::ItemNotFound("i".to_string())), Some(404)),
+        (error(Kind::ItemDeleted("i".to_string())), Some(410)),
+        (
+            error(Kind::RateLimited {
+                retry_after: None,
+                message: String::new(),
+            }),
+            Some(429),
+        ),
+        (
+            error(Kind::BatchFailed(
+                1,
+                10,
+                Box::new(error(Kind::HttpCode(503))),
+            )),
+            Some(503),
+        ),
+        (
+            error(Kind::BatchFailed(
+                1,
+                10,
+                Box::new(error(Kind::Cancelled {
+                    items_sent: 0,
+                    feed_id: None,
+                })),
+            )),
+            None,
+        ),
+        (
+            error(Kind::Cancelled {
+                items_sent: 3,
+                feed_id: None,
+            }),
+            None,
+        ),
+        (error(Kind::Config("c".to_string())), None),
+        (error(Kind::Deserialization("d".to_string())), None),
+        (error(Kind::IllegalParameter("p".to_string())), None),
+        (error(Kind::IllegalResult("r".to_string())), None),
+        (error(Kind::Middleware("m".to_string())), None),
+        (error(Kind::Timeout("t".to_string())), None),
+    ];
+    for (e, code) in cases {
+        assert_eq!(e.status_code(), code, "{}", e);
+        let class = code.map(|code| code / 100);
+        assert_eq!(e.is_client_error(), class == Some(4), "{}", e);
+        assert_eq!(e.is_server_error(), class == Some(5), "{}", e);
+    }
+}
+
+/// An HTTP client error has the status of the response when there was one
+#[tokio::test]
+async fn error_status_code_reqwest() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let e: Error = reqwest::get(server.uri())
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap_err()
+        .into();
+    assert!(matches!(e.kind, Kind::Reqwest(_)));
+    assert_eq!(e.status_code(), Some(503));
+    assert!(e.is_server_error());
+
+    // Nothing listens on port 1, so there is no response and no status
+    let e: Error = reqwest::get("http://127.0.0.1:1/")
+        .await
+        .unwrap_err()
+        .into();
+    assert!(matches!(e.kind, Kind::Connect(_)));
+    assert_eq!(e.status_code(), None);
+    assert!(!e.is_client_error() && !e.is_server_error());
+}
+
+/// Whether each kind is worth retrying, and that none of them has a Retry-After
+#[test]
+fn error_is_retryable_kinds() {
+    let cases = [
+        (error(Kind::HttpCode(429)), true),
+        (api_error(429, r#"{"error": "slow down"}"#), true),
+        (error(Kind::HttpCode(500)), true),
+        (error(Kind::HttpCode(502)), true),
+        (error(Kind::DetailedHttpCode(503, "down".to_string())), true),
+        (error(Kind::HttpCode(504)), true),
+        (error(Kind::HttpCode(501)), false),
+        (error(Kind::HttpCode(400)), false),
+        (error(Kind::DetailedHttpCode(401, "no".to_string())), false),
+        (error(Kind::HttpCode(404)), false),
+        (error(Kind::Timeout("t".to_string())), false),
+        (
+            error(Kind::BatchFailed(
+                1,
+                10,
+                Box::new(error(Kind::HttpCode(429))),
+            )),
+            true,
+        ),
+        (
+            error(Kind::BatchFailed(
+                1,
+                10,
+                Box::new(error(Kind::HttpCode(422))),
+            )),
+            false,
+        ),
+        (
+            error(Kind::Cancelled {
+                items_sent: 3,
+                feed_id: None,
+            }),
+            false,
+        ),
+        (error(Kind::Config("c".to_string())), false),
+        (error(Kind::Deserialization("d".to_string())), false),
+        (error(Kind::FeedNotFound("f".to_string())), false),
+        (error(Kind::Forbidden(String::new())), false),
+        (error(Kind::IllegalParameter("p".to_string())), false),
+        (error(Kind::IllegalResult("r".to_string())), false),
+        (error(Kind::Io(std::io::Error::other("io"))), false),
+        (error(Kind::ItemDeleted("i".to_string())), false),
+        (error(Kind::ItemNotFound("i".to_string())), false),
+        (error(Kind::Middleware("m".to_string())), false),
+        (
+            error(Kind::RateLimited {
+                retry_after: None,
+                message: String::new(),
+            }),
+            true,
+        ),
+    ];
+    for (e, retryable) in cases {
+        assert_eq!(e.is_retryable(), retryable, "{}", e);
+        assert_eq!(e.retry_after(), None, "{}", e);
+    }
+}
+
+/// HTTP client errors are retryable when there was no connection or the status says so
+#[tokio::test]
+async fn error_is_retryable_reqwest() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/busy"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    for (url, retryable) in [
+        (format!("{}/busy", server.uri()), true),
+        (format!("{}/missing", server.uri()), false),
+    ] {
+        let e: Error = reqwest::get(url)
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap_err()
+            .into();
+        assert!(matches!(e.kind, Kind::Reqwest(_)));
+        assert_eq!(e.is_retryable(), retryable, "{}", e);
+    }
+
+    let e: Error = reqwest::get("http://127.0.0.1:1/")
+        .await
+        .unwrap_err()
+        .into();
+    assert!(matches!(e.kind, Kind::Connect(_)));
+    assert!(e.is_retryable());
+}
+
+fn retry_after_headers(value: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("retry-after", value.parse().unwrap());
+    headers
+}
+
+/// A 429 is rate limited, with the Retry-After in either of its forms and the API's message
+#[test]
+fn api_error_rate_limited() {
+    let body = r#"{"code": 429, "error": "slow down"}"#;
+    let e = api_error_with_headers(429, &retry_after_headers("12"), body);
+    match &e.kind {
+        Kind::RateLimited {
+            retry_after,
+            message,
+        } => {
+            assert_eq!(*retry_after, Some(Duration::from_secs(12)));
+            assert_eq!(message, "slow down");
+        }
+        kind => panic!("unexpected error type: {:?}", kind),
+    }
+    assert_eq!(e.retry_after(), Some(Duration::from_secs(12)));
+    assert_eq!(e.to_string(), "HTTP 429, retry after 12s: slow down");
+    let batch = error(Kind::BatchFailed(0, 0, Box::new(e)));
+    assert_eq!(batch.retry_after(), Some(Duration::from_secs(12)));
+
+    let later = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(120));
+    let wait = api_error_with_headers(429, &retry_after_headers(&later), body)
+        .retry_after()
+        .unwrap();
+    assert!(wait > Duration::from_secs(110) && wait <= Duration::from_secs(120));
+    let e = error(Kind::RateLimited {
+        retry_after: Some(Duration::from_millis(11_500)),
+        message: String::new(),
+    });
+    assert_eq!(e.to_string(), "HTTP 429, retry after 12s");
+    let earlier = httpdate::fmt_http_date(SystemTime::now() - Duration::from_secs(120));
+    let e = api_error_with_headers(429, &retry_after_headers(&earlier), "");
+    assert_eq!(e.retry_after(), Some(Duration::ZERO));
+    assert_eq!(e.to_string(), "HTTP 429, retry after 0s");
+
+    let e = api_error_with_headers(429, &retry_after_headers("soon"), "not JSON");
+    assert_eq!(e.retry_after(), None);
+    assert_eq!(e.to_string(), "HTTP 429");
+    assert!(matches!(
+        api_error_with_headers(503, &retry_after_headers("12"), body).kind,
+        Kind::DetailedHttpCode(503, _)
+    ));
+}
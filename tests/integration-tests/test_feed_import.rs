@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::errors::{Kind, Result};
+use yupdates::feed_import::{import_from_feed_url, items_from_feed};
+use yupdates::models::AssociatedFile;
+
+fn fixture(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name);
+    std::fs::read(path).unwrap()
+}
+
+async fn serve(body: Vec<u8>, content_type: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/feed"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, content_type))
+        .mount(&server)
+        .await;
+    server
+}
+
+/// RSS items map to input items, and items without a link are skipped
+#[tokio::test]
+async fn import_rss_feed() -> Result<()> {
+    let server = serve(fixture("import_feed.rss"), "application/rss+xml").await;
+    let feed_url = format!("{}/feed", server.uri());
+    let items = import_from_feed_url(&feed_url, 10).await?;
+    let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, ["First", "Second", "Third"]);
+    assert_eq!(items[0].canonical_url, "https://source.example.com/1");
+    assert_eq!(items[0].content, "<p>Content 1</p>");
+    assert_eq!(
+        items[0].files(),
+        [AssociatedFile::new(
+            "https://source.example.com/1.mp3",
+            1234
+        )?]
+    );
+    assert_eq!(items[1].content, "<p>Summary 2</p>");
+    assert!(items[1].files().is_empty());
+
+    let items = import_from_feed_url(&feed_url, 2).await?;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1].title, "Second");
+    Ok(())
+}
+
+/// Atom entries map the same way, using the alternate link and the content or summary
+#[tokio::test]
+async fn import_atom_feed() -> Result<()> {
+    let server = serve(fixture("import_feed.atom"), "application/atom+xml").await;
+    let items = import_from_feed_url(&format!("{}/feed", server.uri()), 10).await?;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].title, "First");
+    assert_eq!(items[0].canonical_url, "https://source.example.com/1");
+    assert_eq!(items[0].content, "<p>Content 1</p>");
+    assert_eq!(items[0].author.as_deref(), Some("Ann"));
+    assert_eq!(
+        items[0].files(),
+        [AssociatedFile::new("https://source.example.com/1.ogg", 99)?]
+    );
+    assert_eq!(items[1].title, "Second");
+    assert_eq!(items[1].canonical_url, "https://source.example.com/2");
+    assert_eq!(items[1].content, "Summary 2");
+    assert_eq!(items[1].author, None);
+    Ok(())
+}
+
+/// Documents that are not feeds, HTTP errors, and entries that cannot be converted are errors
+#[tokio::test]
+async fn import_feed_errors() {
+    let err = items_from_feed(b"<html><body>Not a feed</body></html>", 10).unwrap_err();
+    assert!(matches!(err.kind, Kind::Deserialization(_)), "{}", err);
+
+    let server = MockServer::start().await;
+    let err = import_from_feed_url(&format!("{}/feed", server.uri()), 10)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, Kind::HttpCode(404)), "{}", err);
+
+    let rss = "<rss version=\"2.0\"><channel><title>t</title><link>l</link><description>d\
+               </description><item><link>https://source.example.com/1</link></item>\
+               </channel></rss>";
+    let err = items_from_feed(rss.as_bytes(), 10).unwrap_err();
+    assert!(matches!(err.kind, Kind::IllegalParameter(_)), "{}", err);
+}
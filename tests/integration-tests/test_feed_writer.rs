@@ -0,0 +1,129 @@
+use crate::{items_received, mock_client, mock_items_server, random_test_items, MOCK_FEED_ID};
+use serde_json::Value;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::errors::Result;
+
+// The number of items in each batch that the server received
+async fn batch_sizes(server: &MockServer) -> Vec<usize> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            body["items"].as_array().unwrap().len()
+        })
+        .collect()
+}
+
+/// Full batches go out as they fill up, and close sends the rest
+#[tokio::test]
+async fn feed_writer_batches() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let mut writer = yup.feed_writer(Duration::from_secs(60));
+
+    let (items, _) = random_test_items(25);
+    for item in items {
+        writer.push(item).await?;
+    }
+    assert_eq!(batch_sizes(&server).await, [10, 10]);
+    assert_eq!(writer.buffered(), 5);
+    assert_eq!(writer.feed_id(), Some(MOCK_FEED_ID));
+
+    assert_eq!(writer.close().await?, Some(MOCK_FEED_ID.to_string()));
+    assert_eq!(batch_sizes(&server).await, [10, 10, 5]);
+    Ok(())
+}
+
+/// A partial batch goes out once the interval has passed
+#[tokio::test]
+async fn feed_writer_interval() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let mut writer = yup.feed_writer(Duration::from_millis(100));
+
+    let (items, _) = random_test_items(3);
+    let mut items = items.into_iter();
+    writer.push(items.next().unwrap()).await?;
+    writer.flush_if_due().await?;
+    assert!(batch_sizes(&server).await.is_empty());
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    writer.push(items.next().unwrap()).await?;
+    assert_eq!(batch_sizes(&server).await, [2]);
+
+    // Dropping without close still tries to send what is left
+    writer.push(items.next().unwrap()).await?;
+    drop(writer);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(batch_sizes(&server).await, [2, 1]);
+    Ok(())
+}
+
+/// After a failed batch the writer catches up a call at a time, also when dropped
+#[tokio::test]
+async fn feed_writer_recovers() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let mut writer = yup.feed_writer(Duration::from_secs(60));
+
+    let (items, _) = random_test_items(21);
+    let mut items = items.into_iter();
+    for _ in 0..9 {
+        writer.push(items.next().unwrap()).await?;
+    }
+    assert!(writer.push(items.next().unwrap()).await.is_err());
+    assert_eq!(writer.buffered(), 10);
+    assert_eq!(writer.feed_id(), None);
+    // The 11th item makes the writer send the 10 waiting items and then the new one
+    writer.push(items.next().unwrap()).await?;
+    assert_eq!(writer.buffered(), 0);
+    for item in items {
+        writer.push(item).await?;
+    }
+    assert_eq!(batch_sizes(&server).await, [10, 10, 1, 10]);
+    assert_eq!(writer.close().await?, Some(MOCK_FEED_ID.to_string()));
+
+    // Two failures leave 11 items buffered, which the drop sends in two calls
+    server.reset().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    let mut writer = yup.feed_writer(Duration::from_secs(60));
+    let (items, _) = random_test_items(11);
+    for item in items {
+        let _ = writer.push(item).await;
+    }
+    assert_eq!(writer.buffered(), 11);
+    drop(writer);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(batch_sizes(&server).await, [10, 10, 10, 1]);
+    Ok(())
+}
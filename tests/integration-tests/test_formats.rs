@@ -0,0 +1,79 @@
+//! Snapshot tests of the RSS and Atom output, these do not require API tokens.
+//!
+//! If a change to the output is deliberate, update the snapshots in `tests/formats/` with:
+//! `UPDATE_FORMAT_SNAPSHOTS=1 cargo test --features rss --test integration-tests formats`
+use crate::MOCK_FEED_ID;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use yupdates::formats::rss::{to_atom, to_rss};
+use yupdates::models::{AssociatedFile, FeedItem};
+
+const UPDATE_FORMAT_SNAPSHOTS: &str = "UPDATE_FORMAT_SNAPSHOTS";
+
+fn fixture() -> Vec<FeedItem> {
+    let item = |num: u64, title: &str, deleted: bool| FeedItem {
+        feed_id: Some(MOCK_FEED_ID.parse().unwrap()),
+        item_id: format!("item{}", num).into(),
+        input_id: format!("input{}", num).into(),
+        title: title.to_string(),
+        content: Some(format!("<p>content & more {}</p>", num)),
+        canonical_url: format!("https://www.example.com/{}?a=1&b=2", num),
+        author: None,
+        item_time: format!("{:0>13}.00000", 1661564013000 + num),
+        item_time_ms: 1661564013000 + num,
+        deleted,
+        associated_files: None,
+        extra: Default::default(),
+    };
+    let mut with_file = item(3, "Fish & <Chips>", false);
+    with_file.author = Some("Ann & Bob".to_string());
+    with_file.associated_files = Some(vec![
+        AssociatedFile {
+            url: "https://www.example.com/episode.mp3".to_string(),
+            length: 123456,
+            type_str: "audio/mpeg".to_string(),
+        },
+        AssociatedFile {
+            url: "https://www.example.com/cover.jpg".to_string(),
+            length: 2048,
+            type_str: "image/jpeg".to_string(),
+        },
+    ]);
+    let mut without_content = item(1, "Plain \"quoted\" title", false);
+    without_content.content = None;
+    vec![with_file, item(2, "Deleted item", true), without_content]
+}
+
+fn assert_snapshot(name: &str, rendered: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("formats")
+        .join(name);
+    if env::var(UPDATE_FORMAT_SNAPSHOTS).is_ok() {
+        fs::write(&path, rendered).unwrap();
+        return;
+    }
+    let snapshot = fs::read_to_string(&path).unwrap_or_default();
+    assert_eq!(rendered, snapshot, "{} does not match the snapshot", name);
+}
+
+#[test]
+fn formats_rss_snapshot() {
+    let rss = to_rss("Fish & Co", "https://www.example.com/", &fixture(), true);
+    assert!(!rss.contains("Deleted item"));
+    // RSS allows one enclosure per item
+    assert_eq!(rss.matches("<enclosure").count(), 1);
+    assert_snapshot("feed.rss", &rss);
+
+    let with_deleted = to_rss("Fish & Co", "https://www.example.com/", &fixture(), false);
+    assert!(with_deleted.contains("Deleted item"));
+}
+
+#[test]
+fn formats_atom_snapshot() {
+    let atom = to_atom("Fish & Co", "https://www.example.com/", &fixture(), true);
+    assert!(!atom.contains("Deleted item"));
+    assert_eq!(atom.matches("rel=\"enclosure\"").count(), 2);
+    assert_snapshot("feed.atom", &atom);
+}
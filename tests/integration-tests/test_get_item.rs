@@ -0,0 +1,205 @@
+use crate::{mock_client, mock_feed_item, MOCK_FEED_ID};
+use serde_json::json;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::errors::{Kind, Result};
+use yupdates::models::{AssociatedFile, FeedId, InputItem, ItemId, ItemPatch};
+
+/// Fetch one item by ID and map a 404 to the dedicated error kind
+#[tokio::test]
+async fn get_item_by_id() -> Result<()> {
+    let server = MockServer::start().await;
+    let yup = mock_client(&server);
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/items/item1/", MOCK_FEED_ID)))
+        .and(header("X-Auth-Token", "mock-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_item": mock_feed_item(1)
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/items/missing/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "code": 404,
+            "error": "Not found"
+        })))
+        .mount(&server)
+        .await;
+
+    let item = yup.get_item(MOCK_FEED_ID, "item1").await?;
+    assert_eq!(item.item_id, "item1");
+    assert_eq!(item.content, Some("content-1".to_string()));
+
+    // The feed and item IDs can be of different types
+    let feed_id = MOCK_FEED_ID.to_string();
+    assert_eq!(yup.get_item(&feed_id, "item1").await?, item);
+    let feed_id: FeedId = MOCK_FEED_ID.parse()?;
+    let item_id: ItemId = "item1".parse().unwrap();
+    assert_eq!(yup.get_item(&feed_id, &item_id).await?, item);
+
+    let result = yup.get_item(MOCK_FEED_ID, "missing").await;
+    match result.unwrap_err().kind {
+        Kind::ItemNotFound(item_id) => assert_eq!(item_id, "missing"),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+
+    // Rejected before making a call:
+    let result = yup.get_item(MOCK_FEED_ID, "../ping").await;
+    match result.unwrap_err().kind {
+        Kind::IllegalParameter(msg) => assert!(msg.contains("letters, digits, '-' and '_'")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// A token that may not delete gets an error that says which token is needed
+#[tokio::test]
+async fn delete_item_forbidden() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path(format!("/feeds/{}/items/item1/", MOCK_FEED_ID)))
+        .and(header("X-Auth-Token", "mock-token"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .set_body_json(json!({"code": 403, "error": "read-only token"})),
+        )
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    match yup
+        .delete_item(MOCK_FEED_ID, "item1")
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::Forbidden(msg) => assert_eq!(
+            msg,
+            "deleting an item needs the feed-specific API token of its feed (read-only token)"
+        ),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    let err = yup.delete_item(MOCK_FEED_ID, "item 1").await.unwrap_err();
+    assert!(matches!(err.kind, Kind::IllegalParameter { .. }));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    Ok(())
+}
+
+/// Only the fields in the patch are sent, and bad patches are rejected before any call
+#[tokio::test]
+async fn update_item_patch() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/feeds/{}/items/item1/", MOCK_FEED_ID)))
+        .and(header("X-Auth-Token", "mock-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_item": mock_feed_item(1)
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/feeds/{}/items/item2/", MOCK_FEED_ID)))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .set_body_json(json!({"code": 403, "error": "read-only token"})),
+        )
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let patch = ItemPatch {
+        title: Some("title-1".to_string()),
+        item_time: Some("1661564013001".to_string()),
+        ..Default::default()
+    };
+    let item = yup.update_item(MOCK_FEED_ID, "item1", &patch).await?;
+    assert_eq!(item.item_id, "item1");
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(
+        body,
+        json!({"title": "title-1", "item_time": "1661564013001.00000"})
+    );
+
+    match yup
+        .update_item(MOCK_FEED_ID, "item2", &patch)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::Forbidden(msg) => assert_eq!(
+            msg,
+            "updating an item needs the feed-specific API token of its feed (read-only token)"
+        ),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+
+    let whole = ItemPatch::from(&InputItem::without_url("title", "content"));
+    assert_eq!(whole.canonical_url, Some(String::new()));
+    assert_eq!(whole.associated_files, Some(vec![]));
+    let illegal = [
+        ItemPatch::default(),
+        ItemPatch {
+            title: Some(" ".to_string()),
+            ..Default::default()
+        },
+        ItemPatch {
+            canonical_url: Some("ftp://www.example.com/".to_string()),
+            ..Default::default()
+        },
+        ItemPatch {
+            item_time: Some("yesterday".to_string()),
+            ..Default::default()
+        },
+    ];
+    for patch in illegal {
+        let err = yup
+            .update_item(MOCK_FEED_ID, "item1", &patch)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err.kind, Kind::IllegalParameter { .. }),
+            "{:?}",
+            patch
+        );
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    Ok(())
+}
+
+/// A patch is checked like new items are, including the URLs of its associated files
+#[tokio::test]
+async fn update_item_patch_files() -> Result<()> {
+    let server = MockServer::start().await;
+    let yup = mock_client(&server);
+    let patch = ItemPatch {
+        title: Some("t".repeat(1025)),
+        associated_files: Some(vec![AssociatedFile {
+            url: "/episode.mp3".to_string(),
+            length: 1000,
+            type_str: "audio/mpeg".to_string(),
+        }]),
+        ..Default::default()
+    };
+    match yup
+        .update_item(MOCK_FEED_ID, "item1", &patch)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::IllegalParameter(msg) => {
+            assert!(msg.contains("the patch: `title` is 1025 bytes"), "{}", msg);
+            assert!(
+                msg.contains("the patch, file 0 (/episode.mp3): `url` must be an absolute"),
+                "{}",
+                msg
+            );
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! These tests do not require API tokens.
+use crate::mock_feed_item;
+use yupdates::errors::Result;
+use yupdates::html_text::html_to_text;
+use yupdates::models::FeedItem;
+
+/// Whitespace collapses, blocks are separated by an empty line, and lines break where they should
+#[test]
+fn html_text_layout() {
+    assert_eq!(
+        html_to_text("  <p>one\n  two </p>\n\n<p> three</p>  "),
+        "one two\n\nthree"
+    );
+    assert_eq!(html_to_text("a<b>b</b> <i>c</i>d"), "ab cd");
+    assert_eq!(
+        html_to_text("line<br>next<br/><br>after"),
+        "line\nnext\n\nafter"
+    );
+    assert_eq!(
+        html_to_text("<h2>List</h2><ul><li>one</li><li>two</ul><p>end"),
+        "List\n\none\ntwo\n\nend"
+    );
+    assert_eq!(
+        html_to_text("<table><tr><th>a</th><th>b</th></tr><tr><td>1</td><td>2</td></tr></table>"),
+        "a b\n1 2"
+    );
+    assert_eq!(
+        html_to_text("<p>code:</p><pre>  let x = 1;\n  let y = 2;\n</pre>"),
+        "code:\n\n  let x = 1;\n  let y = 2;"
+    );
+    assert_eq!(html_to_text(""), "");
+    assert_eq!(html_to_text("<p></p><div> </div>"), "");
+}
+
+/// Entities are decoded, and hidden elements and comments are left out
+#[test]
+fn html_text_entities_and_hidden() {
+    assert_eq!(
+        html_to_text("AT&amp;T &lt;b&gt; &quot;q&quot; &#233;&#x00E9; caf&eacute;"),
+        "AT&T <b> \"q\" éé café"
+    );
+    assert_eq!(html_to_text("a&nbsp;&nbsp;b"), "a\u{a0}\u{a0}b");
+    assert_eq!(
+        html_to_text(
+            "<head><title>T</title><style>p { color: red; }</style></head>\
+             <p>shown<!-- not shown --></p><script>alert(1)</script><template>t</template>"
+        ),
+        "shown"
+    );
+}
+
+/// `None` when the content was not read
+#[test]
+fn feed_item_content_text() -> Result<()> {
+    let mut item: FeedItem = serde_json::from_value(mock_feed_item(1))?;
+    item.content = Some("<p>Fish &amp; chips</p><p>today</p>".to_string());
+    assert_eq!(
+        item.content_text().as_deref(),
+        Some("Fish & chips\n\ntoday")
+    );
+    item.content = None;
+    assert_eq!(item.content_text(), None);
+    Ok(())
+}
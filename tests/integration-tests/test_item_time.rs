@@ -0,0 +1,43 @@
+//! These tests do not require API tokens.
+use yupdates::errors::Kind;
+use yupdates::{normalize_item_time, parse_item_time};
+
+/// The components come back as numbers, and normalizing formats the same components
+#[test]
+fn parse_item_time_components() {
+    let cases = [
+        ("1234", (1234, 0), "0000000001234.00000"),
+        ("1661564013555", (1661564013555, 0), "1661564013555.00000"),
+        (
+            "1661564013555.00003",
+            (1661564013555, 3),
+            "1661564013555.00003",
+        ),
+        ("123456.789", (123456, 789), "0000000123456.00789"),
+        (
+            "9999999999999.99999",
+            (9_999_999_999_999, 99_999),
+            "9999999999999.99999",
+        ),
+    ];
+    for (item_time, components, normalized) in cases {
+        assert_eq!(parse_item_time(item_time).unwrap(), components);
+        assert_eq!(normalize_item_time(item_time).unwrap(), normalized);
+    }
+}
+
+/// The bounds and error kinds are the same as for normalizing
+#[test]
+fn parse_item_time_illegal() {
+    for item_time in ["99999999999990", "123456789.100000", "12x", "1.2x", ""] {
+        let err = parse_item_time(item_time).unwrap_err();
+        assert!(
+            matches!(err.kind, Kind::IllegalParameter(_)),
+            "{}: {:?}",
+            item_time,
+            err
+        );
+    }
+    let err = parse_item_time("1.2.3").unwrap_err();
+    assert!(matches!(err.kind, Kind::Deserialization(_)), "{:?}", err);
+}
@@ -0,0 +1,83 @@
+use crate::{mock_client, mock_items_server, random_test_items};
+use yupdates::errors::{Kind, Result};
+use yupdates::limits::{
+    validate_item_sizes, MAX_CONTENT_BYTES, MAX_FILES_PER_ITEM, MAX_TITLE_BYTES,
+};
+use yupdates::models::AssociatedFile;
+
+fn problems(result: Result<()>) -> Vec<String> {
+    match result.unwrap_err().kind {
+        Kind::IllegalParameter(text) => text.split("; ").map(String::from).collect(),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+}
+
+/// Sizes are in bytes, so multi-byte characters count for more than one
+#[test]
+fn item_sizes_multi_byte() {
+    let (mut items, _) = random_test_items(3);
+
+    // 2-byte characters right up to the limit, then one byte over
+    items[0].title = "é".repeat(MAX_TITLE_BYTES / 2);
+    assert!(validate_item_sizes(&items).is_ok());
+    items[0].title.push('a');
+    // 3-byte characters that end one byte short, then a 2-byte character past the limit
+    items[1].content = "€".repeat(MAX_CONTENT_BYTES / 3);
+    assert_eq!(items[1].content.len(), MAX_CONTENT_BYTES - 1);
+    items[1].content.push('é');
+    let file = AssociatedFile {
+        url: "https://www.example.com/1.mp3".to_string(),
+        length: 1234,
+        type_str: "audio/mpeg".to_string(),
+    };
+    items[2].associated_files = Some(vec![file; MAX_FILES_PER_ITEM + 1]);
+
+    assert_eq!(
+        problems(validate_item_sizes(&items)),
+        vec![
+            format!(
+                "item 0: `title` is {} bytes, more than the limit of {}",
+                MAX_TITLE_BYTES + 1,
+                MAX_TITLE_BYTES
+            ),
+            format!(
+                "item 1: `content` is {} bytes, more than the limit of {}",
+                MAX_CONTENT_BYTES + 1,
+                MAX_CONTENT_BYTES
+            ),
+            format!(
+                "item 2: `associated_files` is {} files, more than the limit of {}",
+                MAX_FILES_PER_ITEM + 1,
+                MAX_FILES_PER_ITEM
+            ),
+        ]
+    );
+}
+
+/// Oversized items fail before anything is sent, unless the checks are skipped
+#[tokio::test]
+async fn item_sizes_checked_before_send() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let (mut items, _) = random_test_items(12);
+    items[11].title = "🦀".repeat(MAX_TITLE_BYTES / 4 + 1);
+
+    let expected = format!(
+        "item 11: `title` is {} bytes, more than the limit of {}",
+        MAX_TITLE_BYTES + 4,
+        MAX_TITLE_BYTES
+    );
+    assert_eq!(
+        problems(yup.new_items(&items[9..]).await.map(|_| ())),
+        vec![expected.replace("item 11", "item 2")]
+    );
+    assert_eq!(
+        problems(yup.new_items_all(&items, 5).await.map(|_| ())),
+        vec![expected]
+    );
+    assert!(server.received_requests().await.unwrap().is_empty());
+
+    yup.new_items_unchecked(&items[9..]).await?;
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    Ok(())
+}
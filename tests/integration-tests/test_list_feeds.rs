@@ -0,0 +1,68 @@
+use crate::{mock_client, MOCK_FEED_ID};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::errors::{Kind, Result};
+
+fn fixture(name: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// Every page is read, and fields the API leaves out are empty
+#[tokio::test]
+async fn list_feeds_paging() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/feeds/"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("list_feeds_page1.json")))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/feeds/"))
+        .and(query_param("cursor", "page2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("list_feeds_page2.json")))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let feeds = yup.list_feeds().await?;
+    assert_eq!(feeds.len(), 3);
+    assert_eq!(feeds[0].typed_feed_id()?.as_str(), MOCK_FEED_ID);
+    assert_eq!(feeds[0].title, "Release notes");
+    assert_eq!(feeds[0].created_time_ms, Some(1661564013000));
+    assert_eq!(feeds[0].item_count, Some(33));
+    assert_eq!(feeds[1].extra["visibility"], "private");
+    assert_eq!(feeds[2].title, "");
+    assert_eq!(feeds[2].item_count, None);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    Ok(())
+}
+
+/// A cursor that repeats would page forever, so it is an error
+#[tokio::test]
+async fn list_feeds_repeated_cursor() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/feeds/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feeds": [],
+            "next_cursor": "again"
+        })))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    match yup.list_feeds().await.unwrap_err().kind {
+        Kind::IllegalResult(text) => assert!(text.contains("'again'")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    Ok(())
+}
@@ -0,0 +1,108 @@
+//! These tests do not require API tokens.
+use yupdates::api::validate_associated_files;
+use yupdates::markdown::{markdown_to_html, MarkdownOptions};
+use yupdates::models::{AssociatedFile, InputItem};
+
+fn html(markdown: &str) -> String {
+    markdown_to_html(markdown, &Default::default())
+}
+
+/// Links keep safe URLs, anything else loses its URL
+#[test]
+fn markdown_links() {
+    assert_eq!(
+        html("[a](https://www.example.com/?a=1&b=2 \"A\") [b](/b) [c](mailto:c@example.com)"),
+        "<p><a href=\"https://www.example.com/?a=1&amp;b=2\" title=\"A\">a</a> \
+         <a href=\"/b\">b</a> <a href=\"mailto:c@example.com\">c</a></p>\n"
+    );
+    assert_eq!(
+        html("[x](javascript:alert(1)) [y](<JavaScript\t:alert(1)>) ![z](data:image/png,x)"),
+        "<p><a href=\"\">x</a> <a href=\"\">y</a> <img src=\"\" alt=\"z\" /></p>\n"
+    );
+}
+
+/// Code is escaped, in blocks and inline
+#[test]
+fn markdown_code() {
+    assert_eq!(
+        html("```rust\nlet x = a < b && c;\n```\n\nRun `<b>`."),
+        "<pre><code class=\"language-rust\">let x = a &lt; b &amp;&amp; c;\n</code></pre>\n\
+         <p>Run <code>&lt;b&gt;</code>.</p>\n"
+    );
+}
+
+/// Raw HTML shows as text, and script and style blocks are left out
+#[test]
+fn markdown_raw_html() {
+    assert_eq!(
+        html("Hello <b onclick=\"x()\">there</b>\n\n<div>block</div>"),
+        "<p>Hello &lt;b onclick=\"x()\"&gt;there&lt;/b&gt;</p>\n\
+         <p>&lt;div&gt;block&lt;/div&gt;</p>\n"
+    );
+    assert_eq!(
+        html("before\n\n<script>\nalert(1)\n</script>\n\n<STYLE>p {}</STYLE>\n\nafter"),
+        "<p>before</p>\n<p>after</p>\n"
+    );
+    assert_eq!(
+        html("inline <script>alert(1)</script>"),
+        "<p>inline &lt;script&gt;alert(1)&lt;/script&gt;</p>\n"
+    );
+}
+
+/// Tables and footnotes are only rendered when enabled
+#[test]
+fn markdown_options() {
+    let markdown = "| a |\n|---|\n| 1 |\n\nNote[^1]\n\n[^1]: The note";
+    let plain = html(markdown);
+    assert!(!plain.contains("<table>"));
+    assert!(!plain.contains("footnote"));
+
+    let options = MarkdownOptions {
+        tables: true,
+        footnotes: true,
+        ..Default::default()
+    };
+    let rendered = markdown_to_html(markdown, &options);
+    assert!(rendered.contains("<table>"), "{}", rendered);
+    assert!(rendered.contains("<td>1</td>"), "{}", rendered);
+    assert!(
+        rendered.contains("class=\"footnote-definition\""),
+        "{}",
+        rendered
+    );
+}
+
+/// The first image with a known type can become an associated file, which needs its length
+#[test]
+fn markdown_first_image() {
+    let markdown = "![logo](/logo.png) ![a](https://cdn.example.com/a.bin) \
+                    ![b](https://cdn.example.com/b.JPG?w=100) ![c](https://cdn.example.com/c.png)";
+    let item = InputItem::from_markdown("title", markdown, "https://www.example.com/1");
+    assert_eq!(item.associated_files, None);
+    assert_eq!(item.canonical_url, "https://www.example.com/1");
+
+    let options = MarkdownOptions {
+        first_image_as_file: true,
+        ..Default::default()
+    };
+    let mut item = InputItem::from_markdown_with_options(
+        "title",
+        markdown,
+        "https://www.example.com/1",
+        &options,
+    );
+    let file = AssociatedFile {
+        url: "https://cdn.example.com/b.JPG?w=100".to_string(),
+        length: 0,
+        type_str: "image/jpeg".to_string(),
+    };
+    assert_eq!(item.associated_files, Some(vec![file]));
+    assert!(item
+        .content
+        .contains("<img src=\"/logo.png\" alt=\"logo\" />"));
+
+    let items = std::slice::from_mut(&mut item);
+    assert!(validate_associated_files(items).is_err());
+    items[0].associated_files.as_mut().unwrap()[0].length = 2048;
+    assert!(validate_associated_files(items).is_ok());
+}
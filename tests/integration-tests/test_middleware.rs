@@ -0,0 +1,121 @@
+use crate::{mock_client, mock_feed_items_response, MOCK_FEED_ID};
+use reqwest::{Request, Response};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, Middleware, Next};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use task_local_extensions::Extensions;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::api::read_items_with_args;
+use yupdates::errors::{Kind, Result};
+use yupdates::middleware::with_middleware;
+
+// Counts the requests it sees and marks them with a header
+#[derive(Default)]
+struct Marker {
+    seen: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl Middleware for Marker {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        self.seen.fetch_add(1, Ordering::SeqCst);
+        req.headers_mut()
+            .insert("x-middleware", "marked".parse().unwrap());
+        next.run(req, extensions).await
+    }
+}
+
+struct Refuse;
+
+#[async_trait::async_trait]
+impl Middleware for Refuse {
+    async fn handle(
+        &self,
+        _: Request,
+        _: &mut Extensions,
+        _: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        Err(reqwest_middleware::Error::Middleware(anyhow::anyhow!(
+            "circuit open"
+        )))
+    }
+}
+
+async fn marked_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .and(header("x-middleware", "marked"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(mock_feed_items_response(&[1, 2])))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn marker_client(marker: &Arc<Marker>) -> ClientWithMiddleware {
+    ClientBuilder::new(reqwest::Client::new())
+        .with_arc(marker.clone())
+        .build()
+}
+
+/// Calls of a client with middleware go through it, including those of clones and copies
+#[tokio::test]
+async fn middleware_client_calls() -> Result<()> {
+    let server = marked_server().await;
+    let marker = Arc::new(Marker::default());
+    let yup = mock_client(&server).with_middleware(marker_client(&marker));
+
+    assert_eq!(yup.read_items(MOCK_FEED_ID).await?.len(), 2);
+    yup.clone().read_items(MOCK_FEED_ID).await?;
+    assert_eq!(marker.seen.load(Ordering::SeqCst), 2);
+
+    // Without the header the mock does not match
+    assert!(mock_client(&server).read_items(MOCK_FEED_ID).await.is_err());
+    assert_eq!(marker.seen.load(Ordering::SeqCst), 2);
+    Ok(())
+}
+
+/// A `_with_args` call goes through the middleware it runs in
+#[tokio::test]
+async fn middleware_with_args_calls() -> Result<()> {
+    let server = marked_server().await;
+    let marker = Arc::new(Marker::default());
+    let base_url = format!("{}/", server.uri());
+    let items = with_middleware(
+        &marker_client(&marker),
+        read_items_with_args(
+            MOCK_FEED_ID,
+            None,
+            &reqwest::Client::new(),
+            &base_url,
+            "mock-token",
+        ),
+    )
+    .await?;
+    assert_eq!(items.len(), 2);
+    assert_eq!(marker.seen.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+/// A middleware that fails the call is a Middleware error
+#[tokio::test]
+async fn middleware_error() -> Result<()> {
+    let server = marked_server().await;
+    let refusing = ClientBuilder::new(reqwest::Client::new())
+        .with(Refuse)
+        .build();
+    let yup = mock_client(&server).with_middleware(refusing);
+    let err = yup.read_items(MOCK_FEED_ID).await.unwrap_err();
+    match err.kind {
+        Kind::Middleware(s) => assert_eq!(s, "circuit open"),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+    Ok(())
+}
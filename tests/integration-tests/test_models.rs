@@ -0,0 +1,720 @@
+//! These tests only exercise client-side logic and do not require API tokens.
+use crate::{mock_feed_item, MOCK_FEED_ID};
+use crate::{random_ascii_string, random_test_items};
+use rand::seq::SliceRandom;
+use serde_json::json;
+use std::env;
+use std::fs;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::api::{
+    chunk_items, validate_associated_files, validate_associated_files_with, validate_items,
+    verify_files, FileValidation, ItemResult, ItemStatus, NewInputItemsResponse,
+    MAX_ASSOCIATED_FILE_LENGTH, MAX_ITEMS_PER_CALL,
+};
+use yupdates::errors::{Kind, Result};
+use yupdates::limits::{MAX_CONTENT_BYTES, MAX_FILES_PER_ITEM};
+use yupdates::models::{AssociatedFile, FeedId, FeedItem, InputId, InputItem, ItemId, MediaKind};
+use yupdates::{api_token_from_file, validate_feed_id, validate_token};
+
+/// Feed IDs are validated once and then usable anywhere a feed ID string is
+#[test]
+fn feed_id_parsing() -> Result<()> {
+    let feed_id: FeedId = MOCK_FEED_ID.parse()?;
+    assert_eq!(feed_id.as_str(), MOCK_FEED_ID);
+    assert_eq!(feed_id.to_string(), MOCK_FEED_ID);
+    assert_eq!(FeedId::new(format!(" {}\n", MOCK_FEED_ID))?, feed_id);
+    assert_eq!(FeedId::try_from(MOCK_FEED_ID)?, feed_id);
+    assert!(validate_feed_id(MOCK_FEED_ID).is_ok());
+
+    for illegal in [
+        "",
+        "02fb24a4",
+        "02fb24a4478462a4491067224b66d9a8b2338ddca2737a",
+    ] {
+        match FeedId::new(illegal).unwrap_err().kind {
+            Kind::IllegalParameter(text) => assert!(text.contains("45 characters")),
+            e => panic!("unexpected error type: {:?}", e),
+        }
+        assert!(validate_feed_id(illegal).is_err());
+    }
+    match FeedId::new("02fb24a4478462a4491067224b66d9a8b2338ddca27/7")
+        .unwrap_err()
+        .kind
+    {
+        Kind::IllegalParameter(text) => assert!(text.contains("alphanumeric")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// The typed IDs are plain strings in JSON, and a feed ID is validated when it is deserialized
+#[test]
+fn typed_ids() -> Result<()> {
+    let item: FeedItem = serde_json::from_value(mock_feed_item(1))?;
+    assert_eq!(item.feed_id, Some(FeedId::new(MOCK_FEED_ID)?));
+    assert_eq!(item.item_id, ItemId::from("item1"));
+    assert_eq!(item.input_id, "input1");
+    assert_eq!("item1".parse::<ItemId>(), Ok(item.item_id.clone()));
+    assert_eq!(serde_json::to_value(&item)?, mock_feed_item(1));
+    assert_eq!(String::from(InputId::from("input1".to_string())), "input1");
+
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct Ids {
+        feed_id: FeedId,
+        item_id: ItemId,
+        input_id: InputId,
+    }
+    let value = json!({"feed_id": MOCK_FEED_ID, "item_id": "item1", "input_id": "input1"});
+    let ids: Ids = serde_json::from_value(value.clone())?;
+    assert_eq!(item.feed_id.as_ref(), Some(&ids.feed_id));
+    assert_eq!(ids.item_id.as_ref(), "item1");
+    assert_eq!(serde_json::to_value(&ids)?, value);
+
+    let value = json!({"feed_id": "02fb24a4", "item_id": "item1", "input_id": "input1"});
+    let err = serde_json::from_value::<Ids>(value).unwrap_err();
+    assert!(err.to_string().contains("45 characters"), "{}", err);
+    Ok(())
+}
+
+/// Feed IDs can be pulled out of the feed URLs that the application shows
+#[test]
+fn feed_id_from_url() -> Result<()> {
+    let expected = FeedId::new(MOCK_FEED_ID)?;
+    for url in [
+        format!("https://feeds.yupdates.com/rss/{}/", MOCK_FEED_ID),
+        format!("https://feeds.yupdates.com/atom/{}/", MOCK_FEED_ID),
+        format!("https://feeds.yupdates.com/json/{}/", MOCK_FEED_ID),
+        format!("https://feeds.yupdates.com/rss/{}", MOCK_FEED_ID),
+        format!("https://feeds.yupdates.com/rss/{}/?x=1&y=2", MOCK_FEED_ID),
+        format!(" https://feeds.yupdates.com/atom/{}?x=1\n", MOCK_FEED_ID),
+    ] {
+        assert_eq!(FeedId::from_url(&url)?, expected, "{}", url);
+    }
+
+    // The error shows the part of the URL that was examined:
+    match FeedId::from_url("https://feeds.yupdates.com/rss/02fb24a4/")
+        .unwrap_err()
+        .kind
+    {
+        Kind::IllegalParameter(text) => {
+            assert!(text.contains("examined '02fb24a4'"));
+            assert!(text.contains("45 characters"));
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    match FeedId::from_url(format!("https://feeds.yupdates.com/xml/{}/", MOCK_FEED_ID))
+        .unwrap_err()
+        .kind
+    {
+        Kind::IllegalParameter(text) => {
+            assert!(text.contains(&format!("examined '/xml/{}/'", MOCK_FEED_ID)))
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    assert!(FeedId::from_url(MOCK_FEED_ID).is_err());
+    Ok(())
+}
+
+/// The MIME type is `type` on the wire
+#[test]
+fn associated_file_type_field() -> Result<()> {
+    let file = AssociatedFile {
+        url: "https://www.example.com/file.mp3".to_string(),
+        length: 1234,
+        type_str: "audio/mpeg".to_string(),
+    };
+    let serialized = serde_json::to_value(&file)?;
+    assert_eq!(serialized["type"], "audio/mpeg");
+    assert!(serialized.get("type_str").is_none());
+
+    let deserialized: AssociatedFile = serde_json::from_value(json!({
+        "url": "https://www.example.com/file.mp3",
+        "length": 1234,
+        "type": "audio/mpeg"
+    }))?;
+    assert_eq!(deserialized, file);
+    Ok(())
+}
+
+/// The MIME type is guessed from the extension, and only http(s) URLs are accepted
+#[test]
+fn associated_file_new() -> Result<()> {
+    for (url, expected) in [
+        ("https://www.example.com/episode.mp3", "audio/mpeg"),
+        ("https://www.example.com/episode.m4a", "audio/mp4"),
+        ("http://www.example.com/files/report.pdf", "application/pdf"),
+        ("https://www.example.com/EPISODE.MP3", "audio/mpeg"),
+        (
+            "https://www.example.com/Photo.JpEg?size=large#top",
+            "image/jpeg",
+        ),
+        (
+            "https://www.example.com/file.xyz",
+            "application/octet-stream",
+        ),
+        ("https://www.example.com/file", "application/octet-stream"),
+        ("https://www.example.mp3/", "application/octet-stream"),
+    ] {
+        let file = AssociatedFile::new(url, 1234)?;
+        assert_eq!(file.url, url);
+        assert_eq!(file.length, 1234);
+        assert_eq!(file.type_str, expected, "{}", url);
+    }
+
+    let file =
+        AssociatedFile::new("https://www.example.com/file.xyz", 1234)?.with_type("audio/x-custom");
+    assert_eq!(file.type_str, "audio/x-custom");
+    assert_eq!(file.media_kind(), MediaKind::Audio);
+
+    for url in [
+        "ftp://www.example.com/file.mp3",
+        "/file.mp3",
+        "file.mp3",
+        "",
+    ] {
+        match AssociatedFile::new(url, 1234).unwrap_err().kind {
+            Kind::IllegalParameter(msg) => assert!(msg.contains("http(s)"), "{}", msg),
+            e => panic!("unexpected error type: {:?}", e),
+        }
+    }
+    Ok(())
+}
+
+/// Files are classified by their MIME type, which stays a string on the wire
+#[test]
+fn associated_file_media_kind() -> Result<()> {
+    for (mime, expected) in [
+        ("audio/mpeg", MediaKind::Audio),
+        ("Audio/MP4", MediaKind::Audio),
+        ("video/mp4", MediaKind::Video),
+        ("image/png", MediaKind::Image),
+        ("text/html; charset=utf-8", MediaKind::Document),
+        ("application/pdf", MediaKind::Document),
+        (
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            MediaKind::Document,
+        ),
+        (
+            "application/zip",
+            MediaKind::Other("application/zip".to_string()),
+        ),
+        ("audio/", MediaKind::Other("audio/".to_string())),
+        ("audio", MediaKind::Other("audio".to_string())),
+        ("", MediaKind::Other("".to_string())),
+    ] {
+        let file = AssociatedFile {
+            url: "https://www.example.com/file".to_string(),
+            length: 1234,
+            type_str: mime.to_string(),
+        };
+        assert_eq!(file.media_kind(), expected, "{}", mime);
+        assert_eq!(serde_json::to_value(&file)?["type"], mime);
+    }
+    Ok(())
+}
+
+/// Associated files need a plausible length and a `type/subtype` MIME type
+#[test]
+fn associated_file_validation() {
+    let (mut items, _) = random_test_items(3);
+    let file = |length: u64, mime: &str| AssociatedFile {
+        url: "https://www.example.com/file.mp3".to_string(),
+        length,
+        type_str: mime.to_string(),
+    };
+    items[2].associated_files = Some(vec![file(1234, "audio/mpeg")]);
+    assert!(validate_associated_files(&items).is_ok());
+
+    for bad in [
+        file(0, "audio/mpeg"),
+        file(MAX_ASSOCIATED_FILE_LENGTH + 1, "audio/mpeg"),
+        file(1234, "audio"),
+        file(1234, "audio/"),
+        file(1234, "/mpeg"),
+        file(1234, ""),
+        file(1234, "audio/mpeg mp3"),
+    ] {
+        items[1].associated_files = Some(vec![bad.clone()]);
+        match validate_associated_files(&items).unwrap_err().kind {
+            Kind::IllegalParameter(text) => {
+                assert!(text.starts_with("item 1, file 0 (https://www.example.com/file.mp3):"));
+            }
+            e => panic!("unexpected error type for {:?}: {:?}", bad, e),
+        }
+    }
+
+    items[1].associated_files = Some(vec![file(1, "text/html; charset=utf-8")]);
+    assert!(validate_associated_files(&items).is_ok());
+
+    // The file index is the position in the item's files
+    let mut bad_url = file(1234, "audio/mpeg");
+    bad_url.url = "www.example.com/file.mp3".to_string();
+    items[1].associated_files = Some(vec![file(1234, "audio/mpeg"), bad_url]);
+    match validate_associated_files(&items).unwrap_err().kind {
+        Kind::IllegalParameter(text) => {
+            assert_eq!(
+                text,
+                "item 1, file 1 (www.example.com/file.mp3): `url` must be an absolute http(s) URL"
+            );
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+
+    items[1].associated_files = Some(vec![file(0, "audio/mpeg")]);
+    let validation = FileValidation {
+        allow_unknown_length: true,
+    };
+    assert!(validate_associated_files_with(&items, &validation).is_ok());
+    items[1].associated_files = Some(vec![file(MAX_ASSOCIATED_FILE_LENGTH + 1, "audio/mpeg")]);
+    assert!(validate_associated_files_with(&items, &validation).is_err());
+}
+
+/// HEAD requests find files that are missing or whose length is wrong
+#[tokio::test]
+async fn verify_files_head() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/episode.mp3"))
+        .respond_with(ResponseTemplate::new(200).insert_header("content-length", "1234"))
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path("/missing.mp3"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    let file = |name: &str, length: u64| AssociatedFile {
+        url: format!("{}/{}", server.uri(), name),
+        length,
+        type_str: "audio/mpeg".to_string(),
+    };
+    let (mut items, _) = random_test_items(2);
+    items[0].associated_files = Some(vec![file("episode.mp3", 1234)]);
+    items[1].associated_files = Some(vec![file("episode.mp3", 0)]);
+    verify_files(&items).await?;
+
+    items[1].associated_files = Some(vec![file("episode.mp3", 1000), file("missing.mp3", 1)]);
+    let text = match verify_files(&items).await.unwrap_err().kind {
+        Kind::IllegalParameter(text) => text,
+        e => panic!("unexpected error type: {:?}", e),
+    };
+    let problems: Vec<&str> = text.split("; ").collect();
+    assert_eq!(problems.len(), 2, "{}", text);
+    assert!(problems[0].starts_with("item 1, file 0 ("));
+    assert!(problems[0].ends_with("`length` is 1000, but the server reports 1234 bytes"));
+    assert!(problems[1].starts_with("item 1, file 1 ("));
+    assert!(problems[1].ends_with("HEAD returned HTTP 404"));
+    Ok(())
+}
+
+/// Every problem in every item is reported, not just the first
+#[test]
+fn input_item_validation() {
+    let (mut items, _) = random_test_items(4);
+    assert!(validate_items(&items).is_ok());
+
+    items[0].title = " ".to_string();
+    items[2].canonical_url = "www.example.com/2".to_string();
+    items[2].content = "x".repeat(MAX_CONTENT_BYTES + 1);
+    items[3].canonical_url = "ftp://www.example.com/3".to_string();
+    items[3].associated_files = Some(vec![AssociatedFile {
+        url: "https://www.example.com/3.mp3".to_string(),
+        length: 0,
+        type_str: "audio".to_string(),
+    }]);
+    let text = match validate_items(&items).unwrap_err().kind {
+        Kind::IllegalParameter(text) => text,
+        e => panic!("unexpected error type: {:?}", e),
+    };
+    let problems: Vec<&str> = text.split("; ").collect();
+    assert_eq!(problems.len(), 6, "{}", text);
+    assert!(problems[0].starts_with("item 0: `title`"));
+    assert!(problems[1].starts_with("item 2: `canonical_url`"));
+    assert!(problems[2].starts_with("item 2: `content`"));
+    assert!(problems[3].starts_with("item 3: `canonical_url`"));
+    assert!(problems[4].starts_with("item 3, file 0 (https://www.example.com/3.mp3): `length`"));
+    assert!(problems[5].starts_with("item 3, file 0 (https://www.example.com/3.mp3): `type`"));
+}
+
+/// Fields the SDK does not model survive a deserialize and serialize round trip
+#[test]
+fn unknown_fields_round_trip() -> Result<()> {
+    let mut value = mock_feed_item(1);
+    value["foo"] = json!({"bar": [1, 2]});
+    let item: FeedItem = serde_json::from_value(value.clone())?;
+    assert_eq!(item.extra["foo"], json!({"bar": [1, 2]}));
+    assert_eq!(serde_json::to_value(&item)?, value);
+
+    let value = json!({
+        "title": "title-1",
+        "content": "content-1",
+        "canonical_url": "https://www.example.com/1",
+        "associated_files": null,
+        "foo": "bar"
+    });
+    let item: InputItem = serde_json::from_value(value.clone())?;
+    assert_eq!(item.extra["foo"], "bar");
+    assert_eq!(serde_json::to_value(&item)?, value);
+    Ok(())
+}
+
+/// New items responses without `item_results` still parse, and unknown statuses are tolerated
+#[test]
+fn new_items_response_item_results() -> Result<()> {
+    let value = json!({"code": 200, "feed_id": MOCK_FEED_ID, "message": "items received"});
+    let response: NewInputItemsResponse = serde_json::from_value(value.clone())?;
+    assert_eq!(response.item_results, None);
+    assert_eq!(serde_json::to_value(&response)?, value);
+
+    let value = json!({
+        "code": 200,
+        "feed_id": MOCK_FEED_ID,
+        "message": "items received",
+        "item_results": [
+            {"index": 0, "status": "accepted", "item_id": "item1", "input_id": "input1"},
+            {"index": 1, "status": "rejected", "message": "title is too long"},
+            {"index": 2, "status": "postponed"}
+        ]
+    });
+    let response: NewInputItemsResponse = serde_json::from_value(value)?;
+    let results = response.item_results.unwrap();
+    assert_eq!(
+        results[0],
+        ItemResult {
+            index: 0,
+            status: ItemStatus::Accepted,
+            item_id: Some("item1".to_string()),
+            input_id: Some("input1".to_string()),
+            message: None,
+        }
+    );
+    assert_eq!(results[1].status, ItemStatus::Rejected);
+    assert_eq!(results[1].message.as_deref(), Some("title is too long"));
+    assert_eq!(results[2].status, ItemStatus::Other);
+    Ok(())
+}
+
+/// `author` round trips when it is set and is left out when it is not
+#[test]
+fn author_round_trip() -> Result<()> {
+    let mut value = mock_feed_item(1);
+    let item: FeedItem = serde_json::from_value(value.clone())?;
+    assert_eq!(item.author, None);
+    assert_eq!(serde_json::to_value(&item)?, value);
+
+    value["author"] = json!("Ann");
+    let item: FeedItem = serde_json::from_value(value.clone())?;
+    assert_eq!(item.author.as_deref(), Some("Ann"));
+    assert_eq!(serde_json::to_value(&item)?, value);
+
+    let input_item = item.to_input_item();
+    assert_eq!(input_item.author.as_deref(), Some("Ann"));
+    assert_eq!(serde_json::to_value(&input_item)?["author"], "Ann");
+    let (items, _) = random_test_items(1);
+    assert!(serde_json::to_value(&items[0])?.get("author").is_none());
+    Ok(())
+}
+
+/// Tokens can come from mounted secret files
+#[test]
+fn token_from_file() -> Result<()> {
+    let path = env::temp_dir().join(format!("yupdates-token-{}", random_ascii_string(10)));
+    fs::write(&path, "789a4e8703:78b15453350458054b84443819060b1a\n").unwrap();
+    let token = api_token_from_file(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(token?, "789a4e8703:78b15453350458054b84443819060b1a");
+
+    match api_token_from_file(&path).unwrap_err().kind {
+        Kind::Config(text) => assert!(text.contains("could not read the API token")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// Tokens are trimmed, and empty or garbled tokens are rejected up front
+#[test]
+fn token_validation() -> Result<()> {
+    assert_eq!(
+        validate_token("789a4e8703:78b15453\n")?,
+        "789a4e8703:78b15453"
+    );
+    assert_eq!(
+        validate_token("  789a4e8703:78b15453\r\n")?,
+        "789a4e8703:78b15453"
+    );
+    for illegal in ["", " \n", "789a4e8703: 78b15453", "789a4e8703:78b1\u{e9}"] {
+        assert!(matches!(
+            validate_token(illegal).unwrap_err().kind,
+            Kind::Config { .. }
+        ));
+    }
+
+    let path = env::temp_dir().join(format!("yupdates-token-{}", random_ascii_string(10)));
+    fs::write(&path, "\n").unwrap();
+    let token = api_token_from_file(&path);
+    fs::remove_file(&path).unwrap();
+    match token.unwrap_err().kind {
+        Kind::Config(text) => assert!(text.contains("empty")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// Input items are batched in order, at most [MAX_ITEMS_PER_CALL] per batch
+#[test]
+fn chunk_items_sizes() {
+    for (count, expected) in [
+        (0, vec![]),
+        (10, vec![10]),
+        (11, vec![10, 1]),
+        (25, vec![10, 10, 5]),
+    ] {
+        let (items, _) = random_test_items(count);
+        let sizes: Vec<usize> = chunk_items(&items).map(|chunk| chunk.len()).collect();
+        assert_eq!(sizes, expected);
+    }
+    let (items, _) = random_test_items(11);
+    assert_eq!(
+        chunk_items(&items).last().unwrap()[0],
+        items[MAX_ITEMS_PER_CALL]
+    );
+}
+
+/// A feed item maps back to an input item, and missing content becomes an empty string
+#[test]
+fn feed_item_to_input_item() -> Result<()> {
+    let mut value = mock_feed_item(1);
+    value["foo"] = json!("bar");
+    let mut item: FeedItem = serde_json::from_value(value)?;
+    item.associated_files = Some(vec![AssociatedFile {
+        url: "https://www.example.com/1.mp3".to_string(),
+        length: 100,
+        type_str: "audio/mpeg".to_string(),
+    }]);
+
+    let input = item.to_input_item();
+    assert_eq!(input.title, item.title);
+    assert_eq!(input.content, item.content.clone().unwrap());
+    assert_eq!(input.canonical_url, item.canonical_url);
+    assert_eq!(input.associated_files, item.associated_files);
+    assert!(input.extra.is_empty());
+    assert_eq!(InputItem::from(item.clone()), input);
+
+    item.content = None;
+    assert_eq!(InputItem::from(item).content, "");
+    Ok(())
+}
+
+/// Items display as a stable one-line summary
+#[test]
+fn feed_item_display() -> Result<()> {
+    let mut item: FeedItem = serde_json::from_value(mock_feed_item(1))?;
+    assert_eq!(
+        item.to_string(),
+        "2022-08-27 01:33:33 UTC  title-1  <https://www.example.com/1>"
+    );
+
+    // The item time is used when `item_time_ms` was not read
+    item.item_time_ms = 0;
+    item.title = "two\nlines".to_string();
+    item.deleted = true;
+    assert_eq!(
+        item.to_string(),
+        "2022-08-27 01:33:33 UTC  two lines  <https://www.example.com/1> (deleted)"
+    );
+    Ok(())
+}
+
+/// Previews are cut between characters, never inside a tag or entity, and end with an ellipsis
+#[test]
+fn feed_item_content_preview() -> Result<()> {
+    let mut item: FeedItem = serde_json::from_value(mock_feed_item(1))?;
+    let preview = |item: &mut FeedItem, content: &str, max_chars: usize| {
+        item.content = Some(content.to_string());
+        item.content_preview(max_chars).unwrap()
+    };
+    assert_eq!(preview(&mut item, "short", 5), "short");
+    assert_eq!(preview(&mut item, "short", 50), "short");
+    assert_eq!(preview(&mut item, "short", 4), "shor…");
+    assert_eq!(preview(&mut item, "short", 0), "…");
+    assert_eq!(preview(&mut item, "", 0), "");
+
+    // Multibyte characters, which byte slicing would split
+    assert_eq!(preview(&mut item, "héllo wörld", 8), "héllo wö…");
+    assert_eq!(preview(&mut item, "🎉🎉🎉🎉", 2), "🎉🎉…");
+
+    // A skin tone modifier, a combining accent, and a joined family stay with their base
+    assert_eq!(preview(&mut item, "👍🏽👍🏽👍🏽", 3), "👍🏽…");
+    assert_eq!(preview(&mut item, "👍🏽👍🏽👍🏽", 4), "👍🏽👍🏽…");
+    assert_eq!(
+        preview(&mut item, "e\u{301}e\u{301}e\u{301}", 3),
+        "e\u{301}…"
+    );
+    assert_eq!(preview(&mut item, "a 👨\u{200D}👩\u{200D}👧 b", 4), "a …");
+
+    // Tags and entities are cut before they start
+    let html = "<p>Hello <a href=\"https://www.example.com/\">world</a></p>";
+    assert_eq!(preview(&mut item, html, 12), "<p>Hello …");
+    assert_eq!(
+        preview(&mut item, html, 49),
+        "<p>Hello <a href=\"https://www.example.com/\">world…"
+    );
+    assert_eq!(preview(&mut item, "Fish &amp; chips", 7), "Fish …");
+    assert_eq!(preview(&mut item, "Fish &amp; chips", 11), "Fish &amp; …");
+    assert_eq!(preview(&mut item, "AT&T rocks", 4), "AT&T…");
+
+    item.content = None;
+    assert_eq!(item.content_preview(10), None);
+    Ok(())
+}
+
+/// Items sort chronologically, by the item time suffix, and then by item ID
+#[test]
+fn feed_item_ordering() -> Result<()> {
+    let item = |item_id: &str, item_time: &str| -> Result<FeedItem> {
+        let mut value = mock_feed_item(1);
+        value["item_id"] = json!(item_id);
+        value["item_time"] = json!(item_time);
+        Ok(serde_json::from_value(value)?)
+    };
+    let expected = vec![
+        item("e", "999.00000")?,
+        item("d", "1661564013000.00000")?,
+        item("c", "1661564013000.00002")?,
+        item("a", "1661564013000.00010")?,
+        item("b", "1661564013000.00010")?,
+        item("a", "1661564013001.00000")?,
+    ];
+    let mut shuffled = expected.clone();
+    shuffled.shuffle(&mut rand::thread_rng());
+    shuffled.sort();
+    assert_eq!(shuffled, expected);
+
+    // Consistent with equality
+    let mut other = expected[0].clone();
+    assert_eq!(expected[0].cmp(&other), std::cmp::Ordering::Equal);
+    other.extra.insert("foo".to_string(), json!(1));
+    assert_ne!(expected[0], other);
+    assert_ne!(expected[0].cmp(&other), std::cmp::Ordering::Equal);
+    Ok(())
+}
+
+/// The builder fills in the optional fields and checks the required ones
+#[test]
+fn input_item_builder() -> Result<()> {
+    let file = |n: u64| AssociatedFile {
+        url: format!("https://www.example.com/{}.mp3", n),
+        length: n,
+        type_str: "audio/mpeg".to_string(),
+    };
+    let builder = InputItem::builder()
+        .title("title-1")
+        .content("content-1")
+        .canonical_url("https://www.example.com/1");
+
+    let item = builder.clone().build()?;
+    assert_eq!(
+        item,
+        InputItem {
+            title: "title-1".to_string(),
+            content: "content-1".to_string(),
+            canonical_url: "https://www.example.com/1".to_string(),
+            author: None,
+            associated_files: None,
+            input_id: None,
+            item_time: None,
+            extra: Default::default(),
+        }
+    );
+
+    let item = builder.clone().file(file(1)).build()?;
+    assert_eq!(item.associated_files, Some(vec![file(1)]));
+
+    let item = builder
+        .clone()
+        .file(file(1))
+        .files([file(2), file(3)])
+        .extra("foo", json!("bar"))
+        .build()?;
+    assert_eq!(item.associated_files, Some(vec![file(1), file(2), file(3)]));
+    assert_eq!(item.extra["foo"], "bar");
+
+    for builder in [
+        builder.clone().title(" "),
+        builder.clone().canonical_url("not a url"),
+        builder.clone().files((1..=11).map(file)),
+    ] {
+        assert!(matches!(
+            builder.build().unwrap_err().kind,
+            Kind::IllegalParameter { .. }
+        ));
+    }
+
+    // The canonical URL is optional
+    let item = InputItem::builder().title("title-1").build()?;
+    assert_eq!(item, InputItem::without_url("title-1", ""));
+    Ok(())
+}
+
+/// Files can be attached by chaining, from a built item or a literal, up to the limit
+#[test]
+fn input_item_attach_file() -> Result<()> {
+    let file = |n: u64| AssociatedFile::new(format!("https://www.example.com/{}.mp3", n), n);
+    let item = InputItem::builder()
+        .title("title-1")
+        .build()?
+        .attach_file(file(1)?)?
+        .attach_files([file(2)?, file(3)?])?;
+    assert_eq!(item.files(), [file(1)?, file(2)?, file(3)?]);
+
+    let mut item = InputItem {
+        title: "title-1".to_string(),
+        content: "content-1".to_string(),
+        canonical_url: "https://www.example.com/1".to_string(),
+        author: None,
+        associated_files: None,
+        input_id: None,
+        item_time: None,
+        extra: Default::default(),
+    };
+    assert!(item.files().is_empty());
+    item.files_mut().push(file(1)?);
+    assert_eq!(item.associated_files, Some(vec![file(1)?]));
+
+    let item = item.attach_files((2..=MAX_FILES_PER_ITEM as u64).map(|n| file(n).unwrap()))?;
+    assert_eq!(item.files().len(), MAX_FILES_PER_ITEM);
+    match item.attach_file(file(11)?).unwrap_err().kind {
+        Kind::IllegalParameter(text) => assert!(text.contains("more than the limit of 10")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// A JSON array of input items is read from a file, and malformed files are errors naming them
+#[test]
+fn input_items_from_json_file() -> Result<()> {
+    let fixtures = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures");
+    let items = InputItem::from_json_file(fixtures.join("input_items.json"))?;
+    assert_eq!(items.len(), 3);
+    assert_eq!(
+        items[0].files(),
+        [AssociatedFile::new("https://www.example.com/1.mp3", 1234)?]
+    );
+    assert_eq!(items[1].associated_files, None);
+    assert_eq!(items[1].input_id.as_deref(), Some("second"));
+    assert_eq!(items[2].canonical_url, "");
+    validate_items(&items)?;
+
+    // NDJSON is not a JSON array
+    let ndjson = fixtures.join("input_items.ndjson");
+    match InputItem::from_json_file(&ndjson).unwrap_err().kind {
+        Kind::Deserialization(s) => assert!(s.starts_with(&ndjson.display().to_string()), "{}", s),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    let missing = InputItem::from_json_file(fixtures.join("missing.json")).unwrap_err();
+    assert!(matches!(missing.kind, Kind::Io(_)), "{}", missing);
+    Ok(())
+}
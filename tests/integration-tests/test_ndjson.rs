@@ -0,0 +1,165 @@
+use crate::{mock_client, mock_feed_item, mock_items_server, PagedFeed, MOCK_FEED_ID};
+use serde_json::json;
+use std::fs::File;
+use std::io::{BufReader, Cursor};
+use std::path::PathBuf;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer};
+use yupdates::errors::{Kind, Result};
+use yupdates::models::{FeedItem, InputItem};
+use yupdates::ndjson::{export_ndjson, import_ndjson, read_input_items};
+
+/// Items survive a round trip, one object per line
+#[test]
+fn ndjson_round_trip() -> Result<()> {
+    let items: Vec<FeedItem> = (1..=3)
+        .map(|n| serde_json::from_value(mock_feed_item(n)).unwrap())
+        .collect();
+    let mut buffer = Vec::new();
+    assert_eq!(export_ndjson(&items, &mut buffer)?, 3);
+    let text = String::from_utf8(buffer).unwrap();
+    assert_eq!(text.lines().count(), 3);
+    assert!(text.lines().all(|line| line.starts_with('{')));
+
+    let imported = import_ndjson(Cursor::new(format!("{}\n", text)))?;
+    assert_eq!(imported, items);
+    Ok(())
+}
+
+/// A malformed line is reported by its line number
+#[test]
+fn ndjson_import_line_number() {
+    let good = mock_feed_item(1).to_string();
+    let text = format!("{}\n\n{}\n{{\"item_id\": 3\n", good, good);
+    match import_ndjson(Cursor::new(text)).unwrap_err().kind {
+        Kind::Deserialization(text) => assert!(text.contains("line 4")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+}
+
+/// Dumping pages through the whole feed, with content, and honors the limit
+#[tokio::test]
+async fn dump_feed_ndjson_pages() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(PagedFeed { count: 25 })
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let mut buffer = Vec::new();
+    assert_eq!(
+        yup.dump_feed_ndjson(MOCK_FEED_ID, &mut buffer, None)
+            .await?,
+        25
+    );
+    let items = import_ndjson(Cursor::new(buffer))?;
+    assert_eq!(items.len(), 25);
+    assert_eq!(items[0].item_id, "item25");
+    assert_eq!(items[24].item_id, "item1");
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0]
+        .url
+        .query()
+        .unwrap()
+        .contains("include_item_content=true"));
+
+    let mut buffer = Vec::new();
+    assert_eq!(
+        yup.dump_feed_ndjson(MOCK_FEED_ID, &mut buffer, Some(12))
+            .await?,
+        12
+    );
+    assert_eq!(import_ndjson(Cursor::new(buffer))?.len(), 12);
+    assert_eq!(server.received_requests().await.unwrap().len(), 5);
+    Ok(())
+}
+
+fn input_items_fixture() -> BufReader<File> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("input_items.ndjson");
+    BufReader::new(File::open(path).unwrap())
+}
+
+/// A malformed line can be skipped, or it can stop the read at its line number
+#[test]
+fn ndjson_read_input_items() {
+    let results: Vec<Result<InputItem>> = read_input_items(input_items_fixture()).collect();
+    assert_eq!(results.len(), 4);
+    match &results[2].as_ref().unwrap_err().kind {
+        Kind::Deserialization(text) => assert!(text.starts_with("NDJSON line 4:"), "{}", text),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+
+    let skipped: Vec<String> = read_input_items(input_items_fixture())
+        .filter_map(|item| item.ok())
+        .map(|item| item.title)
+        .collect();
+    assert_eq!(skipped, ["first", "second", "fourth"]);
+
+    let aborted: Result<Vec<InputItem>> = read_input_items(input_items_fixture()).collect();
+    assert!(matches!(
+        aborted.unwrap_err().kind,
+        Kind::Deserialization(_)
+    ));
+}
+
+/// Items are sent in batches as they are read, and a malformed line stops the call
+#[tokio::test]
+async fn new_items_from_ndjson_batches() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+
+    let lines: String = (1..=12)
+        .map(|n| {
+            let item = json!({
+                "title": format!("title-{}", n),
+                "content": format!("content-{}", n),
+                "canonical_url": format!("https://www.example.com/{}", n),
+            });
+            format!("{}\n", item)
+        })
+        .collect();
+    let summary = yup.new_items_from_ndjson(Cursor::new(lines), 5).await?;
+    assert_eq!(summary.feed_id, MOCK_FEED_ID);
+    assert_eq!(summary.batches_sent, 2);
+    assert_eq!(summary.items_sent, 12);
+    let requests = server.received_requests().await.unwrap();
+    let sizes: Vec<usize> = requests
+        .iter()
+        .map(|r| {
+            serde_json::from_slice::<serde_json::Value>(&r.body).unwrap()["items"]
+                .as_array()
+                .unwrap()
+                .len()
+        })
+        .collect();
+    assert_eq!(sizes, [10, 2]);
+
+    // The malformed line is in the first batch, so nothing is sent
+    match yup
+        .new_items_from_ndjson(input_items_fixture(), 5)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::BatchFailed(0, 0, e) => assert!(e.to_string().contains("NDJSON line 4")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+    match yup
+        .new_items_from_ndjson(Cursor::new("\n\n"), 5)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::IllegalParameter(_) => {}
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
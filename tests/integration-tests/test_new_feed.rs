@@ -0,0 +1,71 @@
+use crate::{mock_client, MOCK_FEED_ID};
+use serde_json::json;
+use wiremock::matchers::{body_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::api::{NewFeedOptions, MAX_FEED_DESCRIPTION_LEN, MAX_FEED_NAME_LEN};
+use yupdates::errors::{Kind, Result};
+
+/// A new feed comes back with its ID and token, and the token stays out of debug output
+#[tokio::test]
+async fn new_feed_created() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/feeds/"))
+        .and(header("X-Auth-Token", "mock-token"))
+        .and(body_json(json!({"name": "Customer 42"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "code": 201,
+            "feed_id": MOCK_FEED_ID,
+            "feed_token": "new-feed-secret",
+            "message": "feed created"
+        })))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let options = NewFeedOptions {
+        name: "Customer 42".to_string(),
+        ..Default::default()
+    };
+    let response = yup.new_feed(&options).await?;
+    assert_eq!(response.typed_feed_id()?.as_str(), MOCK_FEED_ID);
+    assert_eq!(response.feed_token.as_deref(), Some("new-feed-secret"));
+    assert!(!format!("{:?}", response).contains("new-feed-secret"));
+    Ok(())
+}
+
+/// Names and descriptions are checked before anything is sent
+#[tokio::test]
+async fn new_feed_illegal() -> Result<()> {
+    let server = MockServer::start().await;
+    let yup = mock_client(&server);
+
+    let illegal = [
+        NewFeedOptions::default(),
+        NewFeedOptions {
+            name: "é".repeat(MAX_FEED_NAME_LEN + 1),
+            ..Default::default()
+        },
+        NewFeedOptions {
+            name: "Customer 42".to_string(),
+            description: Some("d".repeat(MAX_FEED_DESCRIPTION_LEN + 1)),
+        },
+    ];
+    for options in illegal {
+        let err = yup.new_feed(&options).await.unwrap_err();
+        assert!(
+            matches!(err.kind, Kind::IllegalParameter { .. }),
+            "{:?}",
+            options
+        );
+    }
+    // Multi-byte characters count once
+    let options = NewFeedOptions {
+        name: "é".repeat(MAX_FEED_NAME_LEN),
+        ..Default::default()
+    };
+    let err = yup.new_feed(&options).await.unwrap_err();
+    assert!(matches!(err.kind, Kind::HttpCode(404)), "{:?}", err);
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    Ok(())
+}
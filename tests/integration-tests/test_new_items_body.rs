@@ -0,0 +1,35 @@
+use crate::{mock_client, mock_items_server, random_test_items};
+use serde::Serialize;
+use yupdates::errors::Result;
+use yupdates::models::InputItem;
+
+// The body as it was serialized when it owned a copy of the items
+#[derive(Serialize)]
+struct OwnedBody {
+    items: Vec<InputItem>,
+}
+
+/// Borrowing the items does not change a byte of the request body, and item times are still
+/// normalized
+#[tokio::test]
+async fn new_items_body_unchanged() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let (mut items, _) = random_test_items(3);
+    items[0].item_time = Some("1661564013555.00000".to_string());
+    yup.new_items(&items).await?;
+    let mut expected = vec![serde_json::to_vec(&OwnedBody {
+        items: items.clone(),
+    })
+    .unwrap()];
+
+    items[1].item_time = Some("1661564013555".to_string());
+    yup.new_items(&items).await?;
+    items[1].item_time = Some("1661564013555.00000".to_string());
+    expected.push(serde_json::to_vec(&OwnedBody { items }).unwrap());
+
+    let requests = server.received_requests().await.unwrap();
+    let bodies: Vec<Vec<u8>> = requests.into_iter().map(|r| r.body).collect();
+    assert_eq!(bodies, expected);
+    Ok(())
+}
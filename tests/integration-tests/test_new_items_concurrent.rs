@@ -0,0 +1,93 @@
+use crate::{items_received, mock_client, mock_items_server, random_test_items, MOCK_FEED_ID};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer};
+use yupdates::api::{BatchSummary, MAX_NEW_ITEMS_CONCURRENCY, MIN_PACING};
+use yupdates::errors::{Kind, Result};
+
+/// Batches overlap, and every item is sent exactly once
+#[tokio::test]
+async fn new_items_all_concurrent_sends_all() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID).set_delay(Duration::from_millis(200)))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, suffixes) = random_test_items(45);
+
+    let start = Instant::now();
+    let summary = yup
+        .new_items_all_concurrent(&items, 4, Duration::from_millis(5))
+        .await?;
+    // One at a time, the five batches would take at least a second
+    assert!(start.elapsed() < Duration::from_millis(900));
+    assert_eq!(
+        summary,
+        BatchSummary {
+            feed_id: MOCK_FEED_ID.to_string(),
+            batches_sent: 5,
+            items_sent: 45,
+        }
+    );
+
+    let mut sent = HashSet::new();
+    for request in server.received_requests().await.unwrap() {
+        let body: Value = serde_json::from_slice(&request.body).unwrap();
+        for item in body["items"].as_array().unwrap() {
+            assert!(sent.insert(item["title"].as_str().unwrap().to_string()));
+        }
+    }
+    let expected: HashSet<String> = suffixes.iter().map(|s| format!("title-{}", s)).collect();
+    assert_eq!(sent, expected);
+    Ok(())
+}
+
+/// Concurrency is capped, and pacing has the same floor as the sequential sleep
+#[tokio::test]
+async fn new_items_all_concurrent_illegal() -> Result<()> {
+    let server = MockServer::start().await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(5);
+    for (concurrency, pacing_ms) in [(0, 5), (MAX_NEW_ITEMS_CONCURRENCY + 1, 5), (2, 4)] {
+        let result = yup
+            .new_items_all_concurrent(&items, concurrency, Duration::from_millis(pacing_ms))
+            .await;
+        assert!(matches!(
+            result.unwrap_err().kind,
+            Kind::IllegalParameter { .. }
+        ));
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+    Ok(())
+}
+
+/// Sequential batches pause for the given pacing, which has a floor
+#[tokio::test]
+async fn new_items_all_with_pacing() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(25);
+
+    let result = yup
+        .new_items_all_with_pacing(&items, MIN_PACING - Duration::from_millis(1))
+        .await;
+    assert!(matches!(
+        result.unwrap_err().kind,
+        Kind::IllegalParameter { .. }
+    ));
+    assert!(server.received_requests().await.unwrap().is_empty());
+
+    let start = Instant::now();
+    let feed_id = yup
+        .new_items_all_with_pacing(&items, Duration::from_millis(50))
+        .await?;
+    assert_eq!(feed_id, MOCK_FEED_ID);
+    // Two pauses between three batches
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::{items_received, mock_client, MOCK_FEED_ID};
+use serde_json::Value;
+use std::path::PathBuf;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer};
+use yupdates::api::dedup_items;
+use yupdates::errors::Result;
+use yupdates::models::InputItem;
+
+fn fixture_items() -> Vec<InputItem> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("duplicate_items.json");
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn titles(items: &[InputItem]) -> Vec<&str> {
+    items.iter().map(|item| item.title.as_str()).collect()
+}
+
+/// The first item with a URL is kept, in order, and URLs are compared exactly
+#[test]
+fn dedup_items_keeps_first() {
+    let (kept, skipped) = dedup_items(&fixture_items());
+    assert_eq!(
+        titles(&kept),
+        ["first", "second", "third", "trailing slash"]
+    );
+    assert_eq!(skipped, 3);
+
+    let (kept, skipped) = dedup_items(&kept);
+    assert_eq!(kept.len(), 4);
+    assert_eq!(skipped, 0);
+
+    let (kept, skipped) = dedup_items(&[]);
+    assert!(kept.is_empty());
+    assert_eq!(skipped, 0);
+}
+
+/// Only the deduplicated items are sent
+#[tokio::test]
+async fn new_items_all_dedup_sends_unique() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let (feed_id, skipped) = yup.new_items_all_dedup(&fixture_items(), 5).await?;
+    assert_eq!(feed_id, MOCK_FEED_ID);
+    assert_eq!(skipped, 3);
+
+    let requests = server.received_requests().await.unwrap();
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    let sent: Vec<&str> = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(sent, ["first", "second", "third", "trailing slash"]);
+    Ok(())
+}
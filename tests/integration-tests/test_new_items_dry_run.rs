@@ -0,0 +1,60 @@
+use crate::{mock_client, mock_items_server, random_test_items, MOCK_FEED_ID};
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::errors::{Kind, Result};
+
+/// A dry run checks the token with zero items and returns the feed ID
+#[tokio::test]
+async fn new_items_dry_run_sends_nothing() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(25);
+
+    assert_eq!(yup.new_items_dry_run(&items).await?, MOCK_FEED_ID);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["items"], json!([]));
+    Ok(())
+}
+
+/// Invalid items fail before any call is made
+#[tokio::test]
+async fn new_items_dry_run_invalid() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let (mut items, _) = random_test_items(3);
+    items[1].title = String::new();
+
+    let err = yup.new_items_dry_run(&items).await.unwrap_err();
+    assert!(matches!(err.kind, Kind::IllegalParameter { .. }));
+    assert!(server.received_requests().await.unwrap().is_empty());
+    Ok(())
+}
+
+/// The feed a token maps to comes from posting zero items
+#[tokio::test]
+async fn feed_id_from_token() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+
+    assert_eq!(yup.feed_id().await?, MOCK_FEED_ID);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body, json!({"items": []}));
+
+    // A token that cannot add items
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(
+            ResponseTemplate::new(403).set_body_json(json!({"code": 403, "error": "forbidden"})),
+        )
+        .mount(&server)
+        .await;
+    let err = mock_client(&server).feed_id().await.unwrap_err();
+    assert!(matches!(err.kind, Kind::DetailedHttpCode(403, _)));
+    Ok(())
+}
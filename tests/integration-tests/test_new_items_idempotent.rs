@@ -0,0 +1,53 @@
+use crate::{mock_client, mock_items_server, random_test_items, MOCK_FEED_ID};
+use wiremock::MockServer;
+use yupdates::errors::{Kind, Result};
+use yupdates::IDEMPOTENCY_KEY_HEADER;
+
+/// The key is sent in a header, and sending the same batch again sends the same key
+#[tokio::test]
+async fn new_items_idempotent_sends_key() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(3);
+
+    for _ in 0..2 {
+        let response = yup.new_items_idempotent(&items, "batch-0f3a").await?;
+        assert_eq!(response.feed_id, MOCK_FEED_ID);
+    }
+    yup.new_items(&items).await?;
+
+    let keys: Vec<Option<String>> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| {
+            request
+                .headers
+                .iter()
+                .find(|(name, _)| name.as_str().eq_ignore_ascii_case(IDEMPOTENCY_KEY_HEADER))
+                .map(|(_, values)| values.last().as_str().to_string())
+        })
+        .collect();
+    let key = Some("batch-0f3a".to_string());
+    assert_eq!(keys, vec![key.clone(), key, None]);
+    Ok(())
+}
+
+/// Illegal keys fail before any call is made
+#[tokio::test]
+async fn new_items_idempotent_illegal_key() -> Result<()> {
+    let server = MockServer::start().await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(1);
+
+    for key in ["", "has space", "ключ", &"k".repeat(256)] {
+        let err = yup.new_items_idempotent(&items, key).await.unwrap_err();
+        assert!(matches!(err.kind, Kind::IllegalParameter { .. }), "{}", key);
+    }
+    let (items, _) = random_test_items(11);
+    let err = yup.new_items_idempotent(&items, "key").await.unwrap_err();
+    assert!(matches!(err.kind, Kind::IllegalParameter { .. }));
+    assert!(server.received_requests().await.unwrap().is_empty());
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::{mock_client, mock_items_server, random_test_items, MOCK_FEED_ID};
+use serde_json::{json, Value};
+use yupdates::errors::Result;
+
+/// Only the first 10 items are sent, and the result says how many were dropped
+#[tokio::test]
+async fn new_items_lenient_truncates() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(15);
+
+    let result = yup.new_items_lenient(&items).await?;
+    assert_eq!(result.response.feed_id, MOCK_FEED_ID);
+    assert_eq!((result.sent, result.dropped), (10, 5));
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["items"], json!(&items[..10]));
+
+    let result = yup.new_items_lenient(&items[..3]).await?;
+    assert_eq!((result.sent, result.dropped), (3, 0));
+    Ok(())
+}
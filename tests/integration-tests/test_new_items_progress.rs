@@ -0,0 +1,238 @@
+use crate::{items_received, mock_client, random_test_items, MOCK_FEED_ID};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio_util::sync::CancellationToken;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+use yupdates::api::BatchProgress;
+use yupdates::errors::{Kind, Result};
+
+// Accepts the first `accepted` batches, then fails with a 500
+async fn failing_items_server(accepted: u64) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .up_to_n_times(accepted)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(
+            ResponseTemplate::new(500).set_body_json(json!({"code": 500, "error": "oops"})),
+        )
+        .mount(&server)
+        .await;
+    server
+}
+
+/// Progress is reported at the start and after each batch
+#[tokio::test]
+async fn new_items_all_progress() -> Result<()> {
+    let server = failing_items_server(u64::MAX).await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(25);
+
+    let mut reports = Vec::new();
+    let feed_id = yup
+        .new_items_all_with_progress(&items, 5, |p| reports.push(p))
+        .await?;
+    assert_eq!(feed_id, MOCK_FEED_ID);
+    let progress = |items_sent, batch_index, last_code| BatchProgress {
+        items_sent,
+        total_items: 25,
+        batch_index,
+        total_batches: 3,
+        last_code,
+    };
+    assert_eq!(
+        reports,
+        [
+            progress(0, 0, None),
+            progress(10, 1, Some(200)),
+            progress(20, 2, Some(200)),
+            progress(25, 3, Some(200)),
+        ]
+    );
+    Ok(())
+}
+
+/// A failed batch says how many items were already sent
+#[tokio::test]
+async fn new_items_all_progress_failure() -> Result<()> {
+    let server = failing_items_server(2).await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(35);
+
+    let mut reports = 0;
+    let err = yup
+        .new_items_all_with_progress(&items, 5, |_| reports += 1)
+        .await
+        .unwrap_err();
+    assert_eq!(reports, 3);
+    match err.kind {
+        Kind::BatchFailed(batch_index, items_sent, source) => {
+            assert_eq!((batch_index, items_sent), (2, 20));
+            assert!(matches!(source.kind, Kind::DetailedHttpCode(500, _)));
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// Each batch's response is returned, in order
+#[tokio::test]
+async fn new_items_all_detailed_responses() -> Result<()> {
+    let server = failing_items_server(u64::MAX).await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(25);
+
+    let responses = yup.new_items_all_detailed(&items, 5).await?;
+    assert_eq!(responses.len(), 3);
+    assert!(responses
+        .iter()
+        .all(|r| r.code == 200 && r.feed_id == MOCK_FEED_ID && r.message == "items received"));
+
+    assert!(yup.new_items_all_detailed(&[], 5).await?.is_empty());
+    Ok(())
+}
+
+/// Batches that land in different feeds point at a configuration problem
+#[tokio::test]
+async fn new_items_all_detailed_feed_mismatch() -> Result<()> {
+    let server = failing_items_server(1).await;
+    let other_feed_id = MOCK_FEED_ID.replace('0', "1");
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(&other_feed_id))
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(25);
+
+    match yup
+        .new_items_all_detailed(&items, 5)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::IllegalResult(text) => assert!(text.contains(&other_feed_id)),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    // The run stops at the first mismatch
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    Ok(())
+}
+
+// Accepts every batch, and cancels the token while the second one is in flight
+struct CancelOnSecondBatch {
+    token: CancellationToken,
+    batches: AtomicUsize,
+}
+
+impl Respond for CancelOnSecondBatch {
+    fn respond(&self, _request: &Request) -> ResponseTemplate {
+        if self.batches.fetch_add(1, Ordering::SeqCst) == 1 {
+            self.token.cancel();
+        }
+        items_received(MOCK_FEED_ID)
+    }
+}
+
+/// Cancelling stops before the next batch, and the batch in flight still completes
+#[tokio::test]
+async fn new_items_all_cancelled() -> Result<()> {
+    let server = MockServer::start().await;
+    let token = CancellationToken::new();
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(CancelOnSecondBatch {
+            token: token.clone(),
+            batches: AtomicUsize::new(0),
+        })
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(35);
+
+    match yup
+        .new_items_all_cancellable(&items, 5, token.clone())
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::Cancelled {
+            items_sent,
+            feed_id,
+        } => {
+            assert_eq!(items_sent, 20);
+            assert_eq!(feed_id.as_deref(), Some(MOCK_FEED_ID));
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+    // Already cancelled, nothing is sent
+    match yup
+        .new_items_all_cancellable(&items, 5, token)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::Cancelled {
+            items_sent,
+            feed_id,
+        } => {
+            assert_eq!(items_sent, 0);
+            assert_eq!(feed_id, None);
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+    let feed_id = yup
+        .new_items_all_cancellable(&items[..5], 5, CancellationToken::new())
+        .await?;
+    assert_eq!(feed_id, MOCK_FEED_ID);
+    Ok(())
+}
+
+/// Cancelling from another thread while the first of three batches is in flight sends exactly
+/// 10 items. The mock holds the response until the other thread has cancelled.
+#[tokio::test]
+async fn new_items_all_cancelled_from_thread() -> Result<()> {
+    let server = MockServer::start().await;
+    let token = CancellationToken::new();
+    let responder_token = token.clone();
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(move |_: &Request| {
+            let token = responder_token.clone();
+            std::thread::spawn(move || token.cancel()).join().unwrap();
+            items_received(MOCK_FEED_ID)
+        })
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(25);
+
+    let err = yup
+        .new_items_all_cancellable(&items, 5, token)
+        .await
+        .unwrap_err();
+    match err.kind {
+        Kind::Cancelled {
+            items_sent,
+            feed_id,
+        } => {
+            assert_eq!(items_sent, 10);
+            assert_eq!(feed_id.as_deref(), Some(MOCK_FEED_ID));
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::{items_received, mock_client, random_test_items, MOCK_FEED_ID};
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+use yupdates::api::BulkProgress;
+use yupdates::errors::{Error, Kind, Result};
+
+fn sent_titles(requests: &[Request]) -> Vec<String> {
+    requests
+        .iter()
+        .flat_map(|r| {
+            let body: Value = serde_json::from_slice(&r.body).unwrap();
+            body["items"].as_array().unwrap().clone()
+        })
+        .map(|item| item["title"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// A run that fails on the third of four batches resumes with only the items that were not sent
+#[tokio::test]
+async fn new_items_all_resume_after_failure() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(
+            ResponseTemplate::new(400).set_body_json(json!({"code": 400, "error": "oops"})),
+        )
+        .up_to_n_times(1)
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .with_priority(3)
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(35);
+
+    let err = yup
+        .new_items_all_resume(&items, &BulkProgress::default(), 5)
+        .await
+        .unwrap_err();
+    let expected = BulkProgress {
+        next_index: 20,
+        feed_id: Some(MOCK_FEED_ID.to_string()),
+        batches_sent: 2,
+    };
+    assert_eq!(err.progress, expected);
+    assert!(matches!(err.error.kind, Kind::DetailedHttpCode(400, _)));
+
+    // The progress survives a round trip through JSON, like a save and a restart would need
+    let saved = serde_json::to_string(&err.progress)?;
+    let progress: BulkProgress = serde_json::from_str(&saved)?;
+    server.reset().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .mount(&server)
+        .await;
+    let feed_id = yup.new_items_all_resume(&items, &progress, 5).await?;
+    assert_eq!(feed_id, MOCK_FEED_ID);
+    let expected: Vec<String> = items[20..].iter().map(|i| i.title.clone()).collect();
+    assert_eq!(
+        sent_titles(&server.received_requests().await.unwrap()),
+        expected
+    );
+
+    // Converted to an `Error`, it is a batch failure with the same counts
+    let err: Error = err.into();
+    assert!(matches!(err.kind, Kind::BatchFailed(2, 20, _)), "{}", err);
+    Ok(())
+}
+
+/// Resumed batches must go to the feed that the progress names, and the progress must fit
+#[tokio::test]
+async fn new_items_all_resume_checks() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(
+            "02fb24a4478462a4491067224b66d9a8b2338ddca2738",
+        ))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(15);
+
+    let progress = BulkProgress {
+        next_index: 10,
+        feed_id: Some(MOCK_FEED_ID.to_string()),
+        batches_sent: 1,
+    };
+    let err = yup
+        .new_items_all_resume(&items, &progress, 5)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.error.kind, Kind::IllegalResult(_)), "{}", err);
+    assert_eq!(err.progress, progress);
+
+    let past_the_end = BulkProgress {
+        next_index: 16,
+        ..progress
+    };
+    let err = yup
+        .new_items_all_resume(&items, &past_the_end, 5)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err.error.kind, Kind::IllegalParameter(_)),
+        "{}",
+        err
+    );
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
@@ -0,0 +1,256 @@
+use crate::{items_received, mock_client, random_test_items, MOCK_FEED_ID};
+use serde_json::json;
+use std::time::{Duration, SystemTime};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+use yupdates::api::RetryPolicy;
+use yupdates::errors::{Kind, Result};
+use yupdates::IDEMPOTENCY_KEY_HEADER;
+
+fn idempotency_key(request: &Request) -> Option<&str> {
+    request
+        .headers
+        .iter()
+        .find(|(name, _)| name.as_str().eq_ignore_ascii_case(IDEMPOTENCY_KEY_HEADER))
+        .map(|(_, values)| values.last().as_str())
+}
+
+fn throttled() -> ResponseTemplate {
+    ResponseTemplate::new(429).set_body_json(json!({"code": 429, "error": "slow down"}))
+}
+
+/// A throttled batch is retried until it lands, honoring Retry-After
+#[tokio::test]
+async fn new_items_all_retries_throttled_batch() -> Result<()> {
+    let server = MockServer::start().await;
+    // The first batch is accepted, the second is throttled twice and then accepted
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(throttled().insert_header("Retry-After", "0"))
+        .up_to_n_times(2)
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .with_priority(3)
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(15);
+
+    assert_eq!(yup.new_items_all(&items, 5).await?, MOCK_FEED_ID);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 4);
+    // Exactly three POSTs of the second batch
+    assert_ne!(requests[0].body, requests[1].body);
+    assert_eq!(requests[1].body, requests[2].body);
+    assert_eq!(requests[2].body, requests[3].body);
+    // The attempts of a batch share an idempotency key, so that the server adds its items once
+    let keys: Vec<&str> = requests
+        .iter()
+        .map(|r| idempotency_key(r).unwrap())
+        .collect();
+    assert_ne!(keys[0], keys[1]);
+    assert_eq!(keys[1], keys[2]);
+    assert_eq!(keys[2], keys[3]);
+    Ok(())
+}
+
+/// When the attempts run out, the error says which batch failed and what was already sent
+#[tokio::test]
+async fn new_items_all_retry_exhausted() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(throttled())
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(15);
+    let retry = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::from_millis(10),
+        ..Default::default()
+    };
+
+    let err = yup
+        .new_items_all_with_retry(&items, 5, &retry)
+        .await
+        .unwrap_err();
+    match err.kind {
+        Kind::BatchFailed(batch_index, items_sent, source) => {
+            assert_eq!((batch_index, items_sent), (1, 10));
+            match source.kind {
+                Kind::RateLimited {
+                    retry_after,
+                    message,
+                } => {
+                    assert_eq!(retry_after, None);
+                    assert_eq!(message, "slow down");
+                }
+                e => panic!("unexpected error type: {:?}", e),
+            }
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 4);
+    Ok(())
+}
+
+/// Service unavailable is only retried if the policy says so
+#[tokio::test]
+async fn new_items_all_retry_unavailable() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(5);
+    let retry = RetryPolicy {
+        max_attempts: 2,
+        initial_backoff: Duration::from_millis(10),
+        retry_unavailable: false,
+        ..Default::default()
+    };
+
+    let err = yup
+        .new_items_all_with_retry(&items, 5, &retry)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, Kind::BatchFailed(0, 0, _)));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+    let retry = RetryPolicy {
+        retry_unavailable: true,
+        ..retry
+    };
+    assert!(yup
+        .new_items_all_with_retry(&items, 5, &retry)
+        .await
+        .is_err());
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    Ok(())
+}
+
+/// The retries agree with Error::is_retryable: a bad gateway is retried, a bad request is not
+#[tokio::test]
+async fn new_items_all_retry_is_retryable() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(5);
+    let retry = RetryPolicy {
+        initial_backoff: Duration::from_millis(10),
+        ..Default::default()
+    };
+    assert_eq!(
+        yup.new_items_all_with_retry(&items, 5, &retry).await?,
+        MOCK_FEED_ID
+    );
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(ResponseTemplate::new(400))
+        .mount(&server)
+        .await;
+    let err = mock_client(&server)
+        .new_items_all_with_retry(&items, 5, &retry)
+        .await
+        .unwrap_err();
+    assert!(!err.is_retryable());
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    Ok(())
+}
+
+/// A Retry-After date is honored like a number of seconds, one in the past is no wait at all
+#[tokio::test]
+async fn new_items_all_retry_after_date() -> Result<()> {
+    let server = MockServer::start().await;
+    let past = httpdate::fmt_http_date(SystemTime::now() - Duration::from_secs(60));
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(throttled().insert_header("Retry-After", past.as_str()))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(items_received(MOCK_FEED_ID))
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(5);
+    // Without the Retry-After, the retry would wait for a minute
+    let retry = RetryPolicy {
+        initial_backoff: Duration::from_secs(60),
+        max_backoff: Duration::from_secs(60),
+        ..Default::default()
+    };
+
+    let sent = tokio::time::timeout(
+        Duration::from_secs(10),
+        yup.new_items_all_with_retry(&items, 5, &retry),
+    )
+    .await
+    .expect("the retry did not honor Retry-After");
+    assert_eq!(sent?, MOCK_FEED_ID);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    Ok(())
+}
+
+/// A Retry-After longer than the policy allows fails the batch instead of retrying early
+#[tokio::test]
+async fn new_items_all_retry_after_too_long() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items/"))
+        .respond_with(throttled().insert_header("Retry-After", "120"))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(5);
+
+    let err = yup
+        .new_items_all_with_retry(&items, 5, &RetryPolicy::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, Kind::BatchFailed(0, 0, _)));
+    assert_eq!(err.retry_after(), Some(Duration::from_secs(120)));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    Ok(())
+}
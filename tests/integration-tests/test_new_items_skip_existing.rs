@@ -0,0 +1,100 @@
+//! These tests run against the mock that the `testing` feature exposes, they do not require API
+//! tokens.
+use yupdates::api::{DedupKey, DedupSummary, ItemKey};
+use yupdates::errors::Result;
+use yupdates::models::InputItem;
+use yupdates::testing::{MockYupdates, MOCK_FEED_ID};
+
+fn import_item(n: u64, title: &str) -> InputItem {
+    InputItem {
+        title: title.to_string(),
+        content: format!("content-{}", n),
+        canonical_url: format!("https://www.example.com/{}", n),
+        author: None,
+        associated_files: None,
+        input_id: None,
+        item_time: None,
+        extra: Default::default(),
+    }
+}
+
+/// Running the same 25 item import twice leaves 25 items in the feed
+#[tokio::test]
+async fn new_items_all_skip_existing_twice() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let items: Vec<InputItem> = (1..=25).map(|n| import_item(n, "import")).collect();
+
+    let summary = yup
+        .new_items_all_skip_existing(&items, 100, DedupKey::CanonicalUrl)
+        .await?;
+    assert_eq!(
+        summary,
+        DedupSummary {
+            feed_id: MOCK_FEED_ID.to_string(),
+            submitted: 25,
+            skipped: 0,
+            skipped_keys: vec![],
+        }
+    );
+    assert_eq!(mock.feed_items().len(), 25);
+
+    let summary = yup
+        .new_items_all_skip_existing(&items, 100, DedupKey::CanonicalUrl)
+        .await?;
+    assert_eq!(summary.submitted, 0);
+    assert_eq!(summary.skipped, 25);
+    assert_eq!(
+        summary.skipped_keys[0],
+        ItemKey {
+            title: None,
+            canonical_url: "https://www.example.com/1".to_string(),
+        }
+    );
+    assert_eq!(mock.feed_items().len(), 25);
+    Ok(())
+}
+
+/// With the title in the key, a new title at a known URL is added, and repeats in the input and
+/// items past the lookback are not recognized as such
+#[tokio::test]
+async fn new_items_all_skip_existing_title_and_url() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let first: Vec<InputItem> = (1..=5).map(|n| import_item(n, "first")).collect();
+    yup.new_items_all_skip_existing(&first, 10, DedupKey::TitleAndUrl)
+        .await?;
+
+    let mut items = vec![
+        import_item(1, "first"),
+        import_item(2, "second"),
+        import_item(2, "second"),
+    ];
+    let summary = yup
+        .new_items_all_skip_existing(&items, 10, DedupKey::TitleAndUrl)
+        .await?;
+    assert_eq!(summary.submitted, 1);
+    assert_eq!(
+        summary.skipped_keys,
+        vec![
+            ItemKey {
+                title: Some("first".to_string()),
+                canonical_url: "https://www.example.com/1".to_string(),
+            },
+            ItemKey {
+                title: Some("second".to_string()),
+                canonical_url: "https://www.example.com/2".to_string(),
+            },
+        ]
+    );
+    assert_eq!(mock.feed_items().len(), 6);
+
+    // Item 1 is the oldest of the 6, one further back than the lookback
+    items.truncate(1);
+    let summary = yup
+        .new_items_all_skip_existing(&items, 5, DedupKey::TitleAndUrl)
+        .await?;
+    assert_eq!(summary.submitted, 1);
+    assert_eq!(mock.feed_items().len(), 7);
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::mock_client;
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::api::ERROR_BODY_LIMIT;
+use yupdates::errors::{Kind, Result};
+
+/// The measured latency covers at least the server's response delay
+#[tokio::test]
+async fn ping_timed_latency() -> Result<()> {
+    let server = MockServer::start().await;
+    let delay = Duration::from_millis(100);
+    Mock::given(method("GET"))
+        .and(path("/ping/"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"code": 200, "message": "pong"}))
+                .set_delay(delay),
+        )
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let (response, elapsed) = yup.ping_timed().await?;
+    assert_eq!(response.message, "pong");
+    assert!(elapsed >= delay);
+    assert_eq!(yup.ping().await?.message, "pong");
+    Ok(())
+}
+
+/// A 200 that does not parse includes the (truncated) body, an auth error does not
+#[tokio::test]
+async fn deserialization_error_includes_body() {
+    let server = MockServer::start().await;
+    let long_body = json!({"code": 200, "msg": "x".repeat(3000)});
+    let long_len = long_body.to_string().len();
+    Mock::given(method("GET"))
+        .and(path("/ping/"))
+        .and(header("X-Auth-Token", "short"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"code": 200, "msg": "pong"})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/ping/"))
+        .and(header("X-Auth-Token", "long"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(long_body))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/ping/"))
+        .and(header("X-Auth-Token", "mock-token"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("secret-ish detail"))
+        .mount(&server)
+        .await;
+    let mut yup = mock_client(&server);
+
+    yup.token = "short".to_string();
+    match yup.ping().await.unwrap_err().kind {
+        Kind::Deserialization(text) => {
+            assert!(text.contains(r#"response body: {"code":200,"msg":"pong"}"#))
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+
+    yup.token = "long".to_string();
+    match yup.ping().await.unwrap_err().kind {
+        Kind::Deserialization(text) => {
+            assert!(text.ends_with(&format!("({} more bytes)", long_len - ERROR_BODY_LIMIT)));
+            assert!(text.len() < ERROR_BODY_LIMIT + 200);
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+
+    yup.token = "mock-token".to_string();
+    let e = yup.ping().await.unwrap_err();
+    assert!(matches!(e.kind, Kind::HttpCode(401)));
+    assert!(!e.to_string().contains("secret-ish"));
+}
+
+/// A 200 with a page from a proxy instead of JSON says so, with the first line of the page
+#[tokio::test]
+async fn non_json_response() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/ping/"))
+        .and(header("X-Auth-Token", "html"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "\n<!DOCTYPE html>\n<html><body>Access denied</body></html>",
+            "text/html",
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/ping/"))
+        .and(header("X-Auth-Token", "empty"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let mut yup = mock_client(&server);
+
+    yup.token = "html".to_string();
+    match yup.ping().await.unwrap_err().kind {
+        Kind::Deserialization(text) => assert_eq!(
+            text,
+            "expected JSON but got text/html (possible proxy interception); first line: \
+             <!DOCTYPE html>"
+        ),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+
+    yup.token = "empty".to_string();
+    match yup.ping().await.unwrap_err().kind {
+        Kind::Deserialization(text) => assert!(text.starts_with("expected JSON but got an empty")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+}
+
+/// Timeouts and connection failures get their own error kinds
+#[tokio::test]
+async fn ping_timeout_and_connect_errors() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/ping/"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"code": 200, "message": "pong"}))
+                .set_delay(Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+    let mut yup = mock_client(&server);
+    yup.http_client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+    let err = yup.ping().await.unwrap_err();
+    match &err.kind {
+        Kind::RequestTimeout(e) => assert!(e.is_timeout()),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    assert!(err.to_string().starts_with("Timed out: "));
+    assert!(std::error::Error::source(&err).is_some());
+
+    // Nothing listens on a port that was just released
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    yup.base_url = format!("http://127.0.0.1:{}/", port);
+    let err = yup.ping().await.unwrap_err();
+    assert!(matches!(err.kind, Kind::Connect(_)), "{:?}", err);
+    assert!(err
+        .to_string()
+        .starts_with("Could not connect to the API: "));
+    Ok(())
+}
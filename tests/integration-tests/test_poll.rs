@@ -0,0 +1,141 @@
+use crate::{mock_client, mock_feed_items_response, MOCK_FEED_ID};
+use futures::StreamExt;
+use std::time::Duration;
+use tokio::time::timeout;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::api::ReadOptions;
+use yupdates::errors::{Kind, Result};
+
+/// Two polls with overlapping results yield each item once, oldest first
+#[tokio::test]
+async fn poll_new_items_dedupes() -> Result<()> {
+    let server = MockServer::start().await;
+    let feed_path = format!("/feeds/{}/", MOCK_FEED_ID);
+    Mock::given(method("GET"))
+        .and(path(&feed_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(mock_feed_items_response(&[1, 2])))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(&feed_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(mock_feed_items_response(&[2, 3])))
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let mut poller = yup.poll_new_items(
+        MOCK_FEED_ID,
+        Duration::from_millis(20),
+        &ReadOptions::default(),
+    );
+    let mut titles = Vec::new();
+    // Keep polling for a while to make sure nothing is yielded twice:
+    let _ = timeout(Duration::from_millis(300), async {
+        while let Some(item) = poller.next().await {
+            titles.push(item.unwrap().title);
+        }
+    })
+    .await;
+    assert_eq!(titles, vec!["title-1", "title-2", "title-3"]);
+    assert_eq!(poller.cursor(), Some("1661564013003.00000".to_string()));
+
+    // The first poll has no bound, later polls start after the cursor:
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests.len() > 2);
+    assert!(!requests[0].url.query().unwrap().contains("item_time_after"));
+    assert!(requests[2]
+        .url
+        .query()
+        .unwrap()
+        .contains("item_time_after=1661564013003.00000"));
+    Ok(())
+}
+
+/// More new items than fit on a page between two ticks are all yielded, oldest first
+#[cfg(feature = "testing")]
+#[tokio::test]
+async fn poll_new_items_burst() -> Result<()> {
+    use yupdates::models::FeedItem;
+    use yupdates::testing::MockYupdates;
+
+    let feed_items = |nums: std::ops::RangeInclusive<u64>| -> Vec<FeedItem> {
+        nums.map(|n| serde_json::from_value(crate::mock_feed_item(n)).unwrap())
+            .collect()
+    };
+    let mock = MockYupdates::start().await;
+    mock.add_feed_items(feed_items(1..=3));
+    let yup = mock.client();
+    let mut poller = yup.poll_new_items(
+        MOCK_FEED_ID,
+        Duration::from_millis(20),
+        &ReadOptions::default(),
+    );
+    let mut titles = Vec::new();
+    for _ in 0..3 {
+        titles.push(poller.next().await.unwrap()?.title);
+    }
+
+    // 15 items arrive at once, more than the 10 a page holds
+    mock.add_feed_items(feed_items(4..=18));
+    let _ = timeout(Duration::from_millis(500), async {
+        while let Some(item) = poller.next().await {
+            titles.push(item.unwrap().title);
+        }
+    })
+    .await;
+    let expected: Vec<String> = (1..=18).map(|n| format!("title-{}", n)).collect();
+    assert_eq!(titles, expected);
+    assert_eq!(poller.cursor(), Some("1661564013018.00000".to_string()));
+    Ok(())
+}
+
+/// Wait until the item shows up, or time out with the number of polls
+#[tokio::test]
+async fn wait_for_item_until_visible() -> Result<()> {
+    let server = MockServer::start().await;
+    let feed_path = format!("/feeds/{}/", MOCK_FEED_ID);
+    Mock::given(method("GET"))
+        .and(path(&feed_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(mock_feed_items_response(&[1])))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(&feed_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(mock_feed_items_response(&[1, 2])))
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    // The interval is raised to the minimum, so this takes three polls over ~500ms:
+    let item = yup
+        .wait_for_item(
+            MOCK_FEED_ID,
+            |item| item.title == "title-2",
+            Duration::from_secs(5),
+            Duration::from_millis(1),
+        )
+        .await?;
+    assert_eq!(item.item_id, "item2");
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+    let result = yup
+        .wait_for_item(
+            MOCK_FEED_ID,
+            |item| item.title == "title-3",
+            Duration::from_millis(600),
+            Duration::from_millis(250),
+        )
+        .await;
+    match result.unwrap_err().kind {
+        Kind::Timeout(text) => assert!(text.contains("after 3 polls")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
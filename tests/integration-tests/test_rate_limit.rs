@@ -0,0 +1,77 @@
+use crate::{mock_client, mock_feed_items_response, MOCK_FEED_ID};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+use yupdates::errors::Result;
+use yupdates::rate_limit::RateLimiter;
+
+// Serves reads and records when each request arrived
+async fn timed_server() -> (MockServer, Arc<Mutex<Vec<Instant>>>) {
+    let server = MockServer::start().await;
+    let arrivals = Arc::new(Mutex::new(Vec::new()));
+    let recorded = arrivals.clone();
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(move |_: &Request| {
+            recorded.lock().unwrap().push(Instant::now());
+            ResponseTemplate::new(200).set_body_json(mock_feed_items_response(&[1]))
+        })
+        .mount(&server)
+        .await;
+    (server, arrivals)
+}
+
+/// Reads from many tasks sharing one client are spaced by the limit
+#[tokio::test]
+async fn rate_limit_spaces_requests() -> Result<()> {
+    let (server, arrivals) = timed_server().await;
+    let yup = mock_client(&server).max_requests_per_second(20);
+
+    let started = Instant::now();
+    let tasks: Vec<_> = (0..6)
+        .map(|_| {
+            let yup = yup.clone();
+            tokio::spawn(async move { yup.read_items(MOCK_FEED_ID).await })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap()?;
+    }
+    // The first request goes at once and each of the other 5 waits 50 ms for its token
+    assert!(started.elapsed() >= Duration::from_millis(250));
+    let arrivals = arrivals.lock().unwrap();
+    assert_eq!(arrivals.len(), 6);
+    for pair in arrivals.windows(2) {
+        let gap = pair[1] - pair[0];
+        // Some leeway for when the server gets to the request after it was sent
+        assert!(gap >= Duration::from_millis(40), "{:?}", gap);
+    }
+    Ok(())
+}
+
+/// A burst goes through at once, the requests after it wait, and clients without a limiter do not
+#[tokio::test]
+async fn rate_limit_burst() -> Result<()> {
+    let (server, arrivals) = timed_server().await;
+    let limiter = Arc::new(RateLimiter::per_second(5).with_burst(3));
+    assert_eq!(limiter.interval(), Duration::from_millis(200));
+    let yup = mock_client(&server).with_rate_limiter(limiter);
+
+    let started = Instant::now();
+    for _ in 0..3 {
+        yup.read_items(MOCK_FEED_ID).await?;
+    }
+    assert!(started.elapsed() < Duration::from_millis(150));
+    yup.read_items(MOCK_FEED_ID).await?;
+    assert!(started.elapsed() >= Duration::from_millis(190));
+
+    let unlimited = mock_client(&server);
+    let started = Instant::now();
+    for _ in 0..5 {
+        unlimited.read_items(MOCK_FEED_ID).await?;
+    }
+    assert!(started.elapsed() < Duration::from_millis(150));
+    assert_eq!(arrivals.lock().unwrap().len(), 9);
+    Ok(())
+}
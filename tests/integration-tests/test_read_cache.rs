@@ -0,0 +1,85 @@
+use crate::{mock_client, mock_feed_items_response, MOCK_FEED_ID};
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::api::{Order, ReadOptions};
+use yupdates::cache::ReadCache;
+use yupdates::errors::Result;
+
+async fn mock_feed_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(mock_feed_items_response(&[1, 2])))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn request_count(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+/// Identical reads within the TTL make no requests, different options and bypasses do
+#[tokio::test]
+async fn read_cache_hits_and_bypass() -> Result<()> {
+    let server = mock_feed_server().await;
+    let cache = Arc::new(ReadCache::new(Duration::from_secs(60), 10));
+    let yup = mock_client(&server).with_read_cache(cache.clone());
+
+    let first = yup.read_items(MOCK_FEED_ID).await?;
+    assert_eq!(request_count(&server).await, 1);
+    // Default options are the same read as no options
+    let second = yup
+        .read_items_with_options(MOCK_FEED_ID, &ReadOptions::default())
+        .await?;
+    assert_eq!(request_count(&server).await, 1);
+    assert_eq!(first, second);
+    assert_eq!(cache.len(), 1);
+
+    // The order is applied client-side, so it is part of the key too
+    let oldest = ReadOptions {
+        order: Order::Oldest,
+        ..Default::default()
+    };
+    let reversed = yup.read_items_with_options(MOCK_FEED_ID, &oldest).await?;
+    assert_eq!(request_count(&server).await, 2);
+    assert_eq!(reversed[0].item_id, "item1");
+
+    let bypass = ReadOptions {
+        cache_bypass: true,
+        ..Default::default()
+    };
+    yup.read_items_with_options(MOCK_FEED_ID, &bypass).await?;
+    assert_eq!(request_count(&server).await, 3);
+    yup.read_items(MOCK_FEED_ID).await?;
+    assert_eq!(request_count(&server).await, 3);
+    Ok(())
+}
+
+/// Entries expire after the TTL and the oldest entry is evicted when the cache is full
+#[tokio::test]
+async fn read_cache_ttl_and_bound() -> Result<()> {
+    let server = mock_feed_server().await;
+    let cache = Arc::new(ReadCache::new(Duration::from_millis(200), 2));
+    let yup = mock_client(&server).with_read_cache(cache.clone());
+
+    yup.read_items(MOCK_FEED_ID).await?;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    yup.read_items(MOCK_FEED_ID).await?;
+    assert_eq!(request_count(&server).await, 2);
+
+    for max_items in [1, 2] {
+        let options = ReadOptions {
+            max_items,
+            ..Default::default()
+        };
+        yup.read_items_with_options(MOCK_FEED_ID, &options).await?;
+    }
+    assert_eq!(cache.len(), 2);
+    // The default read was the oldest entry, so it was evicted
+    yup.read_items(MOCK_FEED_ID).await?;
+    assert_eq!(request_count(&server).await, 5);
+    Ok(())
+}
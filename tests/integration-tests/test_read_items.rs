@@ -55,8 +55,9 @@ async fn read_item_parameters() -> Result<()> {
         }
     }
 
-    // Read all of the ones we just added. We can test more queries/bounds in the future with
-    // unique feeds per test (new-feed API).
+    // Read all of the ones we just added. We can test more queries/bounds with a unique feed per
+    // test once these tests have a general token for `new_feed` (the feed-specific token cannot
+    // create feeds).
     let options = ReadOptions {
         max_items: 33,
         ..Default::default()
@@ -112,6 +113,18 @@ async fn read_item_parameters() -> Result<()> {
     Ok(())
 }
 
+/// The feed that the tests add items to is in the account's feed list
+#[tokio::test]
+async fn list_feeds_has_test_feed() -> Result<()> {
+    let (ro_client, feed_client) = test_clients()?;
+    let (input_items, _) = random_test_items(1);
+    let feed_id = feed_client.new_items_all(&input_items, 128).await?;
+
+    let feeds = ro_client.list_feeds().await?;
+    assert!(feeds.iter().any(|feed| feed.feed_id == feed_id));
+    Ok(())
+}
+
 /// Exercise invalid options that are caught client-side
 #[tokio::test]
 async fn illegal_read_items() -> Result<()> {
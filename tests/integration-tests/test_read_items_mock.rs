@@ -0,0 +1,909 @@
+use crate::{mock_client, mock_feed_item, mock_feed_items_response, PagedFeed, MOCK_FEED_ID};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use wiremock::matchers::{header, method, path, path_regex};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+use yupdates::api::{
+    Order, RangeOptions, ReadOptions, ReadOutcome, MAX_COUNTED_ITEMS, MAX_FILTERED_PAGES,
+};
+use yupdates::errors::{Kind, Result};
+use yupdates::models::{FeedId, FeedItem};
+
+async fn mock_feed(nums: &[u64]) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(mock_feed_items_response(nums)))
+        .mount(&server)
+        .await;
+    server
+}
+
+/// The default is newest-first, `Order::Oldest` flips that
+#[tokio::test]
+async fn read_items_order() -> Result<()> {
+    let server = mock_feed(&[1, 2, 3, 4, 5]).await;
+    let yup = mock_client(&server);
+
+    let feed_id = FeedId::new(MOCK_FEED_ID)?;
+    let newest_first = yup.read_items(&feed_id).await?;
+    // The query sent on the wire is the canonical one:
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(
+        requests[0].url.query(),
+        Some(ReadOptions::default().canonical_query_string()?.as_str())
+    );
+    assert_eq!(newest_first.first().unwrap().title, "title-5");
+    assert_eq!(newest_first.last().unwrap().title, "title-1");
+
+    let options = ReadOptions {
+        order: Order::Oldest,
+        ..Default::default()
+    };
+    let oldest_first = yup.read_items_with_options(MOCK_FEED_ID, &options).await?;
+    assert_eq!(oldest_first.first().unwrap().title, "title-1");
+    assert_eq!(oldest_first.last().unwrap().title, "title-5");
+    Ok(())
+}
+
+/// Read into caller-defined types: a subset of fields works, a mismatched type reports the index
+#[tokio::test]
+async fn read_items_as_custom_type() -> Result<()> {
+    #[derive(Deserialize)]
+    struct Article {
+        title: String,
+        canonical_url: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct WrongType {
+        title: u64,
+    }
+
+    let server = mock_feed(&[1, 2, 3]).await;
+    let yup = mock_client(&server);
+
+    let options = ReadOptions::default();
+    let articles: Vec<Article> = yup.read_items_as(MOCK_FEED_ID, &options).await?;
+    assert_eq!(articles.len(), 3);
+    assert_eq!(articles[0].title, "title-3");
+    assert_eq!(articles[2].canonical_url, "https://www.example.com/1");
+
+    let result = yup
+        .read_items_as::<WrongType, _>(MOCK_FEED_ID, &options)
+        .await;
+    match result.unwrap_err().kind {
+        Kind::Deserialization(text) => assert!(text.contains("index 0")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// The index in a deserialization error is the position in the response, deleted items included
+#[tokio::test]
+async fn read_items_error_index_counts_deleted() -> Result<()> {
+    let server = MockServer::start().await;
+    let mut deleted = mock_feed_item(3);
+    deleted["deleted"] = json!(true);
+    let mut malformed = mock_feed_item(2);
+    malformed["title"] = json!(42);
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_items": [deleted, malformed, mock_feed_item(1)],
+        })))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let result = yup
+        .read_items_with_options(MOCK_FEED_ID, &ReadOptions::default())
+        .await;
+    match result.unwrap_err().kind {
+        Kind::Deserialization(text) => assert!(text.contains("index 1"), "{}", text),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// Fields the SDK does not model are still reachable through the raw JSON
+#[tokio::test]
+async fn read_items_raw_extra_fields() -> Result<()> {
+    let server = MockServer::start().await;
+    let mut item = mock_feed_item(1);
+    item["brand_new_field"] = json!("surprise");
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_items": [item],
+            "next_cursor": "abc"
+        })))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let raw = yup
+        .read_items_raw(MOCK_FEED_ID, &ReadOptions::default())
+        .await?;
+    assert_eq!(raw["next_cursor"], "abc");
+    assert_eq!(raw["feed_items"][0]["brand_new_field"], "surprise");
+    assert_eq!(raw["feed_items"][0]["title"], "title-1");
+    Ok(())
+}
+
+/// The full response keeps the code and any top-level fields the SDK does not model
+#[tokio::test]
+async fn read_items_full_response() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_items": [mock_feed_item(2), mock_feed_item(1)],
+            "next_cursor": "abc"
+        })))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let response = yup
+        .read_items_response(MOCK_FEED_ID, &ReadOptions::default())
+        .await?;
+    assert_eq!(response.code, 200);
+    assert_eq!(response.feed_items.len(), 2);
+    assert_eq!(response.extra["next_cursor"], "abc");
+    assert!(!response.extra.contains_key("feed_items"));
+    Ok(())
+}
+
+/// Deleted items are filtered out unless they are requested
+#[tokio::test]
+async fn read_items_include_deleted() -> Result<()> {
+    let server = MockServer::start().await;
+    let mut deleted = mock_feed_item(2);
+    deleted["deleted"] = json!(true);
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_items": [mock_feed_item(3), deleted, mock_feed_item(1)]
+        })))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let items = yup.read_items(MOCK_FEED_ID).await?;
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|item| !item.deleted));
+
+    let options = ReadOptions {
+        include_deleted: true,
+        ..Default::default()
+    };
+    let items = yup.read_items_with_options(MOCK_FEED_ID, &options).await?;
+    assert_eq!(items.len(), 3);
+    assert!(items[1].deleted);
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests[1]
+        .url
+        .query()
+        .unwrap()
+        .contains("include_deleted=true"));
+    Ok(())
+}
+
+/// Items with only some of the fields still deserialize into FeedItem
+#[tokio::test]
+async fn read_items_titles_only() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_items": [
+                {"item_id": "item2", "item_time": "1661564013002.00000", "title": "title-2"},
+                {"item_id": "item1", "item_time": "1661564013001.00000", "title": "title-1"}
+            ]
+        })))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let options = ReadOptions {
+        fields: Some(vec!["title".to_string()]),
+        ..Default::default()
+    };
+    let items = yup.read_items_with_options(MOCK_FEED_ID, &options).await?;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].title, "title-2");
+    assert_eq!(items[0].canonical_url, "");
+    assert_eq!(items[0].content, None);
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests[0]
+        .url
+        .query()
+        .unwrap()
+        .contains("fields=item_id%2Citem_time%2Ctitle"));
+    Ok(())
+}
+
+/// The content shorthand asks for content and enforces the 10 item limit
+#[tokio::test]
+async fn read_items_with_content() -> Result<()> {
+    let server = mock_feed(&[1, 2]).await;
+    let yup = mock_client(&server);
+
+    let items = yup.read_items_with_content(MOCK_FEED_ID).await?;
+    assert_eq!(items[0].content, Some("content-2".to_string()));
+    yup.read_items_with_content_n(MOCK_FEED_ID, 2).await?;
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(
+        requests[0].url.query(),
+        Some("include_item_content=true&max_items=10")
+    );
+    assert_eq!(
+        requests[1].url.query(),
+        Some("include_item_content=true&max_items=2")
+    );
+
+    for max_items in [0, 11] {
+        let result = yup.read_items_with_content_n(MOCK_FEED_ID, max_items).await;
+        match result.unwrap_err().kind {
+            Kind::IllegalParameter(text) => assert!(text.contains("1 to 10 when")),
+            e => panic!("unexpected error type: {:?}", e),
+        }
+    }
+    Ok(())
+}
+
+// Records when each request arrived
+struct ArrivalRecorder {
+    arrivals: Arc<Mutex<Vec<Instant>>>,
+    delay: Duration,
+}
+
+impl Respond for ArrivalRecorder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        self.arrivals.lock().unwrap().push(Instant::now());
+        if request.url.path().ends_with("3/") {
+            return ResponseTemplate::new(500).set_delay(self.delay);
+        }
+        ResponseTemplate::new(200)
+            .set_body_json(mock_feed_items_response(&[1]))
+            .set_delay(self.delay)
+    }
+}
+
+/// Reads across feeds keep their order, fail independently, and respect the concurrency limit
+#[tokio::test]
+async fn read_items_many_bounded() -> Result<()> {
+    let server = MockServer::start().await;
+    let arrivals = Arc::new(Mutex::new(Vec::new()));
+    let delay = Duration::from_millis(200);
+    Mock::given(method("GET"))
+        .and(path_regex("^/feeds/"))
+        .respond_with(ArrivalRecorder {
+            arrivals: arrivals.clone(),
+            delay,
+        })
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let feed_ids: Vec<String> = (0..6).map(|i| format!("{:0>45}", i)).collect();
+    let concurrency = 2;
+    let results = yup
+        .read_items_many(&feed_ids, &ReadOptions::default(), concurrency)
+        .await?;
+    assert_eq!(results.len(), 6);
+    for (i, (feed_id, result)) in results.iter().enumerate() {
+        assert_eq!(feed_id, &feed_ids[i]);
+        assert_eq!(result.is_err(), i == 3);
+    }
+
+    // No more than `concurrency` requests ever arrived within one response delay:
+    let arrivals = arrivals.lock().unwrap().clone();
+    for arrival in arrivals.iter() {
+        let overlapping = arrivals
+            .iter()
+            .filter(|other| *other >= arrival && other.duration_since(*arrival) < delay / 2)
+            .count();
+        assert!(overlapping <= concurrency);
+    }
+
+    assert!(yup
+        .read_items_many(&feed_ids, &ReadOptions::default(), 0)
+        .await
+        .is_err());
+    Ok(())
+}
+
+/// A 304 is "unchanged" rather than an error, and the cached reader sends the last ETag back
+#[tokio::test]
+async fn read_items_conditional_etag() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .and(header("If-None-Match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(mock_feed_items_response(&[]))
+                .insert_header("ETag", "\"v1\""),
+        )
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let options = ReadOptions::default();
+
+    // An empty feed is still Modified, and distinct from NotModified
+    let outcome = yup
+        .read_items_conditional(MOCK_FEED_ID, &options, None)
+        .await?;
+    assert_eq!(
+        outcome,
+        ReadOutcome::Modified {
+            items: vec![],
+            etag: Some("\"v1\"".to_string())
+        }
+    );
+    let outcome = yup
+        .read_items_conditional(MOCK_FEED_ID, &options, Some("\"v1\""))
+        .await?;
+    assert_eq!(outcome, ReadOutcome::NotModified);
+
+    let reader = yup.cached_reader();
+    assert_eq!(reader.etag(MOCK_FEED_ID, &options)?, None);
+    let first = reader.read_items(MOCK_FEED_ID, &options).await?;
+    assert!(matches!(first, ReadOutcome::Modified { .. }));
+    assert_eq!(
+        reader.etag(MOCK_FEED_ID, &options)?,
+        Some("\"v1\"".to_string())
+    );
+    let second = reader.read_items(MOCK_FEED_ID, &options).await?;
+    assert_eq!(second, ReadOutcome::NotModified);
+    // Different options are a different query, so they have no ETag yet
+    let other = ReadOptions {
+        max_items: 5,
+        ..Default::default()
+    };
+    assert_eq!(reader.etag(MOCK_FEED_ID, &other)?, None);
+
+    // So is another cursor: the read sends no ETag and gets the items
+    let older = ReadOptions {
+        item_time_before: Some("1661564013555".to_string()),
+        ..options.clone()
+    };
+    let outcome = reader.read_items(MOCK_FEED_ID, &older).await?;
+    assert!(matches!(outcome, ReadOutcome::Modified { .. }));
+    let requests = server.received_requests().await.unwrap();
+    let last = requests.last().unwrap();
+    assert!(last.url.query().unwrap().contains("item_time_before="));
+    assert!(!last
+        .headers
+        .iter()
+        .any(|(name, _)| name.as_str().eq_ignore_ascii_case("if-none-match")));
+    Ok(())
+}
+
+/// Filtering pages back through the feed and reports what it cost
+#[tokio::test]
+async fn read_items_filtered_paging() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(PagedFeed { count: 25 })
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let even = |item: &FeedItem| item.item_id.as_str().ends_with(['0', '2', '4', '6', '8']);
+
+    // The whole feed: 10 + 10 + 5 items
+    let result = yup
+        .read_items_filtered(MOCK_FEED_ID, &ReadOptions::default(), even, None)
+        .await?;
+    assert_eq!(result.items.len(), 12);
+    assert_eq!(result.items[0].item_id, "item24");
+    assert_eq!(result.items[11].item_id, "item2");
+    assert_eq!(
+        (result.scanned, result.pages, result.exhausted),
+        (25, 3, true)
+    );
+
+    // Stopping early only reads the pages it needs
+    let options = ReadOptions {
+        order: Order::Oldest,
+        ..Default::default()
+    };
+    let result = yup
+        .read_items_filtered(MOCK_FEED_ID, &options, even, Some(6))
+        .await?;
+    let ids: Vec<&str> = result.items.iter().map(|i| i.item_id.as_str()).collect();
+    assert_eq!(
+        ids,
+        ["item14", "item16", "item18", "item20", "item22", "item24"]
+    );
+    assert_eq!(
+        (result.scanned, result.pages, result.exhausted),
+        (20, 2, false)
+    );
+
+    let options = ReadOptions {
+        item_time_after: Some("1661564013001".to_string()),
+        ..Default::default()
+    };
+    assert!(yup
+        .read_items_filtered(MOCK_FEED_ID, &options, even, None)
+        .await
+        .is_err());
+    Ok(())
+}
+
+// The item time of mock item `num`, see mock_feed_item
+fn item_time(num: u64) -> String {
+    format!("{}", 1661564013000 + num)
+}
+
+/// A range pages back from `before` and stops at `after`, both of which are exclusive
+#[tokio::test]
+async fn read_items_in_range_paging() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(PagedFeed { count: 120 })
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let exclusive = RangeOptions::default();
+    let options = ReadOptions {
+        max_items: 50,
+        ..Default::default()
+    };
+
+    // Items 11 to 99 span two pages: 99..=50 and 49..=1
+    let items = yup
+        .read_items_in_range(
+            MOCK_FEED_ID,
+            item_time(10),
+            item_time(100),
+            &options,
+            &exclusive,
+        )
+        .await?;
+    assert_eq!(items.len(), 89);
+    assert_eq!(items[0].item_id, "item99");
+    assert_eq!(items[88].item_id, "item11");
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+    let options = ReadOptions {
+        order: Order::Oldest,
+        ..Default::default()
+    };
+    let items = yup
+        .read_items_in_range(
+            MOCK_FEED_ID,
+            item_time(3),
+            item_time(7),
+            &options,
+            &exclusive,
+        )
+        .await?;
+    let ids: Vec<&str> = items.iter().map(|i| i.item_id.as_str()).collect();
+    assert_eq!(ids, ["item4", "item5", "item6"]);
+
+    // Neighboring bounds leave nothing in between
+    let items = yup
+        .read_items_in_range(
+            MOCK_FEED_ID,
+            item_time(5),
+            item_time(6),
+            &options,
+            &exclusive,
+        )
+        .await?;
+    assert!(items.is_empty());
+    Ok(())
+}
+
+/// The bounds must be in order, and the options may not carry their own item times
+#[tokio::test]
+async fn read_items_in_range_illegal() -> Result<()> {
+    let server = mock_feed(&[1, 2, 3]).await;
+    let yup = mock_client(&server);
+    let defaults = ReadOptions::default();
+    let range = RangeOptions::default();
+    let with_before = ReadOptions {
+        item_time_before: Some(item_time(3)),
+        ..Default::default()
+    };
+    for (after, before, options) in [
+        (item_time(2), item_time(2), &defaults),
+        (item_time(2), item_time(1), &defaults),
+        // Equal once normalized
+        (item_time(2), format!("{}.00000", item_time(2)), &defaults),
+        (item_time(1), item_time(3), &with_before),
+    ] {
+        let result = yup
+            .read_items_in_range(MOCK_FEED_ID, after, before, options, &range)
+            .await;
+        assert!(matches!(
+            result.unwrap_err().kind,
+            Kind::IllegalParameter { .. }
+        ));
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+    Ok(())
+}
+
+/// An inclusive `after` makes the range half open, and the limit keeps the newest items
+#[tokio::test]
+async fn read_items_in_range_inclusive() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(PagedFeed { count: 120 })
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let inclusive = RangeOptions {
+        inclusive_after: true,
+        ..Default::default()
+    };
+    let options = ReadOptions {
+        max_items: 50,
+        ..Default::default()
+    };
+
+    // Items 10 to 99 span two pages: 99..=50 and 49..=1
+    let items = yup
+        .read_items_in_range(
+            MOCK_FEED_ID,
+            item_time(10),
+            item_time(100),
+            &options,
+            &inclusive,
+        )
+        .await?;
+    assert_eq!(items.len(), 90);
+    assert_eq!(items[0].item_id, "item99");
+    assert_eq!(items[89].item_id, "item10");
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+    // Consecutive ranges neither miss nor repeat an item
+    let newer = yup
+        .read_items_in_range(
+            MOCK_FEED_ID,
+            item_time(5),
+            item_time(8),
+            &options,
+            &inclusive,
+        )
+        .await?;
+    let older = yup
+        .read_items_in_range(
+            MOCK_FEED_ID,
+            item_time(2),
+            item_time(5),
+            &options,
+            &inclusive,
+        )
+        .await?;
+    let ids: Vec<&str> = newer
+        .iter()
+        .chain(&older)
+        .map(|i| i.item_id.as_str())
+        .collect();
+    assert_eq!(ids, ["item7", "item6", "item5", "item4", "item3", "item2"]);
+
+    // The limit stops paging once it is reached, before the older page is read
+    let requests = server.received_requests().await.unwrap().len();
+    let options = ReadOptions {
+        max_items: 50,
+        order: Order::Oldest,
+        ..Default::default()
+    };
+    let items = yup
+        .read_items_in_range(
+            MOCK_FEED_ID,
+            item_time(10),
+            item_time(100),
+            &options,
+            &RangeOptions {
+                inclusive_after: true,
+                limit: Some(3),
+            },
+        )
+        .await?;
+    let ids: Vec<&str> = items.iter().map(|i| i.item_id.as_str()).collect();
+    assert_eq!(ids, ["item97", "item98", "item99"]);
+    assert_eq!(
+        server.received_requests().await.unwrap().len(),
+        requests + 1
+    );
+    Ok(())
+}
+
+/// An inclusive range still needs its bounds in order, and a zero limit reads nothing
+#[tokio::test]
+async fn read_items_in_range_inclusive_illegal() -> Result<()> {
+    let server = mock_feed(&[1, 2, 3]).await;
+    let yup = mock_client(&server);
+    let inclusive = RangeOptions {
+        inclusive_after: true,
+        ..Default::default()
+    };
+    let with_after = ReadOptions {
+        item_time_after: Some(item_time(1)),
+        ..Default::default()
+    };
+    for (start, end, options) in [
+        (item_time(2), item_time(2), &ReadOptions::default()),
+        (item_time(3), item_time(1), &ReadOptions::default()),
+        (item_time(1), item_time(3), &with_after),
+    ] {
+        let result = yup
+            .read_items_in_range(MOCK_FEED_ID, start, end, options, &inclusive)
+            .await;
+        assert!(matches!(
+            result.unwrap_err().kind,
+            Kind::IllegalParameter { .. }
+        ));
+    }
+    let none = RangeOptions {
+        limit: Some(0),
+        ..Default::default()
+    };
+    let items = yup
+        .read_items_in_range(
+            MOCK_FEED_ID,
+            item_time(1),
+            item_time(3),
+            &ReadOptions::default(),
+            &none,
+        )
+        .await?;
+    assert!(items.is_empty());
+    assert!(server.received_requests().await.unwrap().is_empty());
+    Ok(())
+}
+
+/// A predicate that never matches stops at the page cap instead of walking all history
+#[tokio::test]
+async fn read_items_filtered_page_cap() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(PagedFeed { count: 5000 })
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    let options = ReadOptions {
+        max_items: 20,
+        ..Default::default()
+    };
+    let result = yup
+        .read_items_filtered(MOCK_FEED_ID, &options, |_| false, Some(1))
+        .await?;
+    assert!(result.items.is_empty());
+    assert_eq!(result.pages, MAX_FILTERED_PAGES);
+    assert_eq!(result.scanned, MAX_FILTERED_PAGES * 20);
+    assert!(!result.exhausted);
+    Ok(())
+}
+
+/// Counting pages back through the whole feed, up to the cap, and skips deleted items
+#[tokio::test]
+async fn count_items_paging() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(PagedFeed { count: 120 })
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    assert_eq!(yup.count_items(MOCK_FEED_ID).await?, 120);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3);
+    let query = requests[0].url.query().unwrap();
+    assert!(
+        query.contains("fields=deleted%2Citem_id%2Citem_time"),
+        "{}",
+        query
+    );
+    assert!(query.contains("max_items=50"), "{}", query);
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(PagedFeed {
+            count: MAX_COUNTED_ITEMS + 100,
+        })
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    assert_eq!(yup.count_items(MOCK_FEED_ID).await?, MAX_COUNTED_ITEMS);
+    assert_eq!(server.received_requests().await.unwrap().len(), 100);
+
+    let server = MockServer::start().await;
+    let mut deleted = mock_feed_item(2);
+    deleted["deleted"] = json!(true);
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_items": [mock_feed_item(3), deleted, mock_feed_item(1)]
+        })))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+    assert_eq!(yup.count_items(MOCK_FEED_ID).await?, 2);
+
+    let err = yup.count_items("not-a-feed").await.unwrap_err();
+    assert!(matches!(err.kind, Kind::IllegalParameter { .. }));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    Ok(())
+}
+
+/// Searching sends `q` with the validated read options, and an empty query is rejected
+#[tokio::test]
+async fn search_items_query() -> Result<()> {
+    let server = mock_feed(&[1, 2]).await;
+    let yup = mock_client(&server);
+
+    let options = ReadOptions {
+        max_items: 5,
+        item_time_before: Some("1661564013003".to_string()),
+        order: Order::Oldest,
+        ..Default::default()
+    };
+    let items = yup
+        .search_items(MOCK_FEED_ID, "release notes", &options)
+        .await?;
+    let titles: Vec<&str> = items.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, ["title-1", "title-2"]);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(
+        requests[0].url.query().unwrap(),
+        "include_item_content=false&item_time_before=1661564013003.00000&max_items=5&q=release+notes"
+    );
+
+    for query in ["", "  "] {
+        let err = yup
+            .search_items(MOCK_FEED_ID, query, &options)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.kind, Kind::IllegalParameter { .. }));
+    }
+    let too_many = ReadOptions {
+        max_items: 51,
+        ..Default::default()
+    };
+    let err = yup
+        .search_items(MOCK_FEED_ID, "release", &too_many)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, Kind::IllegalParameter { .. }));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    Ok(())
+}
+
+/// The direct path returns what the default path does
+#[tokio::test]
+async fn read_items_direct_matches() -> Result<()> {
+    let server = MockServer::start().await;
+    let mut deleted = mock_feed_item(2);
+    deleted["deleted"] = json!(true);
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "code": 200,
+            "feed_items": [mock_feed_item(3), deleted, mock_feed_item(1)]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/feeds/02fb24a4478462a4491067224b66d9a8b2338ddca2738/",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{\"code\": 200}"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/feeds/02fb24a4478462a4491067224b66d9a8b2338ddca2740/",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string("\n <html>Blocked</html>"))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    for options in [
+        ReadOptions::default(),
+        ReadOptions {
+            include_deleted: true,
+            order: Order::Oldest,
+            ..Default::default()
+        },
+    ] {
+        let direct = yup.read_items_direct(MOCK_FEED_ID, &options).await?;
+        let items = yup.read_items_with_options(MOCK_FEED_ID, &options).await?;
+        assert_eq!(direct, items);
+    }
+
+    let options = ReadOptions::default();
+    let other_feed = "02fb24a4478462a4491067224b66d9a8b2338ddca2738";
+    match yup
+        .read_items_direct(other_feed, &options)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::Deserialization(text) => {
+            assert!(text.contains("feed_items"), "{}", text);
+            assert!(text.ends_with("the streamed response body was not kept"));
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    let proxy_feed = "02fb24a4478462a4491067224b66d9a8b2338ddca2740";
+    match yup
+        .read_items_direct(proxy_feed, &options)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::Deserialization(text) => {
+            assert!(
+                text.contains("text/html (possible proxy interception)"),
+                "{}",
+                text
+            )
+        }
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    let unknown_feed = "02fb24a4478462a4491067224b66d9a8b2338ddca2739";
+    match yup
+        .read_items_direct(unknown_feed, &options)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::HttpCode(404) => {}
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// A copy of a client can call another endpoint, the URL gets its trailing slash
+#[tokio::test]
+async fn read_items_at_base_url() -> Result<()> {
+    let production = mock_feed(&[1, 2]).await;
+    let staging = mock_feed(&[3]).await;
+    let yup = mock_client(&production);
+
+    let staging_yup = yup.at_base_url(staging.uri())?;
+    assert_eq!(staging_yup.base_url, format!("{}/", staging.uri()));
+    assert_eq!(staging_yup.token, yup.token);
+    let items = staging_yup.read_items(MOCK_FEED_ID).await?;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].title, "title-3");
+    assert_eq!(yup.read_items(MOCK_FEED_ID).await?.len(), 2);
+
+    for base_url in ["", "staging.example.com/api", "ftp://staging.example.com/"] {
+        match yup.at_base_url(base_url).err().unwrap().kind {
+            Kind::IllegalParameter(_) => {}
+            e => panic!("unexpected error type: {:?}", e),
+        }
+    }
+    Ok(())
+}
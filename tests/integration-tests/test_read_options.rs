@@ -0,0 +1,85 @@
+//! These tests only exercise client-side logic and do not require API tokens.
+use yupdates::api::ReadOptions;
+use yupdates::errors::Result;
+
+/// Golden strings: if one of these changes, caches and request signatures keyed on the query
+/// string will change too, so only update them deliberately.
+#[test]
+fn canonical_query_strings() -> Result<()> {
+    let cases = [
+        (
+            ReadOptions::default(),
+            "include_item_content=false&max_items=10",
+        ),
+        (
+            ReadOptions {
+                include_item_content: true,
+                max_items: 3,
+                ..Default::default()
+            },
+            "include_item_content=true&max_items=3",
+        ),
+        (
+            ReadOptions {
+                item_time_after: Some("1661564013555".to_string()),
+                ..Default::default()
+            },
+            "include_item_content=false&item_time_after=1661564013555.00000&max_items=10",
+        ),
+        (
+            ReadOptions {
+                include_item_content: true,
+                item_time_after: Some("123456.789".to_string()),
+                ..Default::default()
+            },
+            "include_item_content=true&item_time_after=0000000123456.00789&max_items=10",
+        ),
+        (
+            ReadOptions {
+                max_items: 50,
+                item_time_before: Some("1661564013555.00003".to_string()),
+                ..Default::default()
+            },
+            "include_item_content=false&item_time_before=1661564013555.00003&max_items=50",
+        ),
+        (
+            ReadOptions {
+                include_item_content: true,
+                item_time_before: Some("1234".to_string()),
+                ..Default::default()
+            },
+            "include_item_content=true&item_time_before=0000000001234.00000&max_items=10",
+        ),
+        (
+            ReadOptions {
+                include_deleted: true,
+                item_time_after: Some("1234".to_string()),
+                ..Default::default()
+            },
+            "include_deleted=true&include_item_content=false&item_time_after=0000000001234.00000&max_items=10",
+        ),
+        (
+            ReadOptions {
+                fields: Some(vec!["title".to_string(), "item_id".to_string()]),
+                ..Default::default()
+            },
+            "fields=item_id%2Citem_time%2Ctitle&include_item_content=false&max_items=10",
+        ),
+    ];
+    for (options, expected) in cases {
+        assert_eq!(options.canonical_query_string()?, expected);
+    }
+
+    // Invalid options are rejected rather than encoded:
+    let options = ReadOptions {
+        max_items: 0,
+        ..Default::default()
+    };
+    assert!(options.canonical_query_string().is_err());
+    let options = ReadOptions {
+        fields: Some(vec!["titel".to_string()]),
+        ..Default::default()
+    };
+    assert!(options.canonical_query_string().is_err());
+    Ok(())
+}
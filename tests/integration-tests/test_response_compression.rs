@@ -0,0 +1,116 @@
+use crate::{mock_feed_item, MOCK_FEED_ID};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde_json::{json, Value};
+use std::io::Write;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+use yupdates::clients::AsyncYupdatesClient;
+use yupdates::errors::Result;
+
+const WORDS: &str = "the a of to and in that is for it with as was on be by this are at from \
+    release update feed item readers new version support performance server client request \
+    response security fix change team today announce available improved faster users data \
+    project open source community documentation example guide build deploy cloud service api \
+    library language developer tools error handling memory network storage database query \
+    results page content article news week month year first last more most better several";
+
+// A page of 100 items whose content is a few paragraphs of HTML, like full articles
+fn article_page() -> Value {
+    let words: Vec<&str> = WORDS.split_whitespace().collect();
+    let mut rng = StdRng::seed_from_u64(7);
+    let feed_items: Vec<Value> = (1..=100)
+        .rev()
+        .map(|num| {
+            let mut item = mock_feed_item(num);
+            let content: String = (0..6)
+                .map(|_| {
+                    let sentence: Vec<&str> =
+                        (0..80).map(|_| *words.choose(&mut rng).unwrap()).collect();
+                    format!("<p>{}.</p>\n", sentence.join(" "))
+                })
+                .collect();
+            item["content"] = json!(content);
+            item
+        })
+        .collect();
+    json!({ "code": 200, "feed_items": feed_items })
+}
+
+fn gzip(body: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body).unwrap();
+    encoder.finish().unwrap()
+}
+
+// The header's values are split at the commas, join them back
+fn accept_encoding(request: &Request) -> Option<String> {
+    request
+        .headers
+        .iter()
+        .find(|(header, _)| header.as_str().eq_ignore_ascii_case("accept-encoding"))
+        .map(|(_, values)| {
+            let values: Vec<&str> = values.iter().map(|value| value.as_str()).collect();
+            values.join(", ")
+        })
+}
+
+// Gzips the page for the clients that accept it
+struct ArticlePage {
+    plain: Vec<u8>,
+}
+
+impl Respond for ArticlePage {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let response = ResponseTemplate::new(200).insert_header("Content-Type", "application/json");
+        match accept_encoding(request) {
+            Some(encodings) if encodings.contains("gzip") => response
+                .insert_header("Content-Encoding", "gzip")
+                .set_body_raw(gzip(&self.plain), "application/json"),
+            _ => response.set_body_raw(self.plain.clone(), "application/json"),
+        }
+    }
+}
+
+/// A compressed page of articles is a fraction of the plain one, and reads the same
+#[tokio::test]
+async fn response_compression_reads_gzip() -> Result<()> {
+    let plain = serde_json::to_vec(&article_page())?;
+    let compressed = gzip(&plain).len();
+    assert!(
+        compressed * 4 < plain.len(),
+        "{} of {}",
+        compressed,
+        plain.len()
+    );
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ArticlePage { plain })
+        .mount(&server)
+        .await;
+    let uncompressed = AsyncYupdatesClient::builder()
+        .with_compression(false)
+        .build_with_args(server.uri(), "mock-token".to_string())?;
+    let expected = uncompressed.read_items(MOCK_FEED_ID).await?;
+    assert_eq!(expected.len(), 100);
+
+    let yup = AsyncYupdatesClient::builder()
+        .with_compression(true)
+        .build_with_args(server.uri(), "mock-token".to_string())?;
+    assert_eq!(yup.read_items(MOCK_FEED_ID).await?, expected);
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(accept_encoding(&requests[0]), None);
+    let accepted = accept_encoding(&requests[1]).unwrap();
+    assert!(
+        accepted.contains("gzip") && accepted.contains("br"),
+        "{}",
+        accepted
+    );
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//! Conversions to and from the `rss` crate, these do not require API tokens.
+use crate::MOCK_FEED_ID;
+use rss::Channel;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use yupdates::errors::{Kind, Result};
+use yupdates::models::{AssociatedFile, FeedItem, InputItem};
+
+fn fixture_items() -> Vec<rss::Item> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("podcast.rss");
+    let channel = Channel::read_from(BufReader::new(File::open(path).unwrap())).unwrap();
+    channel.items().to_vec()
+}
+
+/// Content comes from content:encoded (CDATA included) or the description, enclosures are files
+#[test]
+fn rss_item_to_input_item() -> Result<()> {
+    let items = fixture_items();
+
+    let episode = InputItem::try_from(&items[0])?;
+    assert_eq!(episode.title, "Episode 42: Fish & Chips");
+    assert_eq!(
+        episode.canonical_url,
+        "https://podcast.example.com/episodes/42"
+    );
+    assert_eq!(
+        episode.content,
+        r#"<p>Show notes with <a href="https://example.com/?a=1&b=2">links</a> & more.</p>"#
+    );
+    assert_eq!(
+        episode.associated_files,
+        Some(vec![AssociatedFile {
+            url: "https://cdn.example.com/ep42.mp3".to_string(),
+            length: 34216300,
+            type_str: "audio/mpeg".to_string(),
+        }])
+    );
+
+    let post = InputItem::try_from(&items[1])?;
+    assert_eq!(post.content, "Just a <b>description</b>");
+    assert_eq!(post.associated_files, None);
+
+    for (item, field) in [(&items[2], "`title`"), (&items[3], "`link`")] {
+        match InputItem::try_from(item).unwrap_err().kind {
+            Kind::IllegalParameter(text) => assert!(text.contains(field)),
+            e => panic!("unexpected error type: {:?}", e),
+        }
+    }
+    Ok(())
+}
+
+/// A feed item becomes an RSS item with its first file as the enclosure
+#[test]
+fn feed_item_to_rss_item() {
+    let feed_item = FeedItem {
+        feed_id: Some(MOCK_FEED_ID.parse().unwrap()),
+        item_id: "item1".into(),
+        input_id: "input1".into(),
+        title: "Fish & Chips".to_string(),
+        content: Some("<p>content</p>".to_string()),
+        canonical_url: "https://www.example.com/1".to_string(),
+        author: None,
+        item_time: "1661564013000.00000".to_string(),
+        item_time_ms: 1661564013000,
+        deleted: false,
+        associated_files: Some(vec![AssociatedFile {
+            url: "https://cdn.example.com/1.mp3".to_string(),
+            length: 100,
+            type_str: "audio/mpeg".to_string(),
+        }]),
+        extra: Default::default(),
+    };
+    let item = rss::Item::from(&feed_item);
+    assert_eq!(item.title(), Some("Fish & Chips"));
+    assert_eq!(item.link(), Some("https://www.example.com/1"));
+    assert_eq!(item.description(), Some("<p>content</p>"));
+    assert_eq!(item.guid().unwrap().value(), "item1");
+    assert!(!item.guid().unwrap().is_permalink());
+    assert_eq!(item.pub_date(), Some("Sat, 27 Aug 2022 01:33:33 GMT"));
+    let enclosure = item.enclosure().unwrap();
+    assert_eq!(enclosure.url(), "https://cdn.example.com/1.mp3");
+    assert_eq!(enclosure.length(), "100");
+    assert_eq!(enclosure.mime_type(), "audio/mpeg");
+
+    // And back again
+    let input = InputItem::try_from(&item).unwrap();
+    assert_eq!(input.title, feed_item.title);
+    assert_eq!(input.associated_files, feed_item.associated_files);
+}
@@ -0,0 +1,80 @@
+use serde_json::json;
+use std::thread;
+use tokio::runtime::Runtime;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::api::YupdatesV0;
+use yupdates::clients::sync::{shared_client_with_args, shutdown_shared};
+use yupdates::errors::{Error, Kind, Result};
+
+fn mock_ping_server(rt: &Runtime) -> MockServer {
+    rt.block_on(async {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/ping/"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"code": 200, "message": "pong"})),
+            )
+            .mount(&server)
+            .await;
+        server
+    })
+}
+
+/// Handles can be created again and again without creating a runtime each time, see the
+/// `shared_client` bench for how long that takes
+#[test]
+fn shared_client_construction() -> Result<()> {
+    let base_url = "http://localhost/".to_string();
+    for _ in 0..20 {
+        let yup = shared_client_with_args(base_url.clone(), "token".to_string())?;
+        assert_eq!(yup.client.base_url, base_url);
+    }
+    Ok(())
+}
+
+/// The base URL is checked and given a trailing slash, like `AsyncYupdatesClient::at_base_url`
+#[test]
+fn shared_client_base_url() -> Result<()> {
+    let yup = shared_client_with_args("http://localhost".to_string(), "token".to_string())?;
+    assert_eq!(yup.client.base_url, "http://localhost/");
+    match shared_client_with_args("localhost".to_string(), "token".to_string()) {
+        Err(Error {
+            kind: Kind::IllegalParameter(text),
+        }) => assert!(text.contains("absolute http(s) URL"), "{}", text),
+        other => panic!(
+            "unexpected result: {:?}",
+            other.map(|yup| yup.client.base_url)
+        ),
+    }
+    Ok(())
+}
+
+/// Many threads can use shared handles at once, and shutdown does not break existing handles
+#[test]
+fn shared_client_concurrency() -> Result<()> {
+    let server_rt = Runtime::new().unwrap();
+    let server = mock_ping_server(&server_rt);
+    let base_url = format!("{}/", server.uri());
+
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let base_url = base_url.clone();
+            thread::spawn(move || -> Result<()> {
+                let yup = shared_client_with_args(base_url, "token".to_string())?;
+                for _ in 0..5 {
+                    assert_eq!(yup.ping()?.message, "pong");
+                }
+                Ok(())
+            })
+        })
+        .collect();
+    for handle in threads {
+        handle.join().unwrap()?;
+    }
+
+    let yup = shared_client_with_args(base_url, "token".to_string())?;
+    shutdown_shared();
+    assert!(yup.ping_bool());
+    Ok(())
+}
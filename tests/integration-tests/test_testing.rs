@@ -0,0 +1,336 @@
+//! These tests exercise the mock that the `testing` feature exposes, they do not require API
+//! tokens.
+use crate::mock_feed_item;
+use yupdates::api::{ItemStatus, ReadOptions};
+use yupdates::errors::{Kind, Result};
+use yupdates::models::{FeedItem, InputItem, ItemPatch};
+use yupdates::testing::{client_with_base_url, MockYupdates, MOCK_FEED_ID};
+
+/// Published items can be read back, newest first, and content is only returned on request
+#[tokio::test]
+async fn mock_publish_and_read() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    assert!(yup.ping_bool().await);
+
+    let items: Vec<InputItem> = (1..=12)
+        .map(|n| InputItem {
+            title: format!("title-{}", n),
+            content: format!("content-{}", n),
+            canonical_url: format!("https://www.example.com/{}", n),
+            author: None,
+            associated_files: None,
+            input_id: None,
+            item_time: None,
+            extra: Default::default(),
+        })
+        .collect();
+    let feed_id = yup.new_items_all(&items, 5).await?;
+    assert_eq!(feed_id, MOCK_FEED_ID);
+    assert_eq!(mock.feed_items().len(), 12);
+
+    let latest = yup.read_items(&feed_id).await?;
+    assert_eq!(latest.len(), 10);
+    assert_eq!(latest[0].title, "title-12");
+    assert_eq!(latest[0].content, None);
+
+    let options = ReadOptions {
+        max_items: 5,
+        include_item_content: true,
+        item_time_before: Some(latest[9].item_time.clone()),
+        ..Default::default()
+    };
+    let older = yup.read_items_with_options(&feed_id, &options).await?;
+    let titles: Vec<&str> = older.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, ["title-2", "title-1"]);
+    assert_eq!(older[1].content, Some("content-1".to_string()));
+    Ok(())
+}
+
+/// A search matches the title or the content, ignoring case
+#[tokio::test]
+async fn mock_search_items() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let seeded: Vec<FeedItem> = (1..=3)
+        .map(|n| serde_json::from_value(mock_feed_item(n)).unwrap())
+        .collect();
+    mock.add_feed_items(seeded);
+    let yup = mock.client();
+
+    let options = ReadOptions::default();
+    let found = yup.search_items(MOCK_FEED_ID, "TITLE-2", &options).await?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].item_id, "item2");
+    let found = yup
+        .search_items(MOCK_FEED_ID, "content-3", &options)
+        .await?;
+    assert_eq!(found[0].item_id, "item3");
+    assert!(yup
+        .search_items(MOCK_FEED_ID, "nothing", &options)
+        .await?
+        .is_empty());
+    Ok(())
+}
+
+/// Seeded items are served, and other feeds are not found
+#[tokio::test]
+async fn mock_seeded_items() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let seeded: FeedItem = serde_json::from_value(mock_feed_item(7)).unwrap();
+    mock.add_feed_items(vec![seeded.clone()]);
+
+    let yup = client_with_base_url(mock.server().uri());
+    let items = yup.read_items(MOCK_FEED_ID).await?;
+    assert_eq!(items[0].item_id, seeded.item_id);
+
+    let other_feed = format!("{:0>45}", 1);
+    match yup.read_items(&other_feed).await.unwrap_err().kind {
+        Kind::DetailedHttpCode(404, _) => {}
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// An item imported with its original 2019 item time is read back with that time
+#[tokio::test]
+async fn mock_backdated_item() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let item = InputItem::builder()
+        .title("From the archive")
+        .content("content")
+        .canonical_url("https://www.example.com/archive")
+        // 2019-06-01T12:00:00Z
+        .item_time("1559390400000")
+        .build()?;
+    let feed_id = yup.new_items(&[item]).await?.feed_id;
+
+    // The client sent the normalized item time
+    let requests = mock.server().received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["items"][0]["item_time"], "1559390400000.00000");
+
+    let items = yup.read_items(&feed_id).await?;
+    assert_eq!(items[0].item_time, "1559390400000.00000");
+    assert_eq!(items[0].item_time_ms, 1559390400000);
+    assert_eq!(
+        items[0].to_string(),
+        "2019-06-01 12:00:00 UTC  From the archive  <https://www.example.com/archive>"
+    );
+
+    // Without an item time the field is left out
+    let (items, _) = crate::random_test_items(1);
+    let body = serde_json::to_value(&items[0]).unwrap();
+    assert!(body.get("item_time").is_none());
+
+    let mut bad = items[0].clone();
+    bad.item_time = Some("yesterday".to_string());
+    match yup.new_items(&[bad]).await.unwrap_err().kind {
+        Kind::IllegalParameter(text) => assert!(text.contains("item 0: `item_time`")),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// An item without a canonical URL is sent without the field and read back with an empty one
+#[tokio::test]
+async fn mock_item_without_url() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let item = InputItem::without_url("Deploy finished", "All services are up");
+    yupdates::api::validate_items(std::slice::from_ref(&item))?;
+    let feed_id = yup.new_items(&[item]).await?.feed_id;
+
+    let requests = mock.server().received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert!(body["items"][0].get("canonical_url").is_none());
+
+    let items = yup.read_items(&feed_id).await?;
+    assert_eq!(items[0].title, "Deploy finished");
+    assert_eq!(items[0].canonical_url, "");
+    assert!(items[0].to_string().ends_with("UTC  Deploy finished"));
+
+    // A response that leaves the field out is read the same way
+    let item: FeedItem = serde_json::from_value(serde_json::json!({
+        "item_id": "item1",
+        "item_time": "1661564013001.00000",
+        "title": "Deploy finished",
+    }))
+    .unwrap();
+    assert_eq!(item.canonical_url, "");
+    Ok(())
+}
+
+/// Backdated items keep their item time, and item times out of range are never sent
+#[tokio::test]
+async fn mock_backdated_import() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let (mut items, _) = crate::random_test_items(2);
+    // 2019-01-01T00:00:00Z, with and without a suffix
+    items[0].item_time = Some("1546300800000.7".to_string());
+    items[1].item_time = Some("1546300800001".to_string());
+    yup.new_items(&items).await?;
+    let read = yup.read_items(MOCK_FEED_ID).await?;
+    assert_eq!(read[0].item_time, "1546300800001.00000");
+    assert_eq!(read[0].item_time_ms, 1546300800001);
+    assert_eq!(read[1].item_time, "1546300800000.00007");
+    assert_eq!(read[1].item_time_ms, 1546300800000);
+
+    items[1].item_time = Some("10000000000000".to_string());
+    let expected = "item 1: `item_time` is not a valid item time ('10000000000000')";
+    match yupdates::api::validate_items(&items).unwrap_err().kind {
+        Kind::IllegalParameter(text) => assert!(text.starts_with(expected), "{}", text),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    match yup.new_items(&items).await.unwrap_err().kind {
+        Kind::IllegalParameter(text) => assert!(text.starts_with(expected), "{}", text),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    // Only the first POST and the read
+    assert_eq!(mock.server().received_requests().await.unwrap().len(), 2);
+    Ok(())
+}
+
+/// Posting an item with the same input ID twice leaves a single item in the feed
+#[tokio::test]
+async fn mock_repeated_input_id() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let (mut items, _) = crate::random_test_items(2);
+    items[0].input_id = Some("post-2019-01.a_1".to_string());
+    yup.new_items(&items[..1]).await?;
+    // A retry of the first batch together with a new item
+    yup.new_items_all(&items, 5).await?;
+
+    let feed_items = mock.feed_items();
+    assert_eq!(feed_items.len(), 2);
+    assert_eq!(feed_items[0].input_id, "post-2019-01.a_1");
+    assert_eq!(feed_items[0].title, items[0].title);
+    assert_eq!(feed_items[1].title, items[1].title);
+
+    for (input_id, expected) in [
+        ("", "item 0: `input_id` must be 1 to 64 characters"),
+        (
+            &"x".repeat(65),
+            "item 0: `input_id` must be 1 to 64 characters",
+        ),
+        ("a b", "item 0: `input_id` may only contain"),
+    ] {
+        items[0].input_id = Some(input_id.to_string());
+        match yup.new_items(&items[..1]).await.unwrap_err().kind {
+            Kind::IllegalParameter(text) => assert!(text.starts_with(expected), "{}", text),
+            e => panic!("unexpected error type: {:?}", e),
+        }
+        assert!(yupdates::api::validate_items(&items[..1]).is_err());
+    }
+    Ok(())
+}
+
+/// A deleted item is read back with `deleted` set, and deleting it again says so
+#[tokio::test]
+async fn mock_delete_item() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let (items, _) = crate::random_test_items(2);
+    yup.new_items(&items).await?;
+    let item_id = yup.read_items(MOCK_FEED_ID).await?[0].item_id.clone();
+
+    let response = yup.delete_item(MOCK_FEED_ID, &item_id).await?;
+    assert_eq!(response.code, 200);
+    assert_eq!(yup.read_items(MOCK_FEED_ID).await?.len(), 1);
+    let options = ReadOptions {
+        include_deleted: true,
+        ..Default::default()
+    };
+    let read = yup.read_items_with_options(MOCK_FEED_ID, &options).await?;
+    assert_eq!(read[0].item_id, item_id);
+    assert!(read[0].deleted);
+    assert!(!read[1].deleted);
+
+    match yup
+        .delete_item(MOCK_FEED_ID, &item_id)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::ItemDeleted(id) => assert_eq!(id, item_id.as_str()),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    match yup
+        .delete_item(MOCK_FEED_ID, "item999")
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::ItemNotFound(id) => assert_eq!(id, "item999"),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// Updating just the title keeps the content and the item time
+#[tokio::test]
+async fn mock_update_item_title() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let seeded: FeedItem = serde_json::from_value(mock_feed_item(1)).unwrap();
+    mock.add_feed_items(vec![seeded.clone()]);
+    let yup = mock.client();
+
+    let patch = ItemPatch {
+        title: Some("title-1, without the typo".to_string()),
+        ..Default::default()
+    };
+    let updated = yup.update_item(MOCK_FEED_ID, "item1", &patch).await?;
+    assert_eq!(updated.title, "title-1, without the typo");
+    assert_eq!(updated.content, seeded.content);
+    assert_eq!(updated.item_time, seeded.item_time);
+    assert_eq!(mock.feed_items(), vec![updated]);
+
+    match yup
+        .update_item(MOCK_FEED_ID, "item2", &patch)
+        .await
+        .unwrap_err()
+        .kind
+    {
+        Kind::ItemNotFound(id) => assert_eq!(id, "item2"),
+        e => panic!("unexpected error type: {:?}", e),
+    }
+    Ok(())
+}
+
+/// The results of every batch are collected, indexed into all of the items
+#[tokio::test]
+async fn mock_new_items_all_with_results() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let items: Vec<InputItem> = (1..=15)
+        .map(|n| InputItem {
+            title: format!("title-{}", n),
+            content: format!("content-{}", n),
+            canonical_url: format!("https://www.example.com/{}", n),
+            author: None,
+            associated_files: None,
+            input_id: Some(format!("source-{}", n % 12)),
+            item_time: None,
+            extra: Default::default(),
+        })
+        .collect();
+    let (feed_id, results) = yup.new_items_all_with_results(&items, 5).await?;
+    assert_eq!(feed_id, MOCK_FEED_ID);
+    let results = results.unwrap();
+    assert_eq!(results.len(), 15);
+    assert!(results.iter().enumerate().all(|(i, r)| r.index == i));
+
+    // Items 13 to 15 repeat the input IDs of items 1 to 3
+    let duplicates: Vec<usize> = results
+        .iter()
+        .filter(|r| r.status == ItemStatus::Duplicate)
+        .map(|r| r.index)
+        .collect();
+    assert_eq!(duplicates, [12, 13, 14]);
+    assert_eq!(results[12].item_id.as_deref(), Some("item1"));
+    assert_eq!(results[11].item_id.as_deref(), Some("item12"));
+    assert_eq!(mock.feed_items().len(), 12);
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use crate::{
+    mock_client, mock_feed_items_response, mock_items_server, random_test_items, MOCK_FEED_ID,
+};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use yupdates::errors::Result;
+
+// Collects every span and event as text, with the field values
+#[derive(Clone, Default)]
+struct Recorder {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+struct LineVisitor(String);
+
+impl Visit for LineVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push_str(&format!(" {}={:?}", field.name(), value));
+    }
+}
+
+impl Recorder {
+    fn push(&self, prefix: &str, record: impl FnOnce(&mut LineVisitor)) {
+        let mut visitor = LineVisitor(prefix.to_string());
+        record(&mut visitor);
+        self.lines.lock().unwrap().push(visitor.0);
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let prefix = format!("span {}", span.metadata().name());
+        self.push(&prefix, |v| span.record(v));
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, values: &Record<'_>) {
+        self.push("record", |v| values.record(v));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let prefix = format!("event {}", event.metadata().level());
+        self.push(&prefix, |v| event.record(v));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// Calls are traced with their endpoint, status, and item counts, but never the token
+#[tokio::test]
+async fn tracing_spans_and_events() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/feeds/{}/", MOCK_FEED_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(mock_feed_items_response(&[1, 2])))
+        .mount(&server)
+        .await;
+    let yup = mock_client(&server);
+
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    yup.read_items(MOCK_FEED_ID).await?;
+    assert!(yup.ping().await.is_err());
+
+    let lines = recorder.lines.lock().unwrap().join("\n");
+    assert!(lines.contains(&format!("feed_id=\"{}\"", MOCK_FEED_ID)));
+    assert!(lines.contains("method=\"GET\""));
+    assert!(lines.contains(&format!("endpoint=\"{}/feeds/", server.uri())));
+    assert!(lines.contains("event DEBUG message=API call succeeded status=200"));
+    assert!(lines.contains("items=2"));
+    assert!(lines.contains("event WARN message=API call failed status=404"));
+    assert!(!lines.contains("mock-token"));
+    Ok(())
+}
+
+/// Dropping items in a lenient call is a warning
+#[tokio::test]
+async fn tracing_new_items_lenient_warns() -> Result<()> {
+    let server = mock_items_server().await;
+    let yup = mock_client(&server);
+    let (items, _) = random_test_items(12);
+
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    yup.new_items_lenient(&items).await?;
+
+    let lines = recorder.lines.lock().unwrap().join("\n");
+    assert!(lines.contains(
+        "event WARN message=too many items for one call, the rest were dropped sent=10 dropped=2"
+    ));
+    Ok(())
+}
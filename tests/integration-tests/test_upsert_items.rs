@@ -0,0 +1,116 @@
+//! These tests run against the mock that the `testing` feature exposes, they do not require API
+//! tokens.
+use yupdates::api::{UpsertMode, UpsertSummary};
+use yupdates::errors::{Kind, Result};
+use yupdates::models::InputItem;
+use yupdates::testing::{MockYupdates, MOCK_FEED_ID};
+
+fn upsert_input(n: u64, title: &str) -> InputItem {
+    InputItem {
+        title: title.to_string(),
+        content: format!("content-{}", n),
+        canonical_url: format!("https://www.example.com/{}", n),
+        author: None,
+        associated_files: None,
+        input_id: None,
+        item_time: None,
+        extra: Default::default(),
+    }
+}
+
+fn new_items_posts(requests: &[wiremock::Request]) -> usize {
+    requests
+        .iter()
+        .filter(|r| r.method == wiremock::http::Method::Post && r.url.path() == "/items/")
+        .filter(|r| !r.body.starts_with(b"{\"items\":[]"))
+        .count()
+}
+
+/// Running the same input twice adds nothing the second time, and repeats in the input are skipped
+#[tokio::test]
+async fn upsert_items_twice() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let mut items: Vec<InputItem> = (1..=15).map(|n| upsert_input(n, "first")).collect();
+    items.push(upsert_input(3, "repeat"));
+
+    let summary = yup
+        .upsert_items(MOCK_FEED_ID, &items, UpsertMode::Skip, 100)
+        .await?;
+    assert_eq!(
+        summary,
+        UpsertSummary {
+            inserted: 15,
+            skipped: 1,
+            updated: 0,
+        }
+    );
+    assert_eq!(mock.feed_items().len(), 15);
+    let posts = new_items_posts(&mock.server().received_requests().await.unwrap());
+
+    let summary = yup
+        .upsert_items(MOCK_FEED_ID, &items, UpsertMode::Skip, 100)
+        .await?;
+    assert_eq!(summary.inserted, 0);
+    assert_eq!(summary.skipped, 16);
+    assert_eq!(mock.feed_items().len(), 15);
+    let received = mock.server().received_requests().await.unwrap();
+    assert_eq!(new_items_posts(&received), posts);
+    Ok(())
+}
+
+/// With [UpsertMode::Update] only the items whose title or content changed are updated
+#[tokio::test]
+async fn upsert_items_update_changed() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let items: Vec<InputItem> = (1..=12).map(|n| upsert_input(n, "first")).collect();
+    yup.upsert_items(MOCK_FEED_ID, &items, UpsertMode::Update, 50)
+        .await?;
+
+    let mut changed = items.clone();
+    changed[1].title = "second".to_string();
+    changed[10].content = "new content".to_string();
+    changed.push(upsert_input(13, "first"));
+    let summary = yup
+        .upsert_items(MOCK_FEED_ID, &changed, UpsertMode::Update, 50)
+        .await?;
+    assert_eq!(
+        summary,
+        UpsertSummary {
+            inserted: 1,
+            skipped: 10,
+            updated: 2,
+        }
+    );
+    let feed_items = mock.feed_items();
+    assert_eq!(feed_items.len(), 13);
+    assert_eq!(feed_items[1].title, "second");
+    assert_eq!(feed_items[10].content.as_deref(), Some("new content"));
+    Ok(())
+}
+
+/// Only `lookback` items are compared, and the token has to add items to the feed that was read
+#[tokio::test]
+async fn upsert_items_lookback_and_feed() -> Result<()> {
+    let mock = MockYupdates::start().await;
+    let yup = mock.client();
+    let items: Vec<InputItem> = (1..=5).map(|n| upsert_input(n, "first")).collect();
+    yup.upsert_items(MOCK_FEED_ID, &items, UpsertMode::Skip, 0)
+        .await?;
+
+    // Items 1 to 3 are older than the two newest items, so they are added again
+    let summary = yup
+        .upsert_items(MOCK_FEED_ID, &items, UpsertMode::Skip, 2)
+        .await?;
+    assert_eq!(summary.inserted, 3);
+    assert_eq!(summary.skipped, 2);
+
+    let other_feed = "02fb24a4478462a4491067224b66d9a8b2338ddca2738";
+    let err = yup
+        .upsert_items(other_feed, &items, UpsertMode::Skip, 0)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, Kind::IllegalResult(_)), "{}", err);
+    Ok(())
+}